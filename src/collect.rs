@@ -1,4 +1,15 @@
-use crate::{ExtendWithCapacity, common::collect_iterator};
+use alloc::{
+  borrow::{Cow, ToOwned},
+  collections::VecDeque,
+};
+
+use crate::{
+  CollectError, ExtendWithCapacity, ExtendWithCapacityAndHasher, ShortInput,
+  common::{
+    bound_or, collect_iterator, collect_iterator_with_hasher,
+    collect_with_exact_capacity, saturating_capacity,
+  },
+};
 
 // Implement CollectWithCapacity trait for Iterator
 impl<I: Iterator> CollectWithCapacity for I {}
@@ -21,6 +32,43 @@ pub trait CollectWithCapacity: Iterator {
   {
     collect_iterator(self, false, |_| capacity)
   }
+
+  /// Collect iterator elements into a collection with pre-allocated
+  /// capacity, clamping absurd requests instead of aborting.
+  ///
+  /// [collect_with_capacity()](CollectWithCapacity::collect_with_capacity)
+  /// hands the request to the container as is, so a value like `usize::MAX`
+  /// that came out of unchecked arithmetic ends in a capacity overflow panic
+  /// or an allocation failure abort. This method first clamps the capacity
+  /// (after the usual max with the size bound) to
+  /// `isize::MAX / size_of::<Self::Item>().max(1)` items, the most any
+  /// allocation can hold.
+  ///
+  /// The clamp only keeps the request within what the allocator can be
+  /// asked for. A clamped request can still exceed the memory of the
+  /// machine, so untrusted capacities should be bounded by the caller as
+  /// well.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectWithCapacity;
+  ///
+  /// let v: Vec<()> = core::iter::repeat_n((), 3).collect_with_capacity_saturating(usize::MAX);
+  /// assert_eq!(v.len(), 3);
+  ///
+  /// let v: Vec<u64> = (0..3).collect_with_capacity_saturating(16);
+  /// assert_eq!(v.capacity(), 16);
+  /// ```
+  fn collect_with_capacity_saturating<T>(self, capacity: usize) -> T
+  where
+    T: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    collect_iterator(self, true, |bound| {
+      saturating_capacity::<Self::Item>(capacity.max(bound))
+    })
+  }
 }
 /// Implement CollectWith trait for Iterator
 impl<I: Iterator> CollectWith for I {}
@@ -82,15 +130,574 @@ pub trait CollectWith: Iterator {
   {
     collect_iterator(self, true, capacity)
   }
+
+  /// Collect borrowed `Copy` elements into a collection of owned elements.
+  ///
+  /// [`ExtendWithCapacity`] is only implemented for owned items, so
+  /// `Vec<_>` or `HashSet<_>` stays inferable for iterators of references
+  /// (it becomes a collection of references). This method is the by-reference
+  /// path: it is `.copied().collect_with(capacity)`, the capacity closure
+  /// receiving the size bound of the borrowing iterator.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::collections::VecDeque;
+  ///
+  /// use collect_with::CollectWith;
+  ///
+  /// let src = [3u32, 1, 4];
+  /// let nums: Vec<u32> = src.iter().collect_copied_with(|n| n);
+  /// assert_eq!(nums, src);
+  ///
+  /// let queue = src
+  ///   .iter()
+  ///   .collect_copied_with::<VecDeque<_>, _>(|n| n + 1);
+  /// assert_eq!(queue.capacity(), 4);
+  /// ```
+  fn collect_copied_with<'a, T, U>(self, capacity: impl FnOnce(usize) -> usize) -> T
+  where
+    T: ExtendWithCapacity<U>,
+    Self: Sized + Iterator<Item = &'a U>,
+    U: Copy + 'a,
+  {
+    collect_iterator(self.copied(), false, capacity)
+  }
+
+  /// Collect borrowed `Copy` key-value pairs, such as those of
+  /// `HashMap::iter()`, into a map of owned pairs.
+  ///
+  /// Like [collect_copied_with()](CollectWith::collect_copied_with), this is
+  /// the by-reference path that keeps `HashMap<_, _>` inferable for the
+  /// plain [collect_with()](CollectWith::collect_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// # #[cfg(feature = "std")] {
+  /// use std::collections::HashMap;
+  ///
+  /// use collect_with::CollectWith;
+  ///
+  /// let src = HashMap::from([(1u8, 'a'), (2, 'b')]);
+  /// let copy: HashMap<u8, char> = src.iter().collect_copied_pairs_with(|n| n);
+  /// assert_eq!(copy, src);
+  /// # }
+  /// ```
+  fn collect_copied_pairs_with<'a, T, K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> T
+  where
+    T: ExtendWithCapacity<(K, V)>,
+    Self: Sized + Iterator<Item = (&'a K, &'a V)>,
+    K: Copy + 'a,
+    V: Copy + 'a,
+  {
+    collect_iterator(self.map(|(k, v)| (*k, *v)), false, capacity)
+  }
+
+  /// Collect elements using the size hint bound directly as the capacity.
+  ///
+  /// When the bound is 0, [`T::FALLBACK_CAPACITY`] is used instead (e.g. 8
+  /// for `Vec`, 16 bytes for `String`).
+  ///
+  /// [`T::FALLBACK_CAPACITY`]: crate::ExtendWithCapacity::FALLBACK_CAPACITY
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectWith;
+  ///
+  /// let v = (0..20)
+  ///   .filter(|x| x % 2 == 0)
+  ///   .collect_hinted::<Vec<_>>(); // size_hint: (0, Some(20))
+  /// assert_eq!(v.capacity(), 20);
+  ///
+  /// let v = core::iter::from_fn(|| None::<u8>).collect_hinted::<Vec<_>>();
+  /// assert_eq!(v.capacity(), 8);
+  /// ```
+  fn collect_hinted<T>(self) -> T
+  where
+    T: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    self.collect_with_fallback(T::FALLBACK_CAPACITY)
+  }
+
+  /// Collect elements using the size hint bound as the exact capacity, or
+  /// `fallback` when the bound is 0.
+  ///
+  /// This is a shorthand for the common closure
+  /// `|bound| if bound == 0 { fallback } else { bound }`.
+  ///
+  /// Unlike
+  /// [collect_with_capacity()](crate::CollectWithCapacity::collect_with_capacity),
+  /// which allocates `max(bound, capacity)`, the fallback is only used when
+  /// the iterator gives no hint at all; a nonzero bound is used unchanged.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectWith;
+  ///
+  /// let v = (0..3).collect_with_fallback::<Vec<_>>(32);
+  /// assert_eq!(v.capacity(), 3);
+  ///
+  /// let s = "a b c"
+  ///   .split(' ')
+  ///   .collect_with_fallback::<String>(32);
+  /// assert_eq!(s, "abc");
+  /// assert_eq!(s.capacity(), 32);
+  /// ```
+  fn collect_with_fallback<T>(self, fallback: usize) -> T
+  where
+    T: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    collect_iterator(self, true, bound_or(fallback))
+  }
+
+  /// Collect elements and guarantee spare capacity for `extra` more elements
+  /// afterwards.
+  ///
+  /// - `extra`
+  ///   - Minimum spare capacity after collection, i.e. `capacity() - len() >=
+  ///     extra`
+  /// - `capacity`
+  ///   - Closure that calculates the initial capacity from the size hint
+  ///
+  /// The spare room is reserved *after* the elements have been added, so the
+  /// guarantee holds even when the size hint was too low. It relies on
+  /// [`ExtendWithCapacity::reserve()`]; containers that keep its default
+  /// no-op implementation get no additional room.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectWith;
+  ///
+  /// let mut v = (0..10).collect_with_headroom::<Vec<_>>(2, |n| n);
+  /// assert!(v.capacity() - v.len() >= 2);
+  ///
+  /// let ptr = v.as_ptr();
+  /// v.extend([100, 101]); // no reallocation
+  /// assert_eq!(ptr, v.as_ptr());
+  /// ```
+  fn collect_with_headroom<T>(
+    self,
+    extra: usize,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> T
+  where
+    T: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    let mut container: T = collect_iterator(self, false, capacity);
+    container.reserve(extra);
+    container
+  }
+
+  /// Collect elements into an inner container and wrap it, e.g. in a
+  /// newtype.
+  ///
+  /// The inner container `C` is collected with the rules of
+  /// [collect_with()](CollectWith::collect_with), then handed to `wrap`; a
+  /// tuple struct constructor works as is.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectWith;
+  ///
+  /// #[derive(Debug, PartialEq)]
+  /// struct TokenList(Vec<&'static str>);
+  ///
+  /// let tokens = "let x = 1".split(' ').collect_wrapped_with(|n| n, TokenList);
+  /// assert_eq!(tokens, TokenList(vec!["let", "x", "=", "1"]));
+  /// ```
+  fn collect_wrapped_with<W, C>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    wrap: impl FnOnce(C) -> W,
+  ) -> W
+  where
+    C: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    wrap(collect_iterator(self, false, capacity))
+  }
+
+  /// Collect elements into a collection sized by a length known from
+  /// elsewhere, such as a header field or a database count.
+  ///
+  /// `len` is used as the exact capacity and the size hint is ignored. In
+  /// debug builds, the number of collected items is checked against `len`
+  /// and a mismatch panics with both numbers; release builds skip the check.
+  /// Use [try_collect_with_known_len()](CollectWith::try_collect_with_known_len)
+  /// to handle a mismatch as an error.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectWith;
+  ///
+  /// let header_count = 3;
+  /// let rows = "a;b;c".split(';').collect_with_known_len::<Vec<_>>(header_count);
+  /// assert_eq!(rows, ["a", "b", "c"]);
+  /// assert_eq!(rows.capacity(), 3);
+  /// ```
+  fn collect_with_known_len<T>(self, len: usize) -> T
+  where
+    T: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    let mut actual = 0;
+    let container = collect_with_exact_capacity(self.inspect(|_| actual += 1), len);
+    debug_assert!(
+      actual == len,
+      "collect_with_known_len: expected {len} items, got {actual}"
+    );
+    container
+  }
+
+  /// Collect elements into a collection sized by a known length, failing
+  /// with [`CollectError::LengthMismatch`] if the item count differs.
+  ///
+  /// The check runs in every build; the collection is dropped on mismatch.
+  /// Longer input is consumed in full, so `actual` is the real count.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::{CollectError, CollectWith};
+  ///
+  /// let ok = (0..4).try_collect_with_known_len::<Vec<_>>(4);
+  /// assert_eq!(ok, Ok(vec![0, 1, 2, 3]));
+  ///
+  /// let short = (0..2).try_collect_with_known_len::<Vec<_>>(4);
+  /// assert_eq!(
+  ///   short,
+  ///   Err(CollectError::LengthMismatch { expected: 4, actual: 2 })
+  /// );
+  /// ```
+  fn try_collect_with_known_len<T>(self, len: usize) -> Result<T, CollectError>
+  where
+    T: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    let mut actual = 0;
+    let container = collect_with_exact_capacity(self.inspect(|_| actual += 1), len);
+    if actual != len {
+      return Err(CollectError::LengthMismatch {
+        expected: len,
+        actual,
+      });
+    }
+    Ok(container)
+  }
+
+  /// Collect elements into a collection sized by an exact count taken in a
+  /// first pass.
+  ///
+  /// Meant for cheap, borrowing iterators with a useless size hint, such as
+  /// [`BTreeMap::range()`](alloc::collections::BTreeMap::range) or filtered
+  /// map entries: the iterator is cloned and counted, then collected with
+  /// that count as the exact capacity. Items are usually `(&K, &V)` pairs,
+  /// which go into any container extendable with references; map them to
+  /// owned pairs first for an owning map.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::collections::BTreeMap;
+  ///
+  /// use collect_with::CollectWith;
+  ///
+  /// let scores = BTreeMap::from([(10, "a"), (20, "b"), (30, "c"), (40, "d")]);
+  /// let mid: Vec<_> = scores.range(15..35).collect_range_exact();
+  /// assert_eq!(mid, [(&20, &"b"), (&30, &"c")]);
+  /// assert_eq!(mid.capacity(), 2);
+  /// ```
+  fn collect_range_exact<T>(self) -> T
+  where
+    T: ExtendWithCapacity<Self::Item>,
+    Self: Sized + Clone,
+  {
+    let len = self.clone().count();
+    collect_with_exact_capacity(self, len)
+  }
+
+  /// Collect elements into the owned variant of a [`Cow`].
+  ///
+  /// `iter.collect_with::<Cow<str>>(..)` and `::<Cow<[T]>>` are not
+  /// supported: [`ExtendWithCapacity`] has [`Extend`] as a supertrait, and
+  /// the standard library implements neither `Extend` for `Cow<str>` nor for
+  /// `Cow<[T]>`. This method replaces them: it collects into `B::Owned`
+  /// (e.g. `String` for `str`, `Vec<T>` for `[T]`) with the usual capacity
+  /// rules and wraps it in [`Cow::Owned`].
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::borrow::Cow;
+  ///
+  /// use collect_with::CollectWith;
+  ///
+  /// let s = "abc".chars().rev().collect_cow_with::<str>(|n| n + 1);
+  /// assert!(matches!(&s, Cow::Owned(o) if o.capacity() >= 4));
+  /// assert_eq!(s, "cba");
+  ///
+  /// let v: Cow<[u8]> = (1..=3).collect_cow_with(|n| n);
+  /// assert_eq!(*v, [1, 2, 3]);
+  /// ```
+  fn collect_cow_with<'b, B>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Cow<'b, B>
+  where
+    B: ToOwned + ?Sized,
+    B::Owned: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    Cow::Owned(collect_iterator(self, false, capacity))
+  }
+
+  /// Like [collect_with()](CollectWith::collect_with), also returning how long
+  /// the collection took.
+  ///
+  /// The measured wall time covers allocating the container and extending it,
+  /// which includes driving the iterator.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::time::Instant;
+  ///
+  /// use collect_with::CollectWith;
+  ///
+  /// let start = Instant::now();
+  /// let (v, elapsed) = (0..1000).collect_with_timed::<Vec<_>>(|n| n);
+  /// assert_eq!(v.len(), 1000);
+  /// assert!(elapsed <= start.elapsed());
+  /// ```
+  #[cfg(feature = "std")]
+  fn collect_with_timed<T>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (T, core::time::Duration)
+  where
+    T: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    let start = std::time::Instant::now();
+    let container = collect_iterator(self, false, capacity);
+    (container, start.elapsed())
+  }
+
+  /// Consumes the whole iterator, keeping only the last `n` items.
+  ///
+  /// Meant for pipelines that run for their side effects, where the trailing
+  /// window is kept for diagnostics. Every item is pulled, so every upstream
+  /// adapter runs; the ring buffer is allocated once with room for `n` items
+  /// and never grows. With `n == 0` nothing is retained or allocated.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectWith;
+  ///
+  /// let mut sent = 0;
+  /// let tail = (1..=10)
+  ///   .inspect(|_| sent += 1)
+  ///   .drain_keep_last_with(3);
+  /// assert_eq!(sent, 10);
+  /// assert_eq!(tail, [8, 9, 10]);
+  /// ```
+  fn drain_keep_last_with(self, n: usize) -> VecDeque<Self::Item>
+  where
+    Self: Sized,
+  {
+    let mut window = VecDeque::with_capacity(n);
+    for item in self {
+      if n == 0 {
+        continue;
+      }
+      if window.len() == n {
+        window.pop_front();
+      }
+      window.push_back(item);
+    }
+    window
+  }
+
+  /// Consumes the whole iterator and returns how many items it yielded.
+  ///
+  /// - `count_hint`
+  ///   - The number of items the caller expects, or `None`. It never changes
+  ///     the result: in debug builds, a `Some` hint that differs from the
+  ///     drained count panics with both numbers; release builds ignore it.
+  ///
+  /// Nothing is allocated; this is the pure-drain counterpart of
+  /// [drain_keep_last_with()](CollectWith::drain_keep_last_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectWith;
+  ///
+  /// let mut log = Vec::new();
+  /// let n = ["a", "b"]
+  ///   .into_iter()
+  ///   .inspect(|s| log.push(*s))
+  ///   .drain_with(Some(2));
+  /// assert_eq!(n, 2);
+  /// assert_eq!(log, ["a", "b"]);
+  ///
+  /// assert_eq!((0..5).filter(|x| x % 2 == 0).drain_with(None), 3);
+  /// ```
+  fn drain_with(self, count_hint: Option<usize>) -> usize
+  where
+    Self: Sized,
+  {
+    let count = self.count();
+    if let Some(expected) = count_hint {
+      debug_assert!(
+        count == expected,
+        "drain_with: expected {expected} items, got {count}"
+      );
+    }
+    count
+  }
+
+  /// Takes exactly `N` items into an array and hands back the rest of the
+  /// iterator.
+  ///
+  /// Only the first `N` items are consumed, so the remainder can be collected
+  /// separately, e.g. a fixed-size header followed by a body. If the iterator
+  /// ends early, the items taken so far are dropped and [`ShortInput`] reports
+  /// how many there were.
+  ///
+  /// The items are written straight into an array of `N` optional slots on
+  /// the stack, so nothing is allocated. On early end, the filled slots are
+  /// dropped along with the array; on success, every slot is unwrapped into
+  /// the returned array.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::{CollectWith, ShortInput};
+  ///
+  /// let fields = "GET /index.html HTTP/1.1 host=a accept=*".split(' ');
+  /// let ([method, path, version], rest) = fields.collect_exact_take().unwrap();
+  /// assert_eq!((method, path, version), ("GET", "/index.html", "HTTP/1.1"));
+  /// assert_eq!(rest.collect_with::<Vec<_>>(|n| n), ["host=a", "accept=*"]);
+  ///
+  /// let err = "GET".split(' ').collect_exact_take::<3>().unwrap_err();
+  /// assert_eq!(err, ShortInput { expected: 3, actual: 1 });
+  /// ```
+  fn collect_exact_take<const N: usize>(
+    mut self,
+  ) -> Result<([Self::Item; N], Self), ShortInput>
+  where
+    Self: Sized,
+  {
+    let mut slots: [Option<Self::Item>; N] = [const { None }; N];
+    for (filled, slot) in slots.iter_mut().enumerate() {
+      match self.next() {
+        Some(item) => *slot = Some(item),
+        None => {
+          return Err(ShortInput {
+            expected: N,
+            actual: filled,
+          });
+        }
+      }
+    }
+    let array = slots.map(|slot| slot.expect("every slot was filled"));
+    Ok((array, self))
+  }
+}
+
+impl<I: Iterator> CollectWithHasher for I {}
+
+/// Trait for collecting into hashed collections built from a given hasher
+/// instance, e.g. a seeded one for reproducible iteration order.
+pub trait CollectWithHasher: Iterator {
+  /// Collect iterator elements into a hashed collection that uses `hasher`.
+  ///
+  /// The capacity rule is that of
+  /// [collect_with()](CollectWith::collect_with): the final capacity is
+  /// `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// # #[cfg(feature = "std")] {
+  /// use std::{collections::HashMap, hash::RandomState};
+  ///
+  /// use collect_with::CollectWithHasher;
+  ///
+  /// let hasher = RandomState::new();
+  /// let a: HashMap<_, _, _> = (0..16)
+  ///   .map(|i| (i, i))
+  ///   .collect_with_hasher(hasher.clone(), |n| n);
+  /// let b: HashMap<_, _, _> = (0..16)
+  ///   .map(|i| (i, i))
+  ///   .collect_with_hasher(hasher, |n| n);
+  /// // Same hasher, same insertions: same iteration order.
+  /// assert!(a.iter().eq(b.iter()));
+  /// # }
+  /// ```
+  fn collect_with_hasher<T>(
+    self,
+    hasher: T::Hasher,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> T
+  where
+    T: ExtendWithCapacityAndHasher<Self::Item>,
+    Self: Sized,
+  {
+    collect_iterator_with_hasher(self, hasher, false, capacity)
+  }
+
+  /// Collect iterator elements into a hashed collection that uses `hasher`,
+  /// passing the closure result through unchanged.
+  ///
+  /// See [collect_with_exact()](CollectWith::collect_with_exact).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// # #[cfg(feature = "std")] {
+  /// use std::{collections::HashSet, hash::RandomState};
+  ///
+  /// use collect_with::CollectWithHasher;
+  ///
+  /// let set: HashSet<_, _> = (0..3).collect_with_hasher_exact(RandomState::new(), |_| 64);
+  /// assert!(set.capacity() >= 64);
+  /// # }
+  /// ```
+  fn collect_with_hasher_exact<T>(
+    self,
+    hasher: T::Hasher,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> T
+  where
+    T: ExtendWithCapacityAndHasher<Self::Item>,
+    Self: Sized,
+  {
+    collect_iterator_with_hasher(self, hasher, true, capacity)
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use alloc::{string::String, vec};
+  use alloc::{string::String, vec, vec::Vec};
 
   use super::*;
+  use crate::{HintBehavior, HintedIter};
 
-  #[ignore]
   #[test]
   fn test_collect_with() {
     let s = [vec!["a"], vec!["b", "c", "d"]]
@@ -103,4 +710,508 @@ mod tests {
     assert_eq!(s.len(), 4);
     assert_eq!(s.capacity(), 8);
   }
+
+  #[test]
+  fn test_collect_with_bound() {
+    // The closure result is raised to the size bound.
+    let v = HintedIter::exact(0..10, 10).collect_with::<Vec<_>>(|_| 2);
+    assert_eq!(v.capacity(), 10);
+
+    // ... unless the exact variant is used.
+    let v = HintedIter::exact(0..10, 10).collect_with_exact::<Vec<_>>(|_| 12);
+    assert_eq!(v.len(), 10);
+    assert_eq!(v.capacity(), 12);
+
+    // An overstated hint is honoured by the default variant only.
+    let v = HintedIter::lying(0..2, (10, Some(10)), HintBehavior::Tracking)
+      .collect_with::<Vec<_>>(|_| 2);
+    assert_eq!(v.capacity(), 10);
+    let v = HintedIter::lying(0..2, (10, Some(10)), HintBehavior::Tracking)
+      .collect_with_exact::<Vec<_>>(|_| 2);
+    assert_eq!(v, [0, 1]);
+    assert_eq!(v.capacity(), 2);
+
+    // A hint that lies low still collects everything.
+    let v = HintedIter::lying(0..10, (1, Some(1)), HintBehavior::Frozen)
+      .collect_with::<Vec<_>>(|n| n);
+    assert_eq!(v, (0..10).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn test_collect_with_capacity() {
+    let v = HintedIter::bounds(0..3, 0, None).collect_with_capacity::<Vec<_>>(7);
+    assert_eq!(v.capacity(), 7);
+
+    let v = HintedIter::exact(0..3, 12).collect_with_capacity::<Vec<_>>(7);
+    assert_eq!(v.capacity(), 12);
+  }
+
+  #[test]
+  fn test_collect_with_fallback() {
+    let v = HintedIter::bounds(0..2, 0, None).collect_with_fallback::<Vec<_>>(6);
+    assert_eq!(v.capacity(), 6);
+
+    let v = HintedIter::exact(0..4, 4).collect_with_fallback::<Vec<_>>(6);
+    assert_eq!(v.capacity(), 4);
+  }
+
+  #[test]
+  fn test_collect_hinted() {
+    let v = HintedIter::bounds(0..2, 0, None).collect_hinted::<Vec<_>>();
+    assert_eq!(v.capacity(), 8);
+  }
+
+  #[test]
+  fn test_collect_with_headroom() {
+    let spare = |v: &Vec<_>| v.capacity() - v.len();
+
+    // accurate hint
+    let v = HintedIter::exact(0..10, 10).collect_with_headroom::<Vec<_>>(3, |n| n);
+    assert_eq!(v.len(), 10);
+    assert!(spare(&v) >= 3);
+
+    // hint that lies low
+    let v = HintedIter::lying(0..10, (2, Some(2)), HintBehavior::Frozen)
+      .collect_with_headroom::<Vec<_>>(3, |n| n);
+    assert_eq!(v.len(), 10);
+    assert!(spare(&v) >= 3);
+
+    // zero hint
+    let v =
+      HintedIter::bounds(0..10, 0, None).collect_with_headroom::<Vec<_>>(5, |n| n);
+    assert_eq!(v.len(), 10);
+    assert!(spare(&v) >= 5);
+  }
+
+  #[test]
+  fn collect_vec_from_references() {
+    let src = [3u32, 1, 4];
+    let copied: Vec<u32> = src
+      .iter()
+      .collect_copied_with(|n| n);
+    assert_eq!(copied, src);
+    assert_eq!(copied.capacity(), 3);
+
+    // Both owned and borrowed items keep `Vec<_>` inferable.
+    let owned = src
+      .into_iter()
+      .collect_with::<Vec<_>>(|n| n + 1);
+    assert_eq!(owned.capacity(), 4);
+
+    let borrowed = src
+      .iter()
+      .collect_with::<Vec<_>>(|n| n);
+    assert_eq!(*borrowed[2], 4);
+    assert_eq!(borrowed.capacity(), 3);
+  }
+
+  #[test]
+  fn collect_deque_and_heap_from_references() {
+    use alloc::collections::{BinaryHeap, VecDeque};
+
+    let src = [2u8, 7, 1];
+    let deque = src
+      .iter()
+      .collect_copied_with::<VecDeque<u8>, _>(|n| n);
+    assert_eq!(deque, [2, 7, 1]);
+    assert!(deque.capacity() >= 3);
+
+    let heap = deque
+      .iter()
+      .collect_copied_with::<BinaryHeap<u8>, _>(|n| n);
+    assert_eq!(heap.into_sorted_vec(), [1, 2, 7]);
+
+    // `_` still infers, as containers of references.
+    let borrowed = src
+      .iter()
+      .collect_with::<VecDeque<_>>(|n| n);
+    assert_eq!(borrowed, [&2, &7, &1]);
+    let heap = src
+      .iter()
+      .collect_with::<BinaryHeap<_>>(|n| n);
+    assert_eq!(heap.peek(), Some(&&7));
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn collect_hashset_from_references() {
+    use std::collections::HashSet;
+
+    let src = HashSet::from([1u8, 2, 3]);
+    let copy = src
+      .iter()
+      .collect_copied_with::<HashSet<u8>, _>(|n| n);
+    assert_eq!(copy, src);
+
+    let borrowed = src
+      .iter()
+      .collect_with::<HashSet<_>>(|n| n);
+    assert!(borrowed.contains(&&2));
+  }
+
+  #[test]
+  fn collect_cow_owned() {
+    use alloc::borrow::Cow;
+
+    let s = ["ab", "cd"]
+      .into_iter()
+      .collect_cow_with::<str>(|n| n);
+    assert!(matches!(s, Cow::Owned(_)));
+    assert_eq!(s, "abcd");
+
+    let v = HintedIter::bounds(0..3, 0, None).collect_cow_with::<[i32]>(|n| n + 5);
+    match v {
+      Cow::Owned(v) => {
+        assert_eq!(v, [0, 1, 2]);
+        assert_eq!(v.capacity(), 5);
+      }
+      Cow::Borrowed(_) => panic!("expected an owned Cow"),
+    }
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn collect_with_timed_unchanged_result() {
+    use std::collections::HashSet;
+
+    let start = std::time::Instant::now();
+    let (set, elapsed) = (0..50_000u32)
+      .map(|n| n.wrapping_mul(2_654_435_761))
+      .collect_with_timed::<HashSet<_>>(|n| n);
+    assert!(elapsed <= start.elapsed());
+    let plain = (0..50_000u32)
+      .map(|n| n.wrapping_mul(2_654_435_761))
+      .collect_with::<HashSet<_>>(|n| n);
+    assert_eq!(set, plain);
+  }
+
+  #[test]
+  fn drain_keep_last_window() {
+    let mut seen = 0;
+    let tail = (0..100)
+      .inspect(|_| seen += 1)
+      .drain_keep_last_with(4);
+    assert_eq!(seen, 100);
+    assert_eq!(tail, [96, 97, 98, 99]);
+    assert!(tail.capacity() >= 4);
+
+    // Fewer items than the window keeps them all.
+    let tail = (0..2).drain_keep_last_with(5);
+    assert_eq!(tail, [0, 1]);
+
+    let mut seen = 0;
+    let tail = (0..7)
+      .inspect(|_| seen += 1)
+      .drain_keep_last_with(0);
+    assert!(tail.is_empty());
+    assert_eq!(seen, 7);
+  }
+
+  #[test]
+  fn drain_with_counts_and_runs_side_effects() {
+    let mut sum = 0;
+    let n = (1..=5)
+      .inspect(|x| sum += x)
+      .drain_with(Some(5));
+    assert_eq!(n, 5);
+    assert_eq!(sum, 15);
+    assert_eq!(core::iter::empty::<()>().drain_with(None), 0);
+
+    // The hint is checked, never added to the count.
+    let n = HintedIter::lying(0..3, (9, Some(9)), HintBehavior::Frozen)
+      .drain_with(Some(3));
+    assert_eq!(n, 3);
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "drain_with: expected 4 items, got 2")]
+  fn drain_with_wrong_hint_panics() {
+    (0..2).drain_with(Some(4));
+  }
+
+  #[test]
+  fn collect_exact_take_keeps_rest() {
+    let (head, rest) = (1..=6)
+      .collect_exact_take::<4>()
+      .unwrap();
+    assert_eq!(head, [1, 2, 3, 4]);
+    assert!(rest.eq(5..=6));
+
+    let (empty, rest) = (0..2)
+      .collect_exact_take::<0>()
+      .unwrap();
+    assert_eq!(empty, [0; 0]);
+    assert_eq!(rest.len(), 2);
+
+    let err = (0..3)
+      .collect_exact_take::<5>()
+      .unwrap_err();
+    assert_eq!(
+      err,
+      ShortInput {
+        expected: 5,
+        actual: 3
+      }
+    );
+  }
+
+  #[test]
+  fn collect_exact_take_drops_every_item() {
+    use alloc::rc::Rc;
+    use core::cell::Cell;
+
+    struct Counted(Rc<Cell<usize>>);
+    impl Drop for Counted {
+      fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+      }
+    }
+
+    let drops = Rc::new(Cell::new(0));
+    let make = |n| (0..n).map(|_| Counted(Rc::clone(&drops)));
+
+    // Short input: the partially filled prefix is dropped with the error.
+    assert!(
+      make(2)
+        .collect_exact_take::<3>()
+        .is_err()
+    );
+    assert_eq!(drops.get(), 2);
+
+    // Exact fit: nothing is dropped until the caller drops the parts.
+    drops.set(0);
+    let (head, rest) = make(5)
+      .collect_exact_take::<3>()
+      .unwrap();
+    assert_eq!(drops.get(), 0);
+    drop(head);
+    assert_eq!(drops.get(), 3);
+    assert_eq!(rest.count(), 2);
+    assert_eq!(drops.get(), 5);
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn collect_with_hasher_uses_instance() {
+    use alloc::rc::Rc;
+    use core::{
+      cell::Cell,
+      hash::{BuildHasher, Hasher},
+    };
+    use std::{
+      collections::{HashMap, HashSet},
+      hash::DefaultHasher,
+    };
+
+    /// Seeds a `DefaultHasher` and counts how often it is built.
+    #[derive(Clone)]
+    struct Seeded {
+      seed: u64,
+      builds: Rc<Cell<usize>>,
+    }
+    impl BuildHasher for Seeded {
+      type Hasher = DefaultHasher;
+
+      fn build_hasher(&self) -> DefaultHasher {
+        self
+          .builds
+          .set(self.builds.get() + 1);
+        let mut h = DefaultHasher::new();
+        h.write_u64(self.seed);
+        h
+      }
+    }
+
+    let builds = Rc::new(Cell::new(0));
+    let hasher = Seeded {
+      seed: 7,
+      builds: Rc::clone(&builds),
+    };
+    let pairs = || (0..32).map(|i| (i, i * 2));
+
+    let a: HashMap<_, _, _> = pairs().collect_with_hasher(hasher.clone(), |n| n);
+    let b: HashMap<_, _, _> =
+      HintedIter::bounds(pairs(), 0, None).collect_with_hasher_exact(hasher, |_| 32);
+    // One hash per insertion, for each map.
+    assert!(builds.get() >= 64);
+    // Equal capacities and a shared seed give the same iteration order.
+    assert!(a.iter().eq(b.iter()));
+    assert!(b.capacity() >= 32);
+
+    let set: HashSet<_, _> = [1, 1, 2]
+      .into_iter()
+      .collect_with_hasher(
+        Seeded {
+          seed: 1,
+          builds: Rc::new(Cell::new(0)),
+        },
+        |n| n,
+      );
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.hasher().seed, 1);
+  }
+
+  #[cfg(all(feature = "ahash", feature = "indexmap"))]
+  #[test]
+  fn collect_with_hasher_ahash_and_indexmap() {
+    let seeded = || ahash::RandomState::with_seeds(1, 2, 3, 4);
+
+    let a: ahash::AHashMap<_, _> = (0..32)
+      .map(|i| (i, ()))
+      .collect_with_hasher(seeded(), |n| n);
+    let b: ahash::AHashMap<_, _> = (0..32)
+      .map(|i| (i, ()))
+      .collect_with_hasher(seeded(), |n| n);
+    assert!(a.keys().eq(b.keys()));
+
+    let set: ahash::AHashSet<_> = "abc"
+      .chars()
+      .collect_with_hasher(seeded(), |n| n);
+    assert_eq!(set.len(), 3);
+
+    let map: indexmap::IndexMap<_, _, _> = ('a'..='c')
+      .zip(0..)
+      .collect_with_hasher_exact(seeded(), |n| n);
+    assert_eq!(map.capacity(), 3);
+    let set: indexmap::IndexSet<_, _> = (0..3).collect_with_hasher(seeded(), |n| n);
+    assert_eq!(set.as_slice(), &[0, 1, 2][..]);
+  }
+
+  #[test]
+  fn collect_with_capacity_saturating_clamps() {
+    assert_eq!(
+      saturating_capacity::<[u8; 16]>(usize::MAX),
+      isize::MAX as usize / 16
+    );
+    assert_eq!(saturating_capacity::<()>(usize::MAX), isize::MAX as usize);
+    assert_eq!(saturating_capacity::<[u8; 16]>(64), 64);
+
+    let v: Vec<()> = (0..4)
+      .map(|_| ())
+      .collect_with_capacity_saturating(usize::MAX);
+    assert_eq!(v.len(), 4);
+
+    let v: Vec<[u8; 16]> = (0..4)
+      .map(|_| [0; 16])
+      .collect_with_capacity_saturating(32);
+    assert_eq!(v.len(), 4);
+    assert_eq!(v.capacity(), 32);
+
+    // The size bound still wins over a smaller request.
+    let v: Vec<u8> = (0..10).collect_with_capacity_saturating(2);
+    assert_eq!(v.capacity(), 10);
+  }
+
+  #[test]
+  fn collect_with_known_len_matching() {
+    let v: Vec<_> = HintedIter::bounds(0..5, 0, None).collect_with_known_len(5);
+    assert_eq!(v, [0, 1, 2, 3, 4]);
+    assert_eq!(v.capacity(), 5);
+
+    let v: Vec<_> = HintedIter::lying(0..5, (100, Some(100)), HintBehavior::Frozen)
+      .collect_with_known_len(5);
+    assert_eq!(v.capacity(), 5);
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "expected 5 items, got 3")]
+  fn collect_with_known_len_shorter_panics() {
+    let _: Vec<_> = (0..3).collect_with_known_len(5);
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "expected 2 items, got 4")]
+  fn collect_with_known_len_longer_panics() {
+    let _: Vec<_> = (0..4).collect_with_known_len(2);
+  }
+
+  #[test]
+  fn collect_range_exact_from_btreemap() {
+    use alloc::collections::BTreeMap;
+
+    let map: BTreeMap<u32, String> = (0..100)
+      .map(|i| (i * 10, alloc::format!("v{i}")))
+      .collect();
+    let range = map.range(250..=500);
+    // The range does not know its length up front.
+    assert_eq!(range.size_hint(), (0, None));
+
+    let borrowed: Vec<(&u32, &String)> = range.collect_range_exact();
+    assert_eq!(borrowed.len(), 26);
+    assert_eq!(borrowed.capacity(), 26);
+    assert_eq!(borrowed[0], (&250, &String::from("v25")));
+
+    let empty: Vec<_> = map
+      .range(5..9)
+      .collect_range_exact();
+    assert_eq!(empty.capacity(), 0);
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn collect_range_exact_into_owned_hashmap() {
+    use alloc::collections::BTreeMap;
+    use std::collections::HashMap;
+
+    let map: BTreeMap<&str, u8> =
+      [("ant", 1), ("bee", 2), ("cat", 3), ("dog", 4)].into();
+    let owned: HashMap<String, u8> = map
+      .range("b".."d")
+      .map(|(k, v)| (String::from(*k), *v))
+      .collect_range_exact();
+    assert_eq!(owned.len(), 2);
+    assert_eq!(owned["cat"], 3);
+    assert!(owned.capacity() >= 2);
+  }
+
+  #[test]
+  fn try_collect_with_known_len_mismatch() {
+    let ok: Result<Vec<_>, _> = (0..3).try_collect_with_known_len(3);
+    assert_eq!(ok.unwrap().capacity(), 3);
+
+    let short: Result<String, _> = "ab"
+      .chars()
+      .try_collect_with_known_len(3);
+    assert_eq!(
+      short,
+      Err(CollectError::LengthMismatch {
+        expected: 3,
+        actual: 2
+      })
+    );
+
+    let long: Result<Vec<_>, _> = (0..7).try_collect_with_known_len(3);
+    assert_eq!(
+      long,
+      Err(CollectError::LengthMismatch {
+        expected: 3,
+        actual: 7
+      })
+    );
+  }
+
+  #[test]
+  fn collect_wrapped_with_inner_capacity() {
+    #[repr(transparent)]
+    struct Ids(Vec<u32>);
+
+    let ids = HintedIter::bounds(0..3, 0, None).collect_wrapped_with(|_| 8, Ids);
+    assert_eq!(ids.0, [0, 1, 2]);
+    assert_eq!(ids.0.capacity(), 8);
+  }
+
+  #[cfg(feature = "ahash")]
+  #[test]
+  fn collect_wrapped_with_ahashmap() {
+    struct Registry(ahash::AHashMap<&'static str, u8>);
+
+    let reg = [("a", 1), ("b", 2)]
+      .into_iter()
+      .collect_wrapped_with(|n| n + 6, Registry);
+    assert_eq!(reg.0["b"], 2);
+    assert!(reg.0.capacity() >= 8);
+  }
 }
@@ -2,19 +2,10 @@ use core::hash::Hash;
 
 pub use ahash::{AHashMap, AHashSet};
 
-use crate::collect::CollectWith;
-
-// pub trait CollectHashContainer: Iterator {
-//   type Container<K, V>: crate::ExtendWithCapacity<(K, V)>;
-
-//   fn collect_map_with<K, V>(
-//     self,
-//     capacity: impl FnOnce(usize) -> usize,
-//   ) -> Self::Container<K, V>
-//   where
-//     Self: Sized + Iterator<Item = (K, V)>,
-//     K: Hash + Eq;
-// }
+use crate::{
+  collect::{CollectWith, CollectWithHasher},
+  entry,
+};
 
 impl<I: Iterator> CollectAHash for I {}
 
@@ -117,6 +108,259 @@ pub trait CollectAHash: Iterator {
   {
     self.collect_with_exact(capacity)
   }
+
+  /// Collects borrowed pairs into an `AHashMap`, cloning keys and values on
+  /// insertion.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectAHash;
+  ///
+  /// let pairs = [("a".to_owned(), 1), ("b".to_owned(), 2)];
+  /// let map = pairs
+  ///   .iter()
+  ///   .collect_ahashmap_cloned_with(|u| u);
+  /// assert_eq!(map.get("a"), Some(&1));
+  /// ```
+  fn collect_ahashmap_cloned_with<'a, K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> AHashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = &'a (K, V)>,
+    K: Hash + Eq + Clone + 'a,
+    V: Clone + 'a,
+  {
+    self
+      .cloned()
+      .collect_with(capacity)
+  }
+
+  /// Collects borrowed `Copy` pairs into an `AHashMap`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectAHash;
+  ///
+  /// let map = [(1, 'a'), (2, 'b')]
+  ///   .iter()
+  ///   .collect_ahashmap_copied_with(|u| u);
+  /// assert_eq!(map.get(&2), Some(&'b'));
+  /// ```
+  fn collect_ahashmap_copied_with<'a, K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> AHashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = &'a (K, V)>,
+    K: Hash + Eq + Copy + 'a,
+    V: Copy + 'a,
+  {
+    self
+      .copied()
+      .collect_with(capacity)
+  }
+
+  /// Counts items per key derived from each item into an `AHashMap`.
+  ///
+  /// See [collect_group_counts_with()](crate::CollectHash::collect_group_counts_with).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectAHash;
+  ///
+  /// let counts = (1..=10).collect_ahashmap_group_counts_with(|n| n, |x| x % 2 == 0);
+  /// assert_eq!(counts[&true], 5);
+  /// ```
+  fn collect_ahashmap_group_counts_with<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    key: impl FnMut(&Self::Item) -> K,
+  ) -> AHashMap<K, usize>
+  where
+    Self: Sized,
+    K: Hash + Eq,
+  {
+    entry::group_counts(self, capacity, key)
+  }
+
+  /// Groups the values of `(key, value)` pairs into a deduplicated
+  /// `AHashSet` per key.
+  ///
+  /// See [collect_grouped_sets_with()](crate::CollectHash::collect_grouped_sets_with).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectAHash;
+  ///
+  /// let tags = [(1, "rust"), (1, "cli"), (1, "rust")]
+  ///   .into_iter()
+  ///   .collect_ahashmap_grouped_sets_with(|n| n, 2);
+  /// assert_eq!(tags[&1].len(), 2);
+  /// ```
+  fn collect_ahashmap_grouped_sets_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    set_capacity: usize,
+  ) -> AHashMap<K, AHashSet<V>>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+    V: Hash + Eq,
+  {
+    entry::group_sets(self, capacity, set_capacity)
+  }
+
+  /// Collects pairs with known-unique keys into an `AHashMap` sized exactly
+  /// from [`len()`](ExactSizeIterator::len).
+  ///
+  /// See [collect_unique_map_exact()](crate::CollectHash::collect_unique_map_exact).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectAHash;
+  ///
+  /// let map = [('r', 0xff0000), ('g', 0xff00)]
+  ///   .into_iter()
+  ///   .collect_unique_ahashmap_exact();
+  /// assert_eq!(map[&'g'], 0xff00);
+  /// ```
+  fn collect_unique_ahashmap_exact<K, V>(self) -> AHashMap<K, V>
+  where
+    Self: Sized + ExactSizeIterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    let len = self.len();
+    let map: AHashMap<K, V> = self.collect_with_exact(|_| len);
+    debug_assert_eq!(
+      map.len(),
+      len,
+      "collect_unique_ahashmap_exact: duplicate keys"
+    );
+    map
+  }
+
+  /// Folds a stream of `(key, delta)` updates into an `AHashMap`.
+  ///
+  /// See [collect_aggregate_with()](crate::CollectHash::collect_aggregate_with).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectAHash;
+  ///
+  /// let longest = ["ab", "abcd", "b", "bcd"]
+  ///   .into_iter()
+  ///   .map(|w| (w.as_bytes()[0], w.len()))
+  ///   .collect_ahashmap_aggregate_with(|n| n, |max: &mut usize, len| *max = len.max(*max));
+  /// assert_eq!(longest[&b'a'], 4);
+  /// assert_eq!(longest[&b'b'], 3);
+  /// ```
+  fn collect_ahashmap_aggregate_with<K, V, D>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    apply: impl FnMut(&mut V, D),
+  ) -> AHashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, D)>,
+    K: Hash + Eq,
+    V: Default,
+  {
+    entry::aggregate(self, capacity, apply)
+  }
+
+  /// Collects items into an `AHashMap` whose hasher is built from fixed
+  /// seeds, so the iteration order is the same on every run.
+  ///
+  /// - `seeds`
+  ///   - Passed to [`ahash::RandomState::with_seeds()`]
+  ///
+  /// The capacity is computed exactly as in
+  /// [collect_ahashmap_with()](CollectAHash::collect_ahashmap_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectAHash;
+  ///
+  /// let seeds = (1, 2, 3, 4);
+  /// let a = (0..32).zip('a'..).collect_ahashmap_with_seed(seeds, |n| n);
+  /// let b = (0..32).zip('a'..).collect_ahashmap_with_seed(seeds, |n| n);
+  /// assert!(a.iter().eq(b.iter()));
+  /// ```
+  fn collect_ahashmap_with_seed<K, V>(
+    self,
+    seeds: (u64, u64, u64, u64),
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> AHashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    let (k0, k1, k2, k3) = seeds;
+    self
+      .collect_with_hasher(ahash::RandomState::with_seeds(k0, k1, k2, k3), capacity)
+  }
+
+  /// Collects items into an `AHashSet` whose hasher is built from fixed
+  /// seeds.
+  ///
+  /// See
+  /// [collect_ahashmap_with_seed()](CollectAHash::collect_ahashmap_with_seed).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectAHash;
+  ///
+  /// let set = "hello".chars().collect_ahashset_with_seed((0, 0, 0, 1), |n| n);
+  /// assert_eq!(set.len(), 4);
+  /// ```
+  fn collect_ahashset_with_seed<K>(
+    self,
+    seeds: (u64, u64, u64, u64),
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> AHashSet<K>
+  where
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+  {
+    let (k0, k1, k2, k3) = seeds;
+    self
+      .collect_with_hasher(ahash::RandomState::with_seeds(k0, k1, k2, k3), capacity)
+  }
+
+  /// Groups `(outer, inner, value)` triples into a two-level `AHashMap`.
+  ///
+  /// See [collect_nested_map_with()](crate::CollectHash::collect_nested_map_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectAHash;
+  ///
+  /// let nested = [(0, 'x', 1.0), (0, 'y', 2.0)]
+  ///   .into_iter()
+  ///   .collect_ahashmap_nested_with(|n| n, 2);
+  /// assert_eq!(nested[&0][&'y'], 2.0);
+  /// ```
+  fn collect_ahashmap_nested_with<A, B, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    inner_capacity: usize,
+  ) -> AHashMap<A, AHashMap<B, V>>
+  where
+    Self: Sized + Iterator<Item = (A, B, V)>,
+    A: Hash + Eq,
+    B: Hash + Eq,
+  {
+    entry::group_sets(self.map(|(a, b, v)| (a, (b, v))), capacity, inner_capacity)
+  }
 }
 
 #[cfg(test)]
@@ -147,4 +391,140 @@ mod tests {
       });
     dbg!(map);
   }
+
+  #[test]
+  fn collect_ahashmap_cloned_from_slice() {
+    let pairs: &[(String, u32)] = &[("x".into(), 1), ("y".into(), 2)];
+    let map = pairs
+      .iter()
+      .collect_ahashmap_cloned_with(|u| u);
+    assert_eq!(map.get("x"), Some(&1));
+    assert!(map.capacity() >= pairs.len());
+
+    let map = [(1, 2), (3, 4)]
+      .iter()
+      .collect_ahashmap_copied_with(|u| u + 8);
+    assert_eq!(map.get(&3), Some(&4));
+    assert!(map.capacity() >= 10);
+  }
+
+  #[test]
+  fn collect_ahashmap_group_counts() {
+    let counts = ["x", "yy", "z"]
+      .into_iter()
+      .collect_ahashmap_group_counts_with(|n| n, |s| s.len());
+    assert_eq!(counts[&1], 2);
+    assert_eq!(counts[&2], 1);
+  }
+
+  #[test]
+  fn collect_ahashmap_from_reference_pairs() {
+    use crate::CollectWith;
+
+    let src = AHashMap::from([(1u8, 'a'), (2, 'b')]);
+    let copy = src
+      .iter()
+      .collect_copied_pairs_with::<AHashMap<_, _>, _, _>(|n| n);
+    assert_eq!(copy, src);
+
+    let borrowed = src
+      .iter()
+      .collect_with::<AHashMap<_, _>>(|n| n);
+    assert_eq!(borrowed[&&2], &'b');
+
+    let owned = src
+      .into_iter()
+      .collect_with::<AHashMap<_, _>>(|n| n);
+    assert_eq!(owned[&1], 'a');
+  }
+
+  #[test]
+  fn collect_ahashset_from_references() {
+    use crate::CollectWith;
+
+    let src = AHashSet::from([1u8, 2, 3]);
+    let copy = src
+      .iter()
+      .collect_copied_with::<AHashSet<u8>, _>(|n| n);
+    assert_eq!(copy, src);
+
+    let borrowed = src
+      .iter()
+      .collect_with::<AHashSet<_>>(|n| n);
+    assert_eq!(borrowed.len(), 3);
+  }
+
+  #[test]
+  fn collect_ahashmap_grouped_sets() {
+    let groups = [('x', 1), ('x', 1), ('y', 2), ('x', 3)]
+      .into_iter()
+      .collect_ahashmap_grouped_sets_with(|n| n, 8);
+    assert_eq!(groups[&'x'], AHashSet::from([1, 3]));
+    assert_eq!(groups[&'y'].len(), 1);
+    assert!(
+      groups
+        .values()
+        .all(|set| set.capacity() >= 8)
+    );
+  }
+
+  #[test]
+  fn unique_ahashmap_exact_capacity() {
+    let map = (0..10u8)
+      .map(|i| (i, ()))
+      .collect_unique_ahashmap_exact();
+    assert_eq!(map.len(), 10);
+    assert!(map.capacity() >= 10);
+  }
+
+  #[test]
+  fn collect_ahashmap_aggregate() {
+    let lists = [('x', 1), ('y', 2), ('x', 3)]
+      .into_iter()
+      .collect_ahashmap_aggregate_with(|n| n, |v: &mut Vec<i32>, d| v.push(d));
+    assert_eq!(lists[&'x'], [1, 3]);
+    assert_eq!(lists[&'y'], [2]);
+  }
+
+  #[test]
+  fn seeded_ahash_is_deterministic() {
+    use alloc::format;
+
+    let words = || (0..64).map(|i| format!("w{i}"));
+
+    let a = words()
+      .zip(0..)
+      .collect_ahashmap_with_seed((1, 2, 3, 4), |n| n);
+    let b = words()
+      .zip(0..)
+      .collect_ahashmap_with_seed((1, 2, 3, 4), |n| n);
+    let c = words()
+      .zip(0..)
+      .collect_ahashmap_with_seed((5, 6, 7, 8), |n| n);
+    assert_eq!(format!("{a:?}"), format!("{b:?}"));
+    assert!(!a.keys().eq(c.keys()));
+
+    // Same capacity as the unseeded method.
+    let unseeded = words()
+      .zip(0..)
+      .collect_ahashmap_with(|n| n + 5);
+    let seeded = words()
+      .zip(0..)
+      .collect_ahashmap_with_seed((1, 2, 3, 4), |n| n + 5);
+    assert_eq!(seeded.capacity(), unseeded.capacity());
+
+    let s1 = words().collect_ahashset_with_seed((9, 9, 9, 9), |n| n);
+    let s2 = words().collect_ahashset_with_seed((9, 9, 9, 9), |n| n);
+    assert!(s1.iter().eq(s2.iter()));
+  }
+
+  #[test]
+  fn collect_ahashmap_nested() {
+    let nested = [("a", 1, 'p'), ("a", 1, 'q'), ("b", 2, 'r')]
+      .into_iter()
+      .collect_ahashmap_nested_with(|n| n, 8);
+    assert_eq!(nested["a"].len(), 1);
+    assert_eq!(nested["a"][&1], 'q');
+    assert!(nested["b"].capacity() >= 8);
+  }
 }
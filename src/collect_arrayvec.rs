@@ -0,0 +1,141 @@
+use core::fmt;
+
+use arrayvec::ArrayVec;
+
+impl<I: Iterator> CollectArrayVec for I {}
+
+/// Trait for collecting items into a fixed-capacity [`ArrayVec`] without
+/// panicking on overflow.
+pub trait CollectArrayVec: Iterator {
+  /// Collects up to `N` items into an `ArrayVec<_, N>`.
+  ///
+  /// If the iterator yields more than `N` items, returns a
+  /// [`CapacityOverflow`] holding the filled `ArrayVec`, the first item that
+  /// did not fit, and the rest of the iterator. Nothing is lost and nothing
+  /// panics.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectArrayVec;
+  ///
+  /// let v = (0..3).collect_arrayvec_with::<4>().unwrap();
+  /// assert_eq!(v.as_slice(), [0, 1, 2]);
+  ///
+  /// let err = (0..6).collect_arrayvec_with::<4>().unwrap_err();
+  /// assert_eq!(err.collected.as_slice(), [0, 1, 2, 3]);
+  /// assert_eq!(err.overflow, 4);
+  /// assert!(err.rest.eq([5]));
+  /// ```
+  fn collect_arrayvec_with<const N: usize>(
+    self,
+  ) -> Result<ArrayVec<Self::Item, N>, CapacityOverflow<Self, N>>
+  where
+    Self: Sized,
+  {
+    let mut iter = self;
+    let mut collected = ArrayVec::new();
+
+    while !collected.is_full() {
+      match iter.next() {
+        Some(item) => collected.push(item),
+        None => return Ok(collected),
+      }
+    }
+    match iter.next() {
+      Some(overflow) => Err(CapacityOverflow {
+        collected,
+        overflow,
+        rest: iter,
+      }),
+      None => Ok(collected),
+    }
+  }
+}
+
+/// Error returned by
+/// [collect_arrayvec_with()](CollectArrayVec::collect_arrayvec_with) when the
+/// iterator yields more than `N` items.
+pub struct CapacityOverflow<I: Iterator, const N: usize> {
+  /// The first `N` items.
+  pub collected: ArrayVec<I::Item, N>,
+  /// The item that did not fit.
+  pub overflow: I::Item,
+  /// The remaining, unread part of the iterator.
+  pub rest: I,
+}
+
+impl<I: Iterator, const N: usize> fmt::Debug for CapacityOverflow<I, N>
+where
+  I::Item: fmt::Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("CapacityOverflow")
+      .field("collected", &self.collected)
+      .field("overflow", &self.overflow)
+      .finish_non_exhaustive()
+  }
+}
+
+impl<I: Iterator, const N: usize> fmt::Display for CapacityOverflow<I, N> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "more than {N} items for ArrayVec")
+  }
+}
+
+impl<I: Iterator, const N: usize> core::error::Error for CapacityOverflow<I, N> where
+  I::Item: fmt::Debug
+{
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::string::ToString;
+
+  use super::*;
+
+  #[test]
+  fn exactly_n() {
+    let mut probe = crate::FusedProbe::new(0..4);
+    let v = (&mut probe)
+      .collect_arrayvec_with::<4>()
+      .unwrap();
+    assert!(v.is_full());
+    // one extra call to confirm the iterator is done
+    assert_eq!(probe.next_calls(), 5);
+  }
+
+  #[test]
+  fn under_n() {
+    let v = "ab"
+      .chars()
+      .collect_arrayvec_with::<8>()
+      .unwrap();
+    assert_eq!(v.as_slice(), ['a', 'b']);
+    assert_eq!(v.capacity(), 8);
+  }
+
+  #[test]
+  fn over_n() {
+    let err = (1..=10)
+      .collect_arrayvec_with::<3>()
+      .unwrap_err();
+    assert_eq!(err.collected.as_slice(), [1, 2, 3]);
+    assert_eq!(err.overflow, 4);
+    assert_eq!(err.rest.sum::<i32>(), (5..=10).sum());
+
+    let err = (0..2)
+      .collect_arrayvec_with::<0>()
+      .unwrap_err();
+    assert_eq!(err.overflow, 0);
+    assert_eq!(err.to_string(), "more than 0 items for ArrayVec");
+  }
+
+  #[test]
+  fn generic_path_ignores_capacity() {
+    use crate::CollectWith;
+
+    let v = (0..2).collect_with::<ArrayVec<_, 4>>(|n| n + 100);
+    assert_eq!(v.as_slice(), [0, 1]);
+  }
+}
@@ -0,0 +1,251 @@
+use alloc::{vec, vec::Vec};
+use core::hash::{Hash, Hasher};
+
+use crate::common::get_max_hint_bound;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+impl<I: Iterator> CollectBitSet for I {}
+
+/// Trait for collecting set-bit positions into a [`BitSetVec`].
+pub trait CollectBitSet: Iterator {
+  /// Collect `usize` indices into a bit-packed set.
+  ///
+  /// - `max_index_hint`
+  ///   - Closure that receives the size hint (number of indices) and returns
+  ///     the expected **largest index**
+  ///
+  /// The words for `max_index_hint(size_bound) + 1` bits (saturating) are
+  /// allocated and zeroed up front. The size bound counts indices, which
+  /// says nothing about how large they are, so it is only passed to the
+  /// closure and never used as a floor. An index beyond the allocated words
+  /// grows the set, so the estimate only affects allocation, never the
+  /// contents. Duplicate indices are stored once.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectBitSet;
+  ///
+  /// let primes = [2, 3, 5, 7, 11, 13].into_iter().collect_bitset_with(|_| 13);
+  /// assert_eq!(primes.len(), 6);
+  /// assert!(primes.contains(11));
+  /// assert!(!primes.contains(9));
+  /// assert_eq!(primes.as_words().len(), 1);
+  /// ```
+  fn collect_bitset_with(
+    self,
+    max_index_hint: impl FnOnce(usize) -> usize,
+  ) -> BitSetVec
+  where
+    Self: Sized + Iterator<Item = usize>,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let bits = max_index_hint(bound).saturating_add(1);
+    let mut set = BitSetVec {
+      words: vec![0; bits.div_ceil(WORD_BITS)],
+    };
+    self.for_each(|index| set.insert(index));
+    set
+  }
+}
+
+/// A set of `usize` indices stored as bits of a `Vec<u64>`.
+///
+/// Index `i` is bit `i % 64` of word `i / 64`, so the memory use follows the
+/// largest index rather than the number of indices. Built by
+/// [collect_bitset_with()](CollectBitSet::collect_bitset_with).
+///
+/// Equality and hashing only look at the indices: trailing zero words left
+/// over from a generous capacity estimate are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct BitSetVec {
+  words: Vec<u64>,
+}
+
+impl PartialEq for BitSetVec {
+  fn eq(&self, other: &Self) -> bool {
+    self.trimmed_words() == other.trimmed_words()
+  }
+}
+
+impl Eq for BitSetVec {}
+
+impl Hash for BitSetVec {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.trimmed_words().hash(state)
+  }
+}
+
+impl BitSetVec {
+  /// Whether `index` is in the set.
+  pub fn contains(&self, index: usize) -> bool {
+    self
+      .words
+      .get(index / WORD_BITS)
+      .is_some_and(|word| word & (1 << (index % WORD_BITS)) != 0)
+  }
+
+  /// Number of indices in the set (the popcount of all words).
+  pub fn len(&self) -> usize {
+    self
+      .words
+      .iter()
+      .map(|word| word.count_ones() as usize)
+      .sum()
+  }
+
+  /// Whether the set has no indices.
+  pub fn is_empty(&self) -> bool {
+    self
+      .words
+      .iter()
+      .all(|&word| word == 0)
+  }
+
+  /// Iterates over the indices in the set, in ascending order.
+  pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+    self
+      .words
+      .iter()
+      .enumerate()
+      .flat_map(|(i, &word)| {
+        let mut rest = word;
+        core::iter::from_fn(move || {
+          if rest == 0 {
+            return None;
+          }
+          let bit = rest.trailing_zeros() as usize;
+          rest &= rest - 1;
+          Some(i * WORD_BITS + bit)
+        })
+      })
+  }
+
+  /// The underlying words, including trailing zero words.
+  pub fn as_words(&self) -> &[u64] {
+    &self.words
+  }
+
+  /// The words up to the last non-zero one.
+  fn trimmed_words(&self) -> &[u64] {
+    let len = self
+      .words
+      .iter()
+      .rposition(|&word| word != 0)
+      .map_or(0, |last| last + 1);
+    &self.words[..len]
+  }
+
+  fn insert(&mut self, index: usize) {
+    let word = index / WORD_BITS;
+    if word >= self.words.len() {
+      self.words.resize(word + 1, 0);
+    }
+    self.words[word] |= 1 << (index % WORD_BITS);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn bitset_across_word_boundaries() {
+    let indices = [0, 63, 64, 127, 128, 63];
+    let set = indices
+      .into_iter()
+      .collect_bitset_with(|_| 128);
+    assert_eq!(set.as_words().len(), 3);
+    assert_eq!(set.len(), 5);
+    assert!(
+      set
+        .iter_ones()
+        .eq([0, 63, 64, 127, 128])
+    );
+    assert_eq!(set.as_words()[0], 1 | 1 << 63);
+  }
+
+  #[test]
+  fn bitset_grows_past_hint() {
+    let mut seen = None;
+    let set = HintedIter::bounds([3, 1000, 70].into_iter(), 0, None)
+      .collect_bitset_with(|n| {
+        seen = Some(n);
+        63
+      });
+    assert_eq!(seen, Some(0));
+    assert_eq!(set.as_words().len(), 1000 / 64 + 1);
+    assert!(
+      set
+        .iter_ones()
+        .eq([3, 70, 1000])
+    );
+
+    let empty = core::iter::empty().collect_bitset_with(|n| n);
+    assert!(empty.is_empty());
+    // Room for index 0 is allocated even for an empty iterator.
+    assert_eq!(empty.as_words(), [0]);
+    assert!(!empty.contains(0));
+  }
+
+  #[test]
+  fn bitset_eq_ignores_capacity() {
+    let indices = [1, 65, 130];
+    let tight = indices
+      .into_iter()
+      .collect_bitset_with(|_| 0);
+    let loose = indices
+      .into_iter()
+      .collect_bitset_with(|_| 1000);
+    assert_ne!(tight.as_words().len(), loose.as_words().len());
+    assert_eq!(tight, loose);
+
+    #[cfg(feature = "std")]
+    {
+      use std::hash::{BuildHasher, RandomState};
+
+      let state = RandomState::new();
+      assert_eq!(state.hash_one(&tight), state.hash_one(&loose));
+    }
+
+    let other = [1, 65]
+      .into_iter()
+      .collect_bitset_with(|_| 1000);
+    assert_ne!(tight, other);
+    assert_eq!(
+      BitSetVec::default(),
+      core::iter::empty().collect_bitset_with(|_| 256)
+    );
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn bitset_matches_hashset() {
+    use std::collections::HashSet;
+
+    let indices: Vec<usize> = (0..500usize)
+      .map(|i| i * 37 % 1024)
+      .filter(|i| i % 3 != 0)
+      .collect();
+    let reference: HashSet<usize> = indices
+      .iter()
+      .copied()
+      .collect();
+    let set = indices
+      .iter()
+      .copied()
+      .collect_bitset_with(|_| 512);
+
+    assert_eq!(set.len(), reference.len());
+    for i in 0..1100 {
+      assert_eq!(set.contains(i), reference.contains(&i), "index {i}");
+    }
+    assert!(
+      set
+        .iter_ones()
+        .all(|i| reference.contains(&i))
+    );
+  }
+}
@@ -0,0 +1,79 @@
+use bitvec::{order::Lsb0, vec::BitVec};
+
+use crate::collect::CollectWith;
+
+impl<I: Iterator> CollectBitVec for I {}
+
+/// Trait for collecting `bool`s into a [`BitVec`] with a specified capacity.
+///
+/// The capacity is counted in **bits**, matching
+/// [`BitVec::with_capacity()`], so the size hint of a `bool` iterator can be
+/// used as is.
+pub trait CollectBitVec: Iterator {
+  /// Collects `bool`s into a `BitVec<usize, Lsb0>`.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))` bits. For
+  /// other storage or bit orders, use
+  /// [collect_with()](crate::CollectWith::collect_with) with the full type.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectBitVec;
+  ///
+  /// let primes = (0..16u32)
+  ///   .map(|n| n > 1 && (2..n).all(|d| n % d != 0))
+  ///   .collect_bitvec_with(|n| n);
+  /// assert_eq!(primes.count_ones(), 6);
+  /// assert!(primes[13]);
+  /// ```
+  fn collect_bitvec_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> BitVec<usize, Lsb0>
+  where
+    Self: Sized + Iterator<Item = bool>,
+  {
+    self.collect_with(capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use bitvec::order::Msb0;
+
+  use super::*;
+
+  #[test]
+  fn capacity_in_bits() {
+    let bits = (0..1000)
+      .map(|i| i % 3 == 0)
+      .collect_bitvec_with(|n| n + 64);
+    assert_eq!(bits.len(), 1000);
+    assert_eq!(bits.count_ones(), 334);
+    assert!(bits.capacity() >= 1064);
+
+    let bytes = [true, false, true]
+      .into_iter()
+      .collect_with_exact::<BitVec<u8, Msb0>>(|_| 8);
+    assert_eq!(bytes.as_raw_slice(), [0b1010_0000]);
+    assert_eq!(bytes.capacity(), 8);
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_collect_from_options() {
+    use crate::TryCollectWith;
+
+    let bits = [Some(true), Some(true), Some(false)]
+      .into_iter()
+      .try_collect_with::<BitVec, _, _>(|n| n)
+      .unwrap();
+    assert_eq!(bits.count_ones(), 2);
+
+    let none = [Some(true), None]
+      .into_iter()
+      .try_collect_with::<BitVec, _, _>(|n| n);
+    assert!(none.is_err());
+  }
+}
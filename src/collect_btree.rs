@@ -0,0 +1,247 @@
+use alloc::{
+  collections::{BTreeMap, BTreeSet},
+  vec::Vec,
+};
+
+use crate::{CollectError, common::collect_iterator};
+#[cfg(feature = "try")]
+use crate::{TryExtract, common::try_collect_iterator};
+
+impl<I: Iterator> CollectBTree for I {}
+
+/// Trait for building B-tree collections, with fast paths for sorted input.
+pub trait CollectBTree: Iterator {
+  /// Collects pairs that are already sorted by key into a `BTreeMap`.
+  ///
+  /// The pairs are gathered into a `Vec` sized from the size hint, then handed
+  /// to [`BTreeMap::from_iter()`], whose sort is linear on sorted input and
+  /// which builds the tree in bulk instead of inserting one pair at a time.
+  /// Of several pairs with the same key, the last one wins.
+  ///
+  /// Unsorted input is caught by a `debug_assert!`. In release builds the
+  /// result is still correct, only slower; use
+  /// [collect_sorted_btreemap_checked()](CollectBTree::collect_sorted_btreemap_checked)
+  /// to reject it instead.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectBTree;
+  ///
+  /// let map = [(1, 'a'), (2, 'b'), (2, 'B'), (5, 'e')]
+  ///   .into_iter()
+  ///   .collect_sorted_btreemap_with();
+  /// assert_eq!(map.len(), 3);
+  /// assert_eq!(map[&2], 'B');
+  /// ```
+  fn collect_sorted_btreemap_with<K, V>(self) -> BTreeMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Ord,
+  {
+    let pairs: Vec<_> = collect_iterator(self, false, |n| n);
+    debug_assert!(
+      find_unsorted(&pairs).is_none(),
+      "collect_sorted_btreemap_with: input is not sorted by key"
+    );
+    BTreeMap::from_iter(pairs)
+  }
+
+  /// Checked version of
+  /// [collect_sorted_btreemap_with()](CollectBTree::collect_sorted_btreemap_with).
+  ///
+  /// Returns [`CollectError::Unsorted`] with the index of the first pair whose
+  /// key is smaller than the previous one. Equal keys are allowed and follow
+  /// last-wins.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::{CollectBTree, CollectError};
+  ///
+  /// let err = [(1, ()), (3, ()), (2, ())]
+  ///   .into_iter()
+  ///   .collect_sorted_btreemap_checked()
+  ///   .unwrap_err();
+  /// assert_eq!(err, CollectError::Unsorted { index: 2 });
+  /// ```
+  fn collect_sorted_btreemap_checked<K, V>(
+    self,
+  ) -> Result<BTreeMap<K, V>, CollectError>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Ord,
+  {
+    let pairs: Vec<_> = collect_iterator(self, false, |n| n);
+    match find_unsorted(&pairs) {
+      Some(index) => Err(CollectError::Unsorted { index }),
+      None => Ok(BTreeMap::from_iter(pairs)),
+    }
+  }
+
+  /// Collects pairs into a `BTreeMap`, for call sites that mix B-trees with
+  /// the capacity-aware collectors.
+  ///
+  /// B-trees cannot be pre-sized: the closure is called with the size hint
+  /// like everywhere else, but its result is ignored. Input does not need to
+  /// be sorted; later pairs overwrite earlier ones with the same key.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectBTree;
+  ///
+  /// let map = [(3, 'c'), (1, 'a'), (2, 'b')]
+  ///   .into_iter()
+  ///   .collect_btreemap_with(|n| n);
+  /// assert!(map.keys().eq(&[1, 2, 3]));
+  /// ```
+  fn collect_btreemap_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> BTreeMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Ord,
+  {
+    collect_iterator(self, false, capacity)
+  }
+
+  /// Collects items into a `BTreeSet`.
+  ///
+  /// As with [collect_btreemap_with()](CollectBTree::collect_btreemap_with),
+  /// the capacity is ignored.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectBTree;
+  ///
+  /// let set = "banana".chars().collect_btreeset_with(|n| n);
+  /// assert!(set.iter().eq(&['a', 'b', 'n']));
+  /// ```
+  fn collect_btreeset_with<T>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> BTreeSet<T>
+  where
+    Self: Sized + Iterator<Item = T>,
+    T: Ord,
+  {
+    collect_iterator(self, false, capacity)
+  }
+
+  /// Attempts to collect pairs into a `BTreeMap`, short-circuiting on the
+  /// first error like
+  /// [try_collect_with()](crate::TryCollectWith::try_collect_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectBTree;
+  ///
+  /// let map = ["b=2", "a=1"]
+  ///   .into_iter()
+  ///   .map(|kv| kv.split_once('=').ok_or(kv))
+  ///   .try_collect_btreemap_with(|n| n);
+  /// assert_eq!(map.unwrap().first_key_value(), Some((&"a", &"1")));
+  /// ```
+  #[cfg(feature = "try")]
+  fn try_collect_btreemap_with<'a, K, V, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<BTreeMap<K, V>, ERR>
+  where
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = (K, V), Err = ERR>,
+    K: Ord,
+  {
+    try_collect_iterator(self, false, capacity)
+  }
+}
+
+/// Index of the first pair whose key is smaller than its predecessor's.
+fn find_unsorted<K: Ord, V>(pairs: &[(K, V)]) -> Option<usize> {
+  pairs
+    .windows(2)
+    .position(|w| w[0].0 > w[1].0)
+    .map(|i| i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn sorted_matches_naive_build() {
+    let pairs = (0..100u32).map(|n| (n / 3, n));
+    let fast = pairs
+      .clone()
+      .collect_sorted_btreemap_with();
+
+    let mut naive = BTreeMap::new();
+    for (k, v) in pairs {
+      naive.insert(k, v);
+    }
+    assert_eq!(fast, naive);
+    // duplicates: last wins
+    assert_eq!(fast[&0], 2);
+  }
+
+  #[test]
+  fn checked_rejects_out_of_order() {
+    let ok = [(1, 'a'), (1, 'b'), (4, 'd')]
+      .into_iter()
+      .collect_sorted_btreemap_checked();
+    assert_eq!(ok.map(|m| m[&1]), Ok('b'));
+
+    let err = [(0, 'a'), (5, 'b'), (6, 'c'), (4, 'd')]
+      .into_iter()
+      .collect_sorted_btreemap_checked();
+    assert_eq!(err, Err(CollectError::Unsorted { index: 3 }));
+
+    let empty = core::iter::empty::<(u8, u8)>().collect_sorted_btreemap_checked();
+    assert_eq!(empty, Ok(BTreeMap::new()));
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "not sorted")]
+  fn unchecked_debug_asserts() {
+    let _ = [(2, ()), (1, ())]
+      .into_iter()
+      .collect_sorted_btreemap_with();
+  }
+
+  #[test]
+  fn unsorted_input_is_ordered() {
+    let map = [(9, "nine"), (0, "zero"), (4, "four"), (0, "null")]
+      .into_iter()
+      .collect_btreemap_with(|n| n);
+    assert!(map.keys().eq(&[0, 4, 9]));
+    assert_eq!(map[&0], "null");
+
+    let set = [5, 1, 5, 3]
+      .into_iter()
+      .collect_btreeset_with(|n| n);
+    assert!(set.into_iter().eq([1, 3, 5]));
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_collect_btreemap_short_circuits() {
+    use crate::FusedProbe;
+
+    let ok = [Ok::<_, &str>((2, 'b')), Ok((1, 'a'))]
+      .into_iter()
+      .try_collect_btreemap_with(|n| n)
+      .unwrap();
+    assert!(ok.keys().eq(&[1, 2]));
+
+    let mut probe =
+      FusedProbe::new([Ok((1, ())), Err("bad"), Ok((2, ()))].into_iter());
+    let err = (&mut probe).try_collect_btreemap_with(|n| n);
+    assert_eq!(err, Err("bad"));
+    assert_eq!(probe.next_calls(), 2);
+  }
+}
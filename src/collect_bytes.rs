@@ -0,0 +1,118 @@
+use bytes::{Bytes, BytesMut};
+
+use crate::collect::CollectWith;
+
+impl<I: Iterator> CollectBytes for I {}
+
+/// Trait for collecting bytes into [`BytesMut`] or [`Bytes`] with a specified
+/// capacity.
+///
+/// Growing a `BytesMut` reallocates and loses the shared buffer that makes
+/// splitting cheap, so getting the capacity right up front matters more than
+/// for a `Vec`.
+pub trait CollectBytes: Iterator {
+  /// Collect bytes into a `BytesMut` with a capacity calculated from the
+  /// size hint.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectBytes;
+  ///
+  /// let frame = [0x01, 0x02, 0x03]
+  ///   .into_iter()
+  ///   .collect_bytes_mut_with(|n| n + 5);
+  /// assert_eq!(&frame[..], [1, 2, 3]);
+  /// assert_eq!(frame.capacity(), 8);
+  /// ```
+  fn collect_bytes_mut_with(self, capacity: impl FnOnce(usize) -> usize) -> BytesMut
+  where
+    Self: Sized,
+    BytesMut: crate::ExtendWithCapacity<Self::Item>,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collect bytes into a `BytesMut` with exact capacity calculation.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectBytes;
+  ///
+  /// let frame = b"abc"
+  ///   .iter()
+  ///   .collect_bytes_mut_with_exact(|_| 16);
+  /// assert_eq!(frame.capacity(), 16);
+  /// ```
+  fn collect_bytes_mut_with_exact(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> BytesMut
+  where
+    Self: Sized,
+    BytesMut: crate::ExtendWithCapacity<Self::Item>,
+  {
+    self.collect_with_exact(capacity)
+  }
+
+  /// Collect bytes into a `BytesMut` and [freeze](BytesMut::freeze) it into
+  /// an immutable `Bytes`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectBytes;
+  ///
+  /// let bytes = (b'a'..=b'e').collect_bytes_with(|n| n);
+  /// assert_eq!(bytes, "abcde");
+  /// let head = bytes.slice(..2); // cheap, shares the buffer
+  /// assert_eq!(head, "ab");
+  /// ```
+  fn collect_bytes_with(self, capacity: impl FnOnce(usize) -> usize) -> Bytes
+  where
+    Self: Sized,
+    BytesMut: crate::ExtendWithCapacity<Self::Item>,
+  {
+    self
+      .collect_bytes_mut_with(capacity)
+      .freeze()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn capacity_matches_closure() {
+    let buf = HintedIter::bounds(0..4u8, 0, None).collect_bytes_mut_with(|_| 64);
+    assert_eq!(&buf[..], [0, 1, 2, 3]);
+    assert_eq!(buf.capacity(), 64);
+
+    // exact: the closure result is used even below the size bound
+    let buf = HintedIter::bounds(0..5u8, 0, Some(10))
+      .collect_bytes_mut_with_exact(|n| n / 2);
+    assert_eq!(buf.len(), 5);
+    assert_eq!(buf.capacity(), 5);
+
+    let buf = [7u8; 3]
+      .iter()
+      .collect_bytes_mut_with_exact(|n| n);
+    assert_eq!(buf.capacity(), 3);
+  }
+
+  #[test]
+  fn collect_via_generic_paths() {
+    let buf = (1..=3u8).collect_with_exact::<BytesMut>(|_| 12);
+    assert_eq!(buf.capacity(), 12);
+
+    let bytes = b"hi"
+      .iter()
+      .collect_bytes_with(|n| n);
+    assert_eq!(bytes, Bytes::from_static(b"hi"));
+  }
+}
@@ -0,0 +1,133 @@
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+use bytemuck::{
+  Pod, PodCastError,
+  allocation::{pod_collect_to_vec, try_cast_vec},
+};
+
+use crate::common::collect_iterator;
+
+/// How [collect_cast_vec_with()](CollectCast::collect_cast_vec_with) produced
+/// its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastMode {
+  /// The collected buffer was reinterpreted in place, without copying.
+  Reinterpreted,
+  /// Alignment or capacity did not allow an in-place cast, so the bytes were
+  /// copied into a new `Vec<Dst>`.
+  Copied,
+}
+
+impl<I: Iterator> CollectCast for I {}
+
+/// Trait for collecting [`Pod`] items and reinterpreting them as another `Pod`
+/// type.
+pub trait CollectCast: Iterator {
+  /// Collects `Src` items into a pre-sized `Vec`, then casts it to `Vec<Dst>`.
+  ///
+  /// The buffer is reused when `Src` and `Dst` have the same alignment and the
+  /// byte length and capacity are multiples of `size_of::<Dst>()` (see
+  /// [`try_cast_vec()`]). Otherwise the bytes are copied, which is always the
+  /// case for e.g. `u8` to `u32`.
+  ///
+  /// Returns [`PodCastError::OutputSliceWouldHaveSlop`] if the collected bytes
+  /// do not add up to a whole number of `Dst` values, and
+  /// [`PodCastError::SizeMismatch`] if `Dst` is zero-sized.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::{CastMode, CollectCast};
+  ///
+  /// let dump = [0x01, 0x00, 0x00, 0x00, 0xff, 0x00, 0x00, 0x00];
+  /// let (words, mode) = dump
+  ///   .into_iter()
+  ///   .collect_cast_vec_with::<u8, u32>(|n| n)
+  ///   .unwrap();
+  /// assert_eq!(words, [u32::from_le(1), u32::from_le(255)]);
+  /// assert_eq!(mode, CastMode::Copied);
+  ///
+  /// let (bytes, mode) = [[1u8, 2], [3, 4]]
+  ///   .into_iter()
+  ///   .collect_cast_vec_with::<[u8; 2], u8>(|n| n)
+  ///   .unwrap();
+  /// assert_eq!(bytes, [1, 2, 3, 4]);
+  /// assert_eq!(mode, CastMode::Reinterpreted);
+  /// ```
+  fn collect_cast_vec_with<Src, Dst>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<(Vec<Dst>, CastMode), PodCastError>
+  where
+    Self: Sized + Iterator<Item = Src>,
+    Src: Pod,
+    Dst: Pod,
+  {
+    if size_of::<Dst>() == 0 {
+      return Err(PodCastError::SizeMismatch);
+    }
+
+    let src: Vec<Src> = collect_iterator(self, false, capacity);
+    if (src.len() * size_of::<Src>()) % size_of::<Dst>() != 0 {
+      return Err(PodCastError::OutputSliceWouldHaveSlop);
+    }
+
+    match try_cast_vec(src) {
+      Ok(dst) => Ok((dst, CastMode::Reinterpreted)),
+      Err((_, src)) => Ok((pod_collect_to_vec(&src), CastMode::Copied)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn u8_to_u32_exact_multiple() {
+    let bytes = [1u8, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0];
+    let (words, mode) = bytes
+      .into_iter()
+      .collect_cast_vec_with::<u8, u32>(|n| n)
+      .unwrap();
+    assert_eq!(words, [1, 2, 3].map(u32::from_le));
+    // Alignment of u8 and u32 differs, so this always copies.
+    assert_eq!(mode, CastMode::Copied);
+  }
+
+  #[test]
+  fn length_not_multiple_is_error() {
+    let r = [1u8, 2, 3, 4, 5]
+      .into_iter()
+      .collect_cast_vec_with::<u8, u32>(|n| n);
+    assert_eq!(r, Err(PodCastError::OutputSliceWouldHaveSlop));
+  }
+
+  #[test]
+  fn reinterpret_or_copy_by_capacity() {
+    // Same alignment and capacity of 4 * 2 bytes => reused buffer
+    let (v, mode) = (0..4u16)
+      .map(|n| [n as u8; 2])
+      .collect_cast_vec_with::<[u8; 2], [u8; 4]>(|n| n)
+      .unwrap();
+    assert_eq!(v, [[0, 0, 1, 1], [2, 2, 3, 3]]);
+    assert_eq!(mode, CastMode::Reinterpreted);
+
+    // Capacity of 5 * 2 bytes is not a multiple of 4 => copy fallback
+    let (v, mode) = (0..4u16)
+      .map(|n| [n as u8; 2])
+      .collect_cast_vec_with::<[u8; 2], [u8; 4]>(|_| 5)
+      .unwrap();
+    assert_eq!(v, [[0, 0, 1, 1], [2, 2, 3, 3]]);
+    assert_eq!(mode, CastMode::Copied);
+  }
+
+  #[test]
+  fn zero_sized_target_is_error() {
+    let r = [1u8]
+      .into_iter()
+      .collect_cast_vec_with::<u8, ()>(|n| n);
+    assert_eq!(r, Err(PodCastError::SizeMismatch));
+  }
+}
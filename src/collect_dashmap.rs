@@ -0,0 +1,93 @@
+use core::hash::Hash;
+
+use dashmap::DashMap;
+
+use crate::collect::CollectWith;
+
+impl<I: Iterator> CollectDash for I {}
+
+/// Trait for collecting items into a [`DashMap`] with a specified capacity.
+///
+/// Collection itself is single-threaded; the map can be shared afterwards.
+pub trait CollectDash: Iterator {
+  /// Collects pairs into a `DashMap` with a specified capacity.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`, split
+  /// across the shards as described in
+  /// [collect_dashmap_with_exact()](CollectDash::collect_dashmap_with_exact).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectDash;
+  ///
+  /// let sessions = (1..=3)
+  ///   .map(|id| (id, format!("user{id}")))
+  ///   .collect_dashmap_with(|n| n * 2);
+  /// assert_eq!(*sessions.get(&2).unwrap(), "user2");
+  /// assert!(sessions.capacity() >= 6);
+  /// ```
+  fn collect_dashmap_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> DashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects pairs into a `DashMap` using the closure result as the
+  /// capacity.
+  ///
+  /// A `DashMap` is a fixed number of shards, each its own hash table.
+  /// The requested capacity is rounded up to a multiple of the shard count,
+  /// and each shard gets an equal share. The total
+  /// [capacity()](DashMap::capacity) is therefore at least the request, but a
+  /// shard can still grow when the keys do not hash evenly across the
+  /// shards.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectDash;
+  ///
+  /// let map = [("a", 1)]
+  ///   .into_iter()
+  ///   .collect_dashmap_with_exact(|_| 1000);
+  /// assert!(map.capacity() >= 1000);
+  /// ```
+  fn collect_dashmap_with_exact<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> DashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    self.collect_with_exact(capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn dashmap_total_capacity() {
+    for requested in [1, 7, 64, 1000, 4097] {
+      let map = HintedIter::bounds((0..10).map(|i| (i, i)), 0, None)
+        .collect_dashmap_with_exact(|_| requested);
+      assert_eq!(map.len(), 10);
+      assert!(map.capacity() >= requested, "{requested}");
+    }
+
+    let map = (0..500)
+      .map(|i| (i, ()))
+      .collect_dashmap_with(|n| n);
+    assert_eq!(map.len(), 500);
+    assert!(map.capacity() >= 500);
+  }
+}
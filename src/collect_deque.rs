@@ -0,0 +1,119 @@
+use alloc::collections::VecDeque;
+
+use crate::collect::CollectWith;
+#[cfg(feature = "try")]
+use crate::{TryCollectWith, TryExtract};
+
+impl<I: Iterator> CollectDeque for I {}
+
+/// Trait for collecting items into a [`VecDeque`] with a specified capacity.
+///
+/// The methods mirror [`CollectVector`](crate::CollectVector): the final
+/// capacity is `max(size_bound, capacity(size_bound))`, except for the exact
+/// variant.
+pub trait CollectDeque: Iterator {
+  /// Collect iterator elements into a `VecDeque` with a capacity calculated
+  /// from the size hint.
+  ///
+  /// ## Example
+  ///
+  /// A ring buffer that will take a few more items at the front:
+  ///
+  /// ```
+  /// use collect_with::CollectDeque;
+  ///
+  /// let mut history = (3..=5).collect_deque_with(|n| n + 2);
+  /// let cap = history.capacity();
+  /// history.push_front(2);
+  /// history.push_front(1);
+  /// assert!(history.iter().copied().eq(1..=5));
+  /// assert_eq!(history.capacity(), cap); // no reallocation
+  /// ```
+  fn collect_deque_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> VecDeque<Self::Item>
+  where
+    Self: Sized,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collect iterator elements into a `VecDeque`, passing the closure result
+  /// through unchanged.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectDeque;
+  ///
+  /// let q = "a b c".split(' ').collect_deque_with_exact(|_| 8);
+  /// assert_eq!(q.len(), 3);
+  /// assert!(q.capacity() >= 8);
+  /// ```
+  fn collect_deque_with_exact(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> VecDeque<Self::Item>
+  where
+    Self: Sized,
+  {
+    self.collect_with_exact(capacity)
+  }
+
+  /// Attempt to collect iterator elements into a `VecDeque`,
+  /// short-circuiting on the first error.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectDeque;
+  ///
+  /// let jobs = ["1", "2"]
+  ///   .into_iter()
+  ///   .map(str::parse::<u8>)
+  ///   .try_collect_deque_with(|n| n + 1)
+  ///   .unwrap();
+  /// assert_eq!(jobs, [1, 2]);
+  /// ```
+  #[cfg(feature = "try")]
+  fn try_collect_deque_with<'a, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<VecDeque<OK>, ERR>
+  where
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    self.try_collect_with(capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn deque_capacity_rule() {
+    // Raised to the size bound ...
+    let q = HintedIter::exact(0..10, 10).collect_deque_with(|_| 2);
+    assert!(q.capacity() >= 10);
+
+    // ... unless the exact variant is used; the closure is used as given.
+    let q = HintedIter::bounds(0..3, 0, None).collect_deque_with_exact(|_| 1);
+    assert!(q.iter().copied().eq(0..3));
+
+    let q = HintedIter::bounds(0..3, 0, None).collect_deque_with(|_| 16);
+    assert!(q.capacity() >= 16);
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_collect_deque() {
+    let err = [Ok(1), Err('e'), Ok(3)]
+      .into_iter()
+      .try_collect_deque_with(|n| n);
+    assert_eq!(err, Err('e'));
+  }
+}
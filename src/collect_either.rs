@@ -0,0 +1,120 @@
+use core::iter::once;
+
+use either::Either;
+
+use crate::{ExtendWithCapacity, common::get_max_hint_bound};
+
+impl<I: Iterator> CollectEither for I {}
+
+/// Trait for splitting an iterator into two pre-sized collections through
+/// [`Either`].
+pub trait CollectEither: Iterator {
+  /// Map each item to `Either::Left` or `Either::Right` and collect both
+  /// sides into separate collections.
+  ///
+  /// - `left_capacity` / `right_capacity`
+  ///   - Closures that calculate the capacity of each side from the shared size
+  ///     hint bound
+  /// - `f`
+  ///   - Mapper deciding which side an item goes to
+  ///
+  /// Since each side usually receives only part of the items, the closure
+  /// results are used as-is, without being raised to the size bound.
+  /// Items keep their input order within each side.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectEither;
+  /// use either::Either;
+  ///
+  /// let (nums, words): (Vec<i32>, Vec<&str>) = ["1", "two", "3"]
+  ///   .into_iter()
+  ///   .collect_partition_map_with(
+  ///     |n| n,
+  ///     |n| n / 2,
+  ///     |s| match s.parse() {
+  ///       Ok(n) => Either::Left(n),
+  ///       Err(_) => Either::Right(s),
+  ///     },
+  ///   );
+  /// assert_eq!(nums, [1, 3]);
+  /// assert_eq!(words, ["two"]);
+  /// assert_eq!(nums.capacity(), 3);
+  /// ```
+  fn collect_partition_map_with<A, B, L, R>(
+    self,
+    left_capacity: impl FnOnce(usize) -> usize,
+    right_capacity: impl FnOnce(usize) -> usize,
+    mut f: impl FnMut(Self::Item) -> Either<L, R>,
+  ) -> (A, B)
+  where
+    A: ExtendWithCapacity<L>,
+    B: ExtendWithCapacity<R>,
+    Self: Sized,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut left = A::with_capacity(left_capacity(bound));
+    let mut right = B::with_capacity(right_capacity(bound));
+
+    for item in self {
+      match f(item) {
+        Either::Left(l) => left.extend(once(l)),
+        Either::Right(r) => right.extend(once(r)),
+      }
+    }
+    (left, right)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use super::*;
+
+  #[test]
+  fn alternating_sides() {
+    let (even, odd): (Vec<_>, Vec<_>) = (0..10).collect_partition_map_with(
+      |n| n / 2,
+      |n| n / 2,
+      |x| match x % 2 {
+        0 => Either::Left(x),
+        _ => Either::Right(x),
+      },
+    );
+    assert_eq!(even, [0, 2, 4, 6, 8]);
+    assert_eq!(odd, [1, 3, 5, 7, 9]);
+    assert_eq!(even.capacity(), 5);
+    assert_eq!(odd.capacity(), 5);
+  }
+
+  #[test]
+  fn everything_left() {
+    let (left, right): (Vec<_>, Vec<u8>) =
+      (0..4).collect_partition_map_with(|n| n, |_| 0, Either::Left);
+    assert_eq!(left, [0, 1, 2, 3]);
+    assert!(right.is_empty());
+    assert_eq!(right.capacity(), 0);
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn mixed_targets() {
+    use std::collections::HashSet;
+
+    let (ok, bad): (Vec<u8>, HashSet<&str>) = ["1", "x", "2", "x", "y"]
+      .into_iter()
+      .collect_partition_map_with(
+        |n| n,
+        |n| n,
+        |s| {
+          s.parse()
+            .map_or(Either::Right(s), Either::Left)
+        },
+      );
+    assert_eq!(ok, [1, 2]);
+    assert_eq!(bad.len(), 2);
+    assert!(bad.contains("x") && bad.contains("y"));
+  }
+}
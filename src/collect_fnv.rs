@@ -0,0 +1,101 @@
+use core::hash::Hash;
+
+pub use fnv::{FnvHashMap, FnvHashSet};
+
+use crate::collect::CollectWith;
+
+impl<I: Iterator> CollectFnv for I {}
+
+/// Trait for collecting items into std `HashMap` or `HashSet` using
+/// [FNV](fnv::FnvBuildHasher) with a specified capacity.
+///
+/// FNV is fast for small keys such as integers and short strings, but offers
+/// no protection against HashDoS.
+pub trait CollectFnv: Iterator {
+  /// Collects items into an `FnvHashMap` with a specified capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectFnv;
+  ///
+  /// let map = [(1u16, "one"), (2, "two")]
+  ///   .into_iter()
+  ///   .collect_fnvmap_with(|u| u + 6);
+  /// assert_eq!(map.get(&2), Some(&"two"));
+  /// assert!(map.capacity() >= 8);
+  /// ```
+  fn collect_fnvmap_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> FnvHashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects items into an `FnvHashSet` with a specified capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectFnv;
+  ///
+  /// let set = "abcab".bytes().collect_fnvset_with(|u| u);
+  /// assert_eq!(set.len(), 3);
+  /// ```
+  fn collect_fnvset_with<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> FnvHashSet<K>
+  where
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn collect_fnvmap_capacity() {
+    let map = (0u8..12)
+      .map(|i| (i, i))
+      .collect_fnvmap_with(|u| u);
+    assert_eq!(map.len(), 12);
+    // Like std `HashMap`, the load factor may round the capacity up.
+    assert!(map.capacity() >= 12);
+
+    let map: FnvHashMap<u8, u8> =
+      HintedIter::bounds(core::iter::empty::<(u8, u8)>(), 0, None)
+        .collect_with_exact(|_| 40);
+    assert!(map.capacity() >= 40);
+  }
+
+  #[test]
+  fn collect_fnvset_capacity() {
+    let set = HintedIter::bounds([7, 7, 8].into_iter(), 0, None)
+      .collect_fnvset_with(|n| n + 16);
+    assert_eq!(set.len(), 2);
+    assert!(set.capacity() >= 16);
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_collect_fnvmap() {
+    use crate::TryCollectWith;
+
+    let map = [Some(("a", 1)), Some(("b", 2))]
+      .into_iter()
+      .try_collect_with::<FnvHashMap<_, _>, _, _>(|n| n + 2)
+      .unwrap();
+    assert_eq!(map["b"], 2);
+    assert!(map.capacity() >= 4);
+  }
+}
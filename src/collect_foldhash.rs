@@ -0,0 +1,136 @@
+use core::hash::Hash;
+
+pub use foldhash::{HashMap, HashSet};
+
+use crate::collect::CollectWith;
+
+impl<I: Iterator> CollectFold for I {}
+
+/// Trait for collecting items into std `HashMap` or `HashSet` using
+/// [foldhash](foldhash::fast::RandomState) with a specified capacity.
+pub trait CollectFold: Iterator {
+  /// Collects items into a foldhash `HashMap` with a specified capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectFold;
+  ///
+  /// let map = ('a'..='i')
+  ///   .zip(1..=9)
+  ///   .collect_foldmap_with(|u| u + 5);
+  /// assert_eq!(map.get(&'a'), Some(&1));
+  /// assert_eq!(map.len(), 9);
+  /// assert!(map.capacity() >= 14);
+  /// ```
+  fn collect_foldmap_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> HashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects items into a foldhash `HashMap` with an exact specified
+  /// capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectFold;
+  ///
+  /// let map = [(1, "a"), (2, "b"), (3, "c")]
+  ///   .into_iter()
+  ///   .collect_foldmap_with_exact(|size_hint| size_hint);
+  /// assert_eq!(map.len(), 3);
+  /// ```
+  fn collect_foldmap_with_exact<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> HashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    self.collect_with_exact(capacity)
+  }
+
+  /// Collects items into a foldhash `HashSet` with a specified capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectFold;
+  ///
+  /// let set = (0..3).collect_foldset_with(|size_hint| size_hint);
+  /// assert_eq!(set.len(), 3);
+  /// ```
+  fn collect_foldset_with<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> HashSet<K>
+  where
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects items into a foldhash `HashSet` with an exact specified
+  /// capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectFold;
+  ///
+  /// let set = (0..3).collect_foldset_with_exact(|size_hint| size_hint);
+  /// assert_eq!(set.len(), 3);
+  /// ```
+  fn collect_foldset_with_exact<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> HashSet<K>
+  where
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+  {
+    self.collect_with_exact(capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn collect_foldmap_capacity() {
+    let map = ('a'..='e')
+      .zip(1..)
+      .collect_foldmap_with(|u| u + 3);
+    assert_eq!(map.get(&'e'), Some(&5));
+    assert!(map.capacity() >= 8);
+
+    // The size bound still wins over a smaller closure result.
+    let map = (0..10)
+      .zip(0..10)
+      .collect_foldmap_with(|_| 2);
+    assert!(map.capacity() >= 10);
+  }
+
+  #[test]
+  fn collect_foldset_exact_capacity() {
+    // exact: the closure result is used even below the size bound
+    let set =
+      HintedIter::bounds(0..3, 0, Some(10)).collect_foldset_with_exact(|_| 3);
+    assert_eq!(set.len(), 3);
+    assert_eq!(set.capacity(), 3);
+
+    let set = HintedIter::bounds(0..3, 0, None).collect_foldset_with(|n| n + 20);
+    assert!(set.capacity() >= 20);
+  }
+}
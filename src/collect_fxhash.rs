@@ -0,0 +1,141 @@
+use core::hash::Hash;
+
+pub use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::collect::CollectWith;
+
+impl<I: Iterator> CollectFx for I {}
+
+/// Trait for collecting items into std `HashMap` or `HashSet` using
+/// [rustc-hash](rustc_hash::FxBuildHasher) with a specified capacity.
+///
+/// `FxHashMap` and `FxHashSet` are std collections with a different hasher,
+/// so the capacity semantics are the same as for
+/// [`CollectAHash`](crate::CollectAHash) and
+/// [`CollectHash`](crate::CollectHash).
+pub trait CollectFx: Iterator {
+  /// Collects items into an `FxHashMap` with a specified capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectFx;
+  ///
+  /// let map = ('a'..='i')
+  ///   .zip(1..=9)
+  ///   .collect_fxhashmap_with(|u| u + 5);
+  /// assert_eq!(map.get(&'a'), Some(&1));
+  /// assert_eq!(map.len(), 9);
+  /// assert!(map.capacity() >= 14);
+  /// ```
+  fn collect_fxhashmap_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> FxHashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects items into an `FxHashMap` with an exact specified
+  /// capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectFx;
+  ///
+  /// let map = [(1, "a"), (2, "b"), (3, "c")]
+  ///   .into_iter()
+  ///   .collect_fxhashmap_with_exact(|size_hint| size_hint);
+  /// assert_eq!(map.len(), 3);
+  /// ```
+  fn collect_fxhashmap_with_exact<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> FxHashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    self.collect_with_exact(capacity)
+  }
+
+  /// Collects items into an `FxHashSet` with a specified capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectFx;
+  ///
+  /// let set = (0..3).collect_fxhashset_with(|size_hint| size_hint);
+  /// assert_eq!(set.len(), 3);
+  /// ```
+  fn collect_fxhashset_with<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> FxHashSet<K>
+  where
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects items into an `FxHashSet` with an exact specified
+  /// capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectFx;
+  ///
+  /// let set = (0..3).collect_fxhashset_with_exact(|size_hint| size_hint);
+  /// assert_eq!(set.len(), 3);
+  /// ```
+  fn collect_fxhashset_with_exact<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> FxHashSet<K>
+  where
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+  {
+    self.collect_with_exact(capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn collect_fxhashmap_capacity() {
+    let map = (0u32..6)
+      .map(|i| (i, i * 2))
+      .collect_fxhashmap_with(|u| u + 10);
+    assert_eq!(map.get(&5), Some(&10));
+    assert!(map.capacity() >= 16);
+
+    let map = HintedIter::bounds([(1, 'a')].into_iter(), 0, None)
+      .collect_fxhashmap_with_exact(|_| 32);
+    assert_eq!(map.len(), 1);
+    assert!(map.capacity() >= 32);
+  }
+
+  #[test]
+  fn collect_fxhashset_capacity() {
+    let set = "a b a c"
+      .split(' ')
+      .collect_fxhashset_with(|u| u.max(4));
+    assert_eq!(set.len(), 3);
+    assert!(set.capacity() >= 4);
+
+    // The size bound still wins over a smaller closure result.
+    let set = (0..20).collect_fxhashset_with(|_| 1);
+    assert!(set.capacity() >= 20);
+  }
+}
@@ -0,0 +1,580 @@
+use core::hash::Hash;
+use std::{
+  collections::{HashMap, HashSet},
+  vec::Vec,
+};
+
+use crate::{collect::CollectWith, common::get_max_hint_bound, entry};
+
+impl<I: Iterator> CollectHash for I {}
+
+/// Trait for collecting items into std `HashMap` or `HashSet` with a specified
+/// capacity.
+pub trait CollectHash: Iterator {
+  /// Collects borrowed pairs into a `HashMap`, cloning keys and values on
+  /// insertion.
+  ///
+  /// The pairs are cloned one by one while the pre-sized map is being
+  /// extended, so the size hint of the borrowing iterator is what feeds the
+  /// capacity calculation.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectHash;
+  ///
+  /// let pairs = [("a".to_owned(), 1), ("b".to_owned(), 2)];
+  /// let map = pairs
+  ///   .iter()
+  ///   .collect_map_cloned_with(|u| u);
+  /// assert_eq!(map.get("b"), Some(&2));
+  /// assert!(map.capacity() >= 2);
+  /// ```
+  fn collect_map_cloned_with<'a, K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> HashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = &'a (K, V)>,
+    K: Hash + Eq + Clone + 'a,
+    V: Clone + 'a,
+  {
+    self
+      .cloned()
+      .collect_with(capacity)
+  }
+
+  /// Collects borrowed `Copy` pairs into a `HashMap`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectHash;
+  ///
+  /// let map = [(1, 'a'), (2, 'b'), (3, 'c')]
+  ///   .iter()
+  ///   .collect_map_copied_with(|u| u);
+  /// assert_eq!(map.get(&3), Some(&'c'));
+  /// ```
+  fn collect_map_copied_with<'a, K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> HashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = &'a (K, V)>,
+    K: Hash + Eq + Copy + 'a,
+    V: Copy + 'a,
+  {
+    self
+      .copied()
+      .collect_with(capacity)
+  }
+
+  /// Counts items per key derived from each item.
+  ///
+  /// - `capacity`
+  ///   - Closure that calculates the map capacity from the size hint
+  /// - `key`
+  ///   - Closure that derives the grouping key; the item itself is dropped
+  ///     afterwards, so no `Clone` bound is needed
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectHash;
+  ///
+  /// let lines = ["E: disk", "W: cpu", "E: net", "I: ok"];
+  /// let counts = lines
+  ///   .into_iter()
+  ///   .collect_group_counts_with(|n| n, |line| line.as_bytes()[0]);
+  /// assert_eq!(counts[&b'E'], 2);
+  /// assert_eq!(counts[&b'W'], 1);
+  /// ```
+  fn collect_group_counts_with<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    key: impl FnMut(&Self::Item) -> K,
+  ) -> HashMap<K, usize>
+  where
+    Self: Sized,
+    K: Hash + Eq,
+  {
+    entry::group_counts(self, capacity, key)
+  }
+
+  /// Counts equal items and returns only those seen at least `min_count`
+  /// times.
+  ///
+  /// The counts are gathered in an internal `HashMap` pre-sized from the size
+  /// hint, then drained into a `Vec` keeping only the entries with
+  /// `count >= min_count`. The order of the result is unspecified; see
+  /// [collect_counts_filtered_sorted_with()](CollectHash::collect_counts_filtered_sorted_with)
+  /// for a sorted one.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectHash;
+  ///
+  /// let vocab = "a b a c a b".split(' ').collect_counts_filtered_with(2, |n| n);
+  /// assert_eq!(vocab.len(), 2);
+  /// assert!(vocab.contains(&("a", 3)));
+  /// assert!(vocab.contains(&("b", 2)));
+  /// ```
+  fn collect_counts_filtered_with(
+    self,
+    min_count: usize,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<(Self::Item, usize)>
+  where
+    Self: Sized,
+    Self::Item: Hash + Eq,
+  {
+    let counts: HashMap<_, usize> = entry::group_counts_by_item(self, capacity);
+    counts
+      .into_iter()
+      .filter(|&(_, count)| count >= min_count)
+      .collect()
+  }
+
+  /// Like
+  /// [collect_counts_filtered_with()](CollectHash::collect_counts_filtered_with),
+  /// sorted by count in descending order.
+  ///
+  /// The order of items with the same count is unspecified.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectHash;
+  ///
+  /// let top = [3, 1, 3, 2, 3, 1]
+  ///   .into_iter()
+  ///   .collect_counts_filtered_sorted_with(1, |n| n);
+  /// assert_eq!(top, [(3, 3), (1, 2), (2, 1)]);
+  /// ```
+  fn collect_counts_filtered_sorted_with(
+    self,
+    min_count: usize,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<(Self::Item, usize)>
+  where
+    Self: Sized,
+    Self::Item: Hash + Eq,
+  {
+    let mut counts = self.collect_counts_filtered_with(min_count, capacity);
+    counts.sort_unstable_by_key(|&(_, count)| core::cmp::Reverse(count));
+    counts
+  }
+
+  /// Groups the values of `(key, value)` pairs into a deduplicated
+  /// `HashSet` per key.
+  ///
+  /// - `capacity`
+  ///   - Closure that calculates the outer map capacity from the size hint
+  /// - `set_capacity`
+  ///   - Capacity of each per-key set, applied when a key is first seen
+  ///
+  /// Duplicate values within a group collapse silently.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectHash;
+  ///
+  /// let edges = [("a", "b"), ("a", "c"), ("a", "b"), ("b", "c")];
+  /// let graph = edges
+  ///   .into_iter()
+  ///   .collect_grouped_sets_with(|n| n, 4);
+  /// assert_eq!(graph["a"].len(), 2);
+  /// assert!(graph["b"].contains("c"));
+  /// ```
+  fn collect_grouped_sets_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    set_capacity: usize,
+  ) -> HashMap<K, HashSet<V>>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+    V: Hash + Eq,
+  {
+    entry::group_sets(self, capacity, set_capacity)
+  }
+
+  /// Collects pairs with known-unique keys into a `HashMap` sized exactly
+  /// from [`len()`](ExactSizeIterator::len).
+  ///
+  /// Meant for keys that cannot repeat, like enum variants or dense ids: the
+  /// map is allocated once for `len()` entries and no capacity closure is
+  /// involved. Uniqueness is assumed, and checked with a `debug_assert!`; in
+  /// release builds a duplicate key follows the usual last-wins rule.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectHash;
+  ///
+  /// let ids = (0..64u32).map(|id| (id, id.to_string()));
+  /// let map = ids.collect_unique_map_exact();
+  /// assert_eq!(map.len(), 64);
+  /// assert!(map.capacity() >= 64);
+  /// ```
+  fn collect_unique_map_exact<K, V>(self) -> HashMap<K, V>
+  where
+    Self: Sized + ExactSizeIterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    let len = self.len();
+    let map: HashMap<K, V> = self.collect_with_exact(|_| len);
+    debug_assert_eq!(map.len(), len, "collect_unique_map_exact: duplicate keys");
+    map
+  }
+
+  /// Fallible version of
+  /// [collect_unique_map_exact()](CollectHash::collect_unique_map_exact),
+  /// short-circuiting on the first error.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectHash;
+  ///
+  /// let map = ["a=1", "b=2"]
+  ///   .into_iter()
+  ///   .map(|kv| kv.split_once('=').ok_or(kv))
+  ///   .try_collect_unique_map_exact();
+  /// assert_eq!(map.unwrap()["b"], "2");
+  /// ```
+  #[cfg(feature = "try")]
+  fn try_collect_unique_map_exact<'a, K, V, ERR>(self) -> Result<HashMap<K, V>, ERR>
+  where
+    Self: Sized + ExactSizeIterator,
+    Self::Item: crate::TryExtract<'a, Ok = (K, V), Err = ERR>,
+    K: Hash + Eq,
+  {
+    let len = self.len();
+    let map: HashMap<K, V> =
+      crate::common::try_collect_iterator(self, true, |_| len)?;
+    debug_assert_eq!(
+      map.len(),
+      len,
+      "try_collect_unique_map_exact: duplicate keys"
+    );
+    Ok(map)
+  }
+
+  /// Folds a stream of `(key, delta)` updates into a `HashMap`.
+  ///
+  /// For each pair, the value at `key` is fetched, or inserted as
+  /// `V::default()` first, and `apply` merges the delta into it. Counting and
+  /// grouping are special cases of this.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectHash;
+  ///
+  /// let balances = [("alice", 10), ("bob", 5), ("alice", -3)]
+  ///   .into_iter()
+  ///   .collect_aggregate_with(|n| n, |total: &mut i64, delta| *total += delta);
+  /// assert_eq!(balances["alice"], 7);
+  /// ```
+  fn collect_aggregate_with<K, V, D>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    apply: impl FnMut(&mut V, D),
+  ) -> HashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, D)>,
+    K: Hash + Eq,
+    V: Default,
+  {
+    entry::aggregate(self, capacity, apply)
+  }
+
+  /// Splits pairs into a key column, a value column and an index from each
+  /// key to its row, in one pass.
+  ///
+  /// All three containers are pre-sized with
+  /// `max(size_bound, capacity(size_bound))`. Row `i` of the key and value
+  /// columns holds the `i`-th pair, and the index maps a key to the row of
+  /// its **last** occurrence; repeated keys keep all their rows in the
+  /// columns.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectHash;
+  ///
+  /// let (ids, scores, rows) = [("ada", 90), ("bob", 72), ("ada", 95)]
+  ///   .into_iter()
+  ///   .collect_columns_with(|n| n);
+  /// assert_eq!(ids, ["ada", "bob", "ada"]);
+  /// assert_eq!(scores, [90, 72, 95]);
+  /// assert_eq!(rows["ada"], 2);
+  /// assert_eq!(scores[rows["bob"]], 72);
+  /// ```
+  fn collect_columns_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (Vec<K>, Vec<V>, HashMap<K, usize>)
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Clone + Hash + Eq,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let cap = capacity(bound).max(bound);
+    let mut keys = Vec::with_capacity(cap);
+    let mut values = Vec::with_capacity(cap);
+    let mut index = HashMap::with_capacity(cap);
+    for (row, (k, v)) in self.enumerate() {
+      index.insert(k.clone(), row);
+      keys.push(k);
+      values.push(v);
+    }
+    (keys, values, index)
+  }
+
+  /// Groups `(outer, inner, value)` triples into a two-level `HashMap`.
+  ///
+  /// - `capacity`
+  ///   - Closure that calculates the capacity of the outer map from the size
+  ///     bound
+  /// - `inner_capacity`
+  ///   - Capacity of each inner map, allocated when its outer key is first seen
+  ///
+  /// A repeated `(outer, inner)` pair overwrites the earlier value.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectHash;
+  ///
+  /// let sales = [("eu", "fr", 3), ("eu", "de", 5), ("us", "ca", 2), ("eu", "fr", 4)];
+  /// let by_region = sales
+  ///   .into_iter()
+  ///   .collect_nested_map_with(|n| n, 4);
+  /// assert_eq!(by_region["eu"]["fr"], 4);
+  /// assert_eq!(by_region["us"].len(), 1);
+  /// ```
+  fn collect_nested_map_with<A, B, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    inner_capacity: usize,
+  ) -> HashMap<A, HashMap<B, V>>
+  where
+    Self: Sized + Iterator<Item = (A, B, V)>,
+    A: Hash + Eq,
+    B: Hash + Eq,
+  {
+    entry::group_sets(self.map(|(a, b, v)| (a, (b, v))), capacity, inner_capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn collect_map_cloned_from_slice() {
+    let pairs: &[(String, u32)] = &[
+      ("x".into(), 1), //
+      ("y".into(), 2),
+      ("z".into(), 3),
+    ];
+    let map = pairs
+      .iter()
+      .collect_map_cloned_with(|u| u);
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get("y"), Some(&2));
+    // The hint of `slice::Iter` is exact, so the map is sized for all pairs.
+    assert!(map.capacity() >= pairs.len());
+  }
+
+  #[test]
+  fn collect_map_copied_from_slice() {
+    let pairs = [(1u8, 'a'), (2, 'b')];
+    let map = pairs
+      .iter()
+      .collect_map_copied_with(|u| u + 6);
+    assert_eq!(map.get(&1), Some(&'a'));
+    assert!(map.capacity() >= 8);
+  }
+
+  #[test]
+  fn collect_group_counts() {
+    let counts = ["aa", "b", "cc", "ddd", "ee", "f"]
+      .into_iter()
+      .collect_group_counts_with(|n| n, |s| s.len());
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts[&1], 2);
+    assert_eq!(counts[&2], 3);
+    assert_eq!(counts[&3], 1);
+    assert!(counts.capacity() >= 6);
+
+    let counts = (0..5).collect_group_counts_with(|n| n, |_| "all");
+    assert_eq!(counts.len(), 1);
+    assert_eq!(counts["all"], 5);
+  }
+
+  #[test]
+  fn collect_map_from_reference_pairs() {
+    use crate::CollectWith;
+
+    let src = HashMap::from([(1u8, 'a'), (2, 'b')]);
+    let copy = src
+      .iter()
+      .collect_copied_pairs_with::<HashMap<_, _>, _, _>(|n| n + 2);
+    assert_eq!(copy, src);
+    assert!(copy.capacity() >= 4);
+
+    // `_` still infers, as a map of borrowed pairs.
+    let borrowed = src
+      .iter()
+      .collect_with::<HashMap<_, _>>(|n| n);
+    assert_eq!(borrowed[&&1], &'a');
+
+    let owned = src
+      .into_iter()
+      .collect_with::<HashMap<_, _>>(|n| n);
+    assert_eq!(owned[&2], 'b');
+  }
+
+  #[test]
+  fn collect_counts_thresholds() {
+    let words = "to be or not to be that is".split(' ');
+
+    let mut all = words
+      .clone()
+      .collect_counts_filtered_with(1, |n| n);
+    all.sort_unstable();
+    assert_eq!(
+      all,
+      [
+        ("be", 2),
+        ("is", 1),
+        ("not", 1),
+        ("or", 1),
+        ("that", 1),
+        ("to", 2)
+      ]
+    );
+
+    let none = words
+      .clone()
+      .collect_counts_filtered_with(3, |n| n);
+    assert!(none.is_empty());
+
+    let top = words.collect_counts_filtered_sorted_with(2, |n| n);
+    assert_eq!(top.len(), 2);
+    assert!(top.iter().all(|&(_, c)| c == 2));
+  }
+
+  #[test]
+  fn collect_grouped_sets_dedup_and_capacities() {
+    let pairs = [(1, 'a'), (1, 'b'), (1, 'a'), (2, 'a'), (2, 'a')];
+    let groups = pairs
+      .into_iter()
+      .collect_grouped_sets_with(|n| n + 3, 16);
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[&1], HashSet::from(['a', 'b']));
+    assert_eq!(groups[&2], HashSet::from(['a']));
+    assert!(groups.capacity() >= 8);
+    assert!(
+      groups
+        .values()
+        .all(|set| set.capacity() >= 16)
+    );
+  }
+
+  #[test]
+  fn unique_map_exact_capacity() {
+    let map = ["x", "y", "z"]
+      .into_iter()
+      .enumerate()
+      .map(|(i, s)| (s, i))
+      .collect_unique_map_exact();
+    assert_eq!(map["z"], 2);
+    assert!(map.capacity() >= 3);
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_unique_map_exact_errors() {
+    let err = [Ok((1, 'a')), Err("bad"), Ok((3, 'c'))]
+      .into_iter()
+      .try_collect_unique_map_exact();
+    assert_eq!(err, Err("bad"));
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "duplicate keys")]
+  fn unique_map_exact_debug_asserts_duplicates() {
+    let _ = [(1, 'a'), (1, 'b')]
+      .into_iter()
+      .collect_unique_map_exact();
+  }
+
+  #[test]
+  fn collect_aggregate_sums() {
+    let totals = [(1, 2.0), (2, 1.5), (1, 0.5)]
+      .into_iter()
+      .collect_aggregate_with(|n| n + 5, |v: &mut f64, d| *v += d);
+    assert_eq!(totals[&1], 2.5);
+    assert_eq!(totals[&2], 1.5);
+    assert!(totals.capacity() >= 8);
+  }
+
+  #[test]
+  fn collect_columns_alignment_and_duplicates() {
+    let pairs = [('x', 1.5), ('y', 2.5), ('z', 3.5), ('y', 4.5)];
+    let (keys, values, index) = pairs
+      .into_iter()
+      .collect_columns_with(|n| n * 2);
+    assert_eq!(keys.len(), values.len());
+    assert!(
+      keys
+        .iter()
+        .copied()
+        .zip(values.iter().copied())
+        .eq(pairs)
+    );
+
+    // Last occurrence wins in the index; both 'y' rows stay in the columns.
+    assert_eq!(index.len(), 3);
+    assert_eq!(index[&'y'], 3);
+    assert_eq!(values[index[&'y']], 4.5);
+    assert!(
+      index
+        .iter()
+        .all(|(k, &row)| keys[row] == *k)
+    );
+
+    assert!(keys.capacity() >= 8);
+    assert!(values.capacity() >= 8);
+    assert!(index.capacity() >= 8);
+  }
+
+  #[test]
+  fn collect_nested_map_levels_and_capacities() {
+    use crate::HintedIter;
+
+    let rows = [(1, 'a', "x"), (1, 'b', "y"), (2, 'a', "z"), (1, 'a', "w")];
+    let nested =
+      HintedIter::exact(rows.into_iter(), 4).collect_nested_map_with(|n| n * 2, 16);
+    assert_eq!(nested.len(), 2);
+    assert_eq!(nested[&1].len(), 2);
+    // Last write wins for a repeated (outer, inner) pair.
+    assert_eq!(nested[&1][&'a'], "w");
+    assert_eq!(nested[&2][&'a'], "z");
+
+    assert!(nested.capacity() >= 8);
+    assert!(
+      nested
+        .values()
+        .all(|inner| inner.capacity() >= 16)
+    );
+  }
+}
@@ -0,0 +1,180 @@
+use core::hash::Hash;
+
+use crate::{ExtendWithCapacity, collect::CollectWith};
+
+/// A family of hash-based containers, selected by a marker type.
+///
+/// Each backend provides a map and a set type through generic associated
+/// types, so generic code can pick the backend with a single type parameter
+/// instead of calling a backend-specific trait.
+pub trait HashContainer {
+  /// The map type of this backend.
+  type Map<K: Hash + Eq, V>: ExtendWithCapacity<(K, V)>;
+  /// The set type of this backend.
+  type Set<K: Hash + Eq>: ExtendWithCapacity<K>;
+}
+
+/// Selects std [`HashMap`](std::collections::HashMap) and
+/// [`HashSet`](std::collections::HashSet).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdHashContainer;
+
+impl HashContainer for StdHashContainer {
+  type Map<K: Hash + Eq, V> = std::collections::HashMap<K, V>;
+  type Set<K: Hash + Eq> = std::collections::HashSet<K>;
+}
+
+/// Selects [`AHashMap`](ahash::AHashMap) and [`AHashSet`](ahash::AHashSet).
+#[cfg(feature = "ahash")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AHashContainer;
+
+#[cfg(feature = "ahash")]
+impl HashContainer for AHashContainer {
+  type Map<K: Hash + Eq, V> = ahash::AHashMap<K, V>;
+  type Set<K: Hash + Eq> = ahash::AHashSet<K>;
+}
+
+/// Selects [`IndexMap`](indexmap::IndexMap) and
+/// [`IndexSet`](indexmap::IndexSet) with the std hasher.
+#[cfg(feature = "indexmap")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IndexContainer;
+
+#[cfg(feature = "indexmap")]
+impl HashContainer for IndexContainer {
+  type Map<K: Hash + Eq, V> = indexmap::IndexMap<K, V>;
+  type Set<K: Hash + Eq> = indexmap::IndexSet<K>;
+}
+
+impl<I: Iterator> CollectHashContainer for I {}
+
+/// Trait for collecting items into the map or set of any [`HashContainer`]
+/// backend.
+///
+/// ## Example
+///
+/// ```
+/// use collect_with::{CollectHashContainer, HashContainer, StdHashContainer};
+///
+/// fn index_words<M>(words: &[&'static str]) -> M::Map<&'static str, u32>
+/// where
+///   M: HashContainer,
+/// {
+///   words
+///     .iter()
+///     .copied()
+///     .zip(0..)
+///     .collect_map_with::<M, _, _>(|n| n)
+/// }
+///
+/// let map = index_words::<StdHashContainer>(&["a", "b"]);
+/// assert_eq!(map["b"], 1);
+/// ```
+pub trait CollectHashContainer: Iterator {
+  /// Collects pairs into the map type of backend `M`.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::{CollectHashContainer, StdHashContainer};
+  ///
+  /// let map = ('a'..='c')
+  ///   .zip(1..)
+  ///   .collect_map_with::<StdHashContainer, _, _>(|n| n + 5);
+  /// assert_eq!(map[&'c'], 3);
+  /// assert!(map.capacity() >= 8);
+  /// ```
+  fn collect_map_with<M, K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> M::Map<K, V>
+  where
+    M: HashContainer,
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects items into the set type of backend `M`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::{CollectHashContainer, StdHashContainer};
+  ///
+  /// let set = [1, 2, 2, 3]
+  ///   .into_iter()
+  ///   .collect_set_with::<StdHashContainer, _>(|n| n);
+  /// assert_eq!(set.len(), 3);
+  /// ```
+  fn collect_set_with<M, K>(self, capacity: impl FnOnce(usize) -> usize) -> M::Set<K>
+  where
+    M: HashContainer,
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn collect_both<M: HashContainer>() -> (M::Map<u8, char>, M::Set<u8>)
+  where
+    M::Map<u8, char>: IntoIterator<Item = (u8, char)>,
+    M::Set<u8>: IntoIterator<Item = u8>,
+  {
+    let map = (1..=3)
+      .zip('a'..)
+      .collect_map_with::<M, _, _>(|n| n);
+    let set = [4, 4, 5]
+      .into_iter()
+      .collect_set_with::<M, _>(|n| n + 2);
+    (map, set)
+  }
+
+  fn check<M: HashContainer>()
+  where
+    M::Map<u8, char>: IntoIterator<Item = (u8, char)>,
+    M::Set<u8>: IntoIterator<Item = u8>,
+  {
+    let (map, set) = collect_both::<M>();
+    let mut pairs = map
+      .into_iter()
+      .collect::<Vec<_>>();
+    pairs.sort_unstable();
+    assert_eq!(pairs, [(1, 'a'), (2, 'b'), (3, 'c')]);
+
+    let mut keys = set
+      .into_iter()
+      .collect::<Vec<_>>();
+    keys.sort_unstable();
+    assert_eq!(keys, [4, 5]);
+  }
+
+  #[test]
+  fn std_backend() {
+    check::<StdHashContainer>();
+  }
+
+  #[cfg(feature = "ahash")]
+  #[test]
+  fn ahash_backend() {
+    check::<AHashContainer>();
+  }
+
+  #[cfg(feature = "indexmap")]
+  #[test]
+  fn index_backend() {
+    check::<IndexContainer>();
+    // IndexMap keeps insertion order
+    let map = collect_both::<IndexContainer>().0;
+    assert_eq!(map.first(), Some((&1, &'a')));
+  }
+}
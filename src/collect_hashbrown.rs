@@ -0,0 +1,144 @@
+use core::hash::Hash;
+
+pub use hashbrown::{HashMap, HashSet};
+
+use crate::collect::CollectWith;
+
+impl<I: Iterator> CollectHashbrown for I {}
+
+/// Trait for collecting items into hashbrown `HashMap` or `HashSet` with a
+/// specified capacity.
+///
+/// Unlike [`CollectHash`](crate::CollectHash), this does not require `std`.
+pub trait CollectHashbrown: Iterator {
+  /// Collects items into a hashbrown `HashMap` with a specified capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectHashbrown;
+  ///
+  /// let map = ('a'..='i')
+  ///   .zip(1..=9)
+  ///   .collect_hashbrown_map_with(|u| u + 5);
+  /// assert_eq!(map.get(&'a'), Some(&1));
+  /// assert_eq!(map.len(), 9);
+  /// assert!(map.capacity() >= 14);
+  /// ```
+  fn collect_hashbrown_map_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> HashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects items into a hashbrown `HashMap` with an exact specified
+  /// capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectHashbrown;
+  ///
+  /// let map = [(1, "a"), (2, "b"), (3, "c")]
+  ///   .into_iter()
+  ///   .collect_hashbrown_map_with_exact(|size_hint| size_hint);
+  /// assert_eq!(map.len(), 3);
+  /// ```
+  fn collect_hashbrown_map_with_exact<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> HashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    self.collect_with_exact(capacity)
+  }
+
+  /// Collects items into a hashbrown `HashSet` with a specified capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectHashbrown;
+  ///
+  /// let set = (0..3).collect_hashbrown_set_with(|size_hint| size_hint);
+  /// assert_eq!(set.len(), 3);
+  /// ```
+  fn collect_hashbrown_set_with<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> HashSet<K>
+  where
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects items into a hashbrown `HashSet` with an exact specified
+  /// capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectHashbrown;
+  ///
+  /// let set = (0..3).collect_hashbrown_set_with_exact(|size_hint| size_hint);
+  /// assert_eq!(set.len(), 3);
+  /// ```
+  fn collect_hashbrown_set_with_exact<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> HashSet<K>
+  where
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+  {
+    self.collect_with_exact(capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn capacity_like_std_hashmap() {
+    let map = HintedIter::bounds((0..4).zip('a'..), 0, None)
+      .collect_hashbrown_map_with(|n| n + 20);
+    assert_eq!(map.len(), 4);
+    assert!(map.capacity() >= 20);
+
+    // The size bound still wins over a smaller closure result.
+    let map = (0..10)
+      .zip(0..10)
+      .collect_hashbrown_map_with(|_| 2);
+    assert!(map.capacity() >= 10);
+
+    let set = (0..10).collect_hashbrown_set_with_exact(|_| 2);
+    assert_eq!(set.len(), 10);
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_collect_into_hashbrown() {
+    use crate::TryCollectWith;
+
+    let map = [Some((1, 'a')), Some((2, 'b'))]
+      .into_iter()
+      .try_collect_with::<HashMap<_, _>, _, _>(|n| n);
+    assert_eq!(map.map(|m| m[&2]), Ok('b'));
+
+    let set = [Ok(1), Err("e")]
+      .into_iter()
+      .try_collect_with::<HashSet<u8>, _, _>(|n| n);
+    assert_eq!(set, Err("e"));
+  }
+}
@@ -0,0 +1,98 @@
+use core::fmt;
+
+impl<I: Iterator> CollectHeapless for I {}
+
+/// Trait for collecting items into a [`heapless::Vec`] without allocating
+/// and without panicking on overflow.
+pub trait CollectHeapless: Iterator {
+  /// Collects up to `N` items into a `heapless::Vec<_, N>`.
+  ///
+  /// Returns [`ExceededCapacity`] if the iterator yields more than `N`
+  /// items. At most `N + 1` items are read from the iterator.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::{CollectHeapless, ExceededCapacity};
+  ///
+  /// let v = (0..3).collect_heapless_with::<4>().unwrap();
+  /// assert_eq!(v, [0, 1, 2]);
+  ///
+  /// let err = (0..5).collect_heapless_with::<4>().unwrap_err();
+  /// assert_eq!(err, ExceededCapacity { capacity: 4 });
+  /// ```
+  fn collect_heapless_with<const N: usize>(
+    self,
+  ) -> Result<heapless::Vec<Self::Item, N>, ExceededCapacity>
+  where
+    Self: Sized,
+  {
+    let mut v = heapless::Vec::new();
+    for item in self {
+      v.push(item)
+        .map_err(|_| ExceededCapacity { capacity: N })?;
+    }
+    Ok(v)
+  }
+}
+
+/// Error returned by
+/// [collect_heapless_with()](CollectHeapless::collect_heapless_with) when the
+/// iterator yields more items than fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExceededCapacity {
+  /// The fixed capacity that was exceeded.
+  pub capacity: usize,
+}
+
+impl fmt::Display for ExceededCapacity {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "more than {} items for heapless::Vec", self.capacity)
+  }
+}
+
+impl core::error::Error for ExceededCapacity {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{CollectWith, FusedProbe};
+
+  #[test]
+  fn exactly_and_under_n() {
+    let v = (0..4u8)
+      .collect_heapless_with::<4>()
+      .unwrap();
+    assert!(v.is_full());
+
+    let v = core::iter::empty::<u8>()
+      .collect_heapless_with::<4>()
+      .unwrap();
+    assert!(v.is_empty());
+  }
+
+  #[test]
+  fn over_n_stops_reading() {
+    let mut probe = FusedProbe::new(0..100);
+    let err = (&mut probe)
+      .collect_heapless_with::<2>()
+      .unwrap_err();
+    assert_eq!(err.capacity, 2);
+    assert_eq!(probe.next_calls(), 3);
+  }
+
+  #[test]
+  fn generic_path_within_capacity() {
+    let v = (1..=3).collect_with::<heapless::Vec<u8, 3>>(|n| n);
+    assert_eq!(v, [1, 2, 3]);
+  }
+
+  #[test]
+  fn generic_path_ignores_loose_hint() {
+    // The upper bound of 100 exceeds `N`, but only 3 items come out.
+    let v = (0..100u32)
+      .filter(|x| *x < 3)
+      .collect_with::<heapless::Vec<u32, 4>>(|n| n);
+    assert_eq!(v, [0, 1, 2]);
+  }
+}
@@ -0,0 +1,193 @@
+use core::hash::Hash;
+
+use crate::collect::CollectWith;
+
+impl<I: Iterator> CollectIm for I {}
+
+/// Trait for collecting items into the persistent collections of the [`im`]
+/// crate.
+///
+/// `im` collections share structure between clones and grow by allocating
+/// chunks and tree nodes, so they have no notion of capacity. The capacity
+/// closures of this trait are still called, keeping the signatures in line
+/// with the rest of the crate, but their result is only advisory and is
+/// currently ignored. The contents and order are those of
+/// [`collect()`](Iterator::collect).
+pub trait CollectIm: Iterator {
+  /// Collects items into an [`im::Vector`].
+  ///
+  /// The capacity is advisory; see the [trait docs](CollectIm).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectIm;
+  ///
+  /// let v = (1..=3).collect_im_vector_with(|n| n);
+  /// assert_eq!(v, im::vector![1, 2, 3]);
+  /// ```
+  fn collect_im_vector_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> im::Vector<Self::Item>
+  where
+    Self: Sized,
+    Self::Item: Clone,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects items into an [`im::Vector`], passing the closure result through
+  /// unchanged.
+  ///
+  /// Unlike the exact variants for contiguous containers, this gives no
+  /// allocation guarantee: the only guarantee is that every item is collected
+  /// in order, even when `capacity` returns fewer than the number of items.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectIm;
+  ///
+  /// let v = (0..100).collect_im_vector_with_exact(|_| 1);
+  /// assert_eq!(v.len(), 100);
+  /// ```
+  fn collect_im_vector_with_exact(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> im::Vector<Self::Item>
+  where
+    Self: Sized,
+    Self::Item: Clone,
+  {
+    self.collect_with_exact(capacity)
+  }
+
+  /// Collects pairs into an [`im::HashMap`].
+  ///
+  /// The capacity is advisory; see the [trait docs](CollectIm). Later pairs
+  /// overwrite earlier ones with the same key.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectIm;
+  ///
+  /// let map = [("a", 1), ("b", 2), ("a", 3)]
+  ///   .into_iter()
+  ///   .collect_im_map_with(|n| n);
+  /// assert_eq!(map.len(), 2);
+  /// assert_eq!(map.get("a"), Some(&3));
+  /// ```
+  fn collect_im_map_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> im::HashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq + Clone,
+    V: Clone,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects pairs into an [`im::HashMap`], passing the closure result
+  /// through unchanged.
+  ///
+  /// As with
+  /// [collect_im_vector_with_exact()](CollectIm::collect_im_vector_with_exact),
+  /// the capacity gives no allocation guarantee.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectIm;
+  ///
+  /// let map = (0..4).map(|i| (i, i * i)).collect_im_map_with_exact(|_| 0);
+  /// assert_eq!(map[&3], 9);
+  /// ```
+  fn collect_im_map_with_exact<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> im::HashMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq + Clone,
+    V: Clone,
+  {
+    self.collect_with_exact(capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{HintBehavior, HintedIter};
+
+  #[test]
+  fn exact_variants_keep_every_item() {
+    let mut seen = None;
+    let v = HintedIter::exact(0..50, 50).collect_im_vector_with_exact(|n| {
+      seen = Some(n);
+      0
+    });
+    // The closure still sees the hint, but its result does not truncate.
+    assert_eq!(seen, Some(50));
+    assert_eq!(v.len(), 50);
+    assert!(v.iter().copied().eq(0..50));
+
+    let map = HintedIter::lying(
+      (0..10).map(|i| (i, ())),
+      (1, Some(1)),
+      HintBehavior::Frozen,
+    )
+    .collect_im_map_with_exact(|n| n);
+    assert_eq!(map.len(), 10);
+  }
+
+  #[test]
+  fn collect_im_matches_collect() {
+    let mut seen = None;
+    let v = HintedIter::exact(0..8, 8).collect_im_vector_with(|n| {
+      seen = Some(n);
+      n
+    });
+    assert_eq!(seen, Some(8));
+    assert_eq!(v, (0..8).collect::<im::Vector<_>>());
+
+    let pairs = [("a", 1), ("b", 2), ("a", 3)];
+    let mut seen = None;
+    let map =
+      HintedIter::bounds(pairs.into_iter(), 0, None).collect_im_map_with(|n| {
+        seen = Some(n);
+        16
+      });
+    assert_eq!(seen, Some(0));
+    assert_eq!(
+      map,
+      pairs
+        .into_iter()
+        .collect::<im::HashMap<_, _>>()
+    );
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get("a"), Some(&3));
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_collect_into_im() {
+    use crate::TryCollectWith;
+
+    let v = ["1", "2", "3"]
+      .into_iter()
+      .map(str::parse::<u8>)
+      .try_collect_with::<im::Vector<_>, _, _>(|n| n)
+      .unwrap();
+    assert_eq!(v, im::vector![1, 2, 3]);
+
+    let err = [Some((1, 'a')), None]
+      .into_iter()
+      .try_collect_with::<im::HashMap<_, _>, _, _>(|n| n);
+    assert!(err.is_err());
+  }
+}
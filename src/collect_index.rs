@@ -1,8 +1,8 @@
-use core::hash::Hash;
+use core::hash::{BuildHasher, Hash};
 
 pub use indexmap::{IndexMap, IndexSet};
 
-use crate::collect::CollectWith;
+use crate::{collect::CollectWith, entry};
 
 impl<I: Iterator> CollectIndex for I {}
 
@@ -28,7 +28,6 @@ pub trait CollectIndex: Iterator {
   /// assert_eq!(map.get_index(2), Some((&'c', &102)));
   /// assert_eq!(map.capacity(), 10);
   /// ```
-  #[cfg(not(feature = "ahash"))]
   fn collect_indexmap_with<K, V>(
     self,
     capacity: impl FnOnce(usize) -> usize,
@@ -40,36 +39,6 @@ pub trait CollectIndex: Iterator {
     self.collect_with(capacity)
   }
 
-  /// Collects items into an `IndexMap<K, V, ahash::RandomState>` with a
-  /// specified capacity.
-  ///
-  /// ## Example
-  ///
-  /// ```
-  /// use indexmap::IndexMap;
-  /// use collect_with::CollectIndex;
-  ///
-  /// let map = ('a'..='i')
-  ///   .zip(100..=109)
-  ///   .collect_indexmap_with(|u| u + 1); // u + 1 => 9 + 1 = 10
-  ///
-  /// assert_eq!(map.get(&'a'), Some(&100));
-  /// assert_eq!(map.get_index(0), Some((&'a', &100)));
-  /// assert_eq!(map.get_index(2), Some((&'c', &102)));
-  /// assert_eq!(map.capacity(), 10);
-  /// ```
-  #[cfg(feature = "ahash")]
-  fn collect_indexmap_with<K, V>(
-    self,
-    capacity: impl FnOnce(usize) -> usize,
-  ) -> IndexMap<K, V, ahash::RandomState>
-  where
-    Self: Sized + Iterator<Item = (K, V)>,
-    K: Hash + Eq,
-  {
-    self.collect_with(capacity)
-  }
-
   /// Collects items into an `IndexMap` with exact specified capacity.
   ///
   /// Preserves insertion order and strictly uses calculated capacity.
@@ -112,7 +81,6 @@ pub trait CollectIndex: Iterator {
   /// assert_eq!(set.len(), 3);
   /// assert!(set.capacity() >= 3);
   /// ```
-  #[cfg(not(feature = "ahash"))]
   fn collect_indexset_with<K>(
     self,
     capacity: impl FnOnce(usize) -> usize,
@@ -124,10 +92,9 @@ pub trait CollectIndex: Iterator {
     self.collect_with(capacity)
   }
 
-  /// Collects items into an `IndexSet<K, ahash::RandomState>` with specified
-  /// capacity.
+  /// Collects items into an `IndexSet` with exact specified capacity.
   ///
-  /// Preserves insertion order and maintains unique elements.
+  /// Preserves insertion order and strictly uses calculated capacity.
   ///
   /// # Example
   ///
@@ -136,48 +103,431 @@ pub trait CollectIndex: Iterator {
   /// use collect_with::CollectIndex;
   ///
   /// let set = (0..3)
-  ///     .collect_indexset_with(|size_hint| size_hint + 2);
+  ///     .into_iter()
+  ///     .collect_indexset_with_exact(|size_hint| size_hint);
   /// assert_eq!(set.len(), 3);
-  /// assert!(set.capacity() >= 3);
+  /// assert_eq!(set.capacity(), 3);
   /// ```
-  #[cfg(feature = "ahash")]
-  fn collect_indexset_with<K>(
+  fn collect_indexset_with_exact<K>(
     self,
     capacity: impl FnOnce(usize) -> usize,
-  ) -> IndexSet<K, ahash::RandomState>
+  ) -> IndexSet<K>
   where
     Self: Sized + Iterator<Item = K>,
     K: Hash + Eq,
   {
-    self.collect_with(capacity)
+    self.collect_with_exact(capacity)
   }
 
-  /// Collects items into an `IndexSet` with exact specified capacity.
+  /// Collects borrowed pairs into an `IndexMap`, cloning keys and values on
+  /// insertion.
   ///
-  /// Preserves insertion order and strictly uses calculated capacity.
+  /// Preserves the order of the source pairs.
   ///
   /// # Example
   ///
   /// ```
-  /// use indexmap::IndexSet;
   /// use collect_with::CollectIndex;
   ///
-  /// let set = (0..3)
-  ///     .into_iter()
-  ///     .collect_indexset_with_exact(|size_hint| size_hint);
-  /// assert_eq!(set.len(), 3);
-  /// assert_eq!(set.capacity(), 3);
+  /// let pairs = [("b".to_owned(), 2), ("a".to_owned(), 1)];
+  /// let map = pairs
+  ///   .iter()
+  ///   .collect_indexmap_cloned_with(|u| u);
+  /// assert_eq!(map.get_index(0), Some((&"b".to_owned(), &2)));
   /// ```
-  fn collect_indexset_with_exact<K>(
+  fn collect_indexmap_cloned_with<'a, K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> IndexMap<K, V>
+  where
+    Self: Sized + Iterator<Item = &'a (K, V)>,
+    K: Hash + Eq + Clone + 'a,
+    V: Clone + 'a,
+  {
+    self
+      .cloned()
+      .collect_with(capacity)
+  }
+
+  /// Collects borrowed `Copy` pairs into an `IndexMap`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectIndex;
+  ///
+  /// let map = [(3, 'c'), (1, 'a')]
+  ///   .iter()
+  ///   .collect_indexmap_copied_with(|u| u);
+  /// assert_eq!(map.get_index(1), Some((&1, &'a')));
+  /// assert_eq!(map.capacity(), 2);
+  /// ```
+  fn collect_indexmap_copied_with<'a, K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> IndexMap<K, V>
+  where
+    Self: Sized + Iterator<Item = &'a (K, V)>,
+    K: Hash + Eq + Copy + 'a,
+    V: Copy + 'a,
+  {
+    self
+      .copied()
+      .collect_with(capacity)
+  }
+
+  /// Counts items per key derived from each item into an `IndexMap`.
+  ///
+  /// Keys are kept in the order they were first seen.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectIndex;
+  ///
+  /// let counts = ["warn", "error", "warn", "info"]
+  ///   .into_iter()
+  ///   .collect_indexmap_group_counts_with(|n| n, |s| s.len());
+  /// assert_eq!(counts.get_index(0), Some((&4, &3)));
+  /// assert_eq!(counts.get_index(1), Some((&5, &1)));
+  /// ```
+  fn collect_indexmap_group_counts_with<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    key: impl FnMut(&Self::Item) -> K,
+  ) -> IndexMap<K, usize>
+  where
+    Self: Sized,
+    K: Hash + Eq,
+  {
+    entry::group_counts(self, capacity, key)
+  }
+
+  /// Collects items into an `IndexSet` and sorts it before returning.
+  ///
+  /// Duplicates are removed during collection, then the remaining unique
+  /// items are sorted with [`IndexSet::sort_unstable()`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectIndex;
+  ///
+  /// let symbols = ["main", "exit", "main", "alloc"]
+  ///   .into_iter()
+  ///   .collect_indexset_sorted_with(|n| n);
+  /// assert!(symbols.iter().eq(&["alloc", "exit", "main"]));
+  /// ```
+  fn collect_indexset_sorted_with<K>(
     self,
     capacity: impl FnOnce(usize) -> usize,
   ) -> IndexSet<K>
   where
     Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq + Ord,
+  {
+    let mut set = self.collect_indexset_with(capacity);
+    set.sort_unstable();
+    set
+  }
+
+  /// Collects items into an `IndexSet` and sorts it with a comparator.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectIndex;
+  ///
+  /// let set = [3, 1, 3, 2]
+  ///   .into_iter()
+  ///   .collect_indexset_sorted_by_with(|n| n, |a, b| b.cmp(a));
+  /// assert!(set.iter().eq(&[3, 2, 1]));
+  /// ```
+  fn collect_indexset_sorted_by_with<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    compare: impl FnMut(&K, &K) -> core::cmp::Ordering,
+  ) -> IndexSet<K>
+  where
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+  {
+    let mut set = self.collect_indexset_with(capacity);
+    set.sort_unstable_by(compare);
+    set
+  }
+
+  /// Groups the values of `(key, value)` pairs into a deduplicated
+  /// `IndexSet` per key.
+  ///
+  /// Keys are kept in the order they were first seen, and values in each set
+  /// in the order they were first seen for that key. Duplicates collapse
+  /// silently.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectIndex;
+  ///
+  /// let deps = [("app", "log"), ("app", "serde"), ("lib", "log"), ("app", "log")]
+  ///   .into_iter()
+  ///   .collect_indexmap_grouped_sets_with(|n| n, 4);
+  /// assert!(deps["app"].iter().eq(&["log", "serde"]));
+  /// assert_eq!(deps.get_index_of("lib"), Some(1));
+  /// ```
+  fn collect_indexmap_grouped_sets_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    set_capacity: usize,
+  ) -> IndexMap<K, IndexSet<V>>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
     K: Hash + Eq,
+    V: Hash + Eq,
+  {
+    entry::group_sets(self, capacity, set_capacity)
+  }
+
+  /// Collects pairs with known-unique keys into an `IndexMap` sized exactly
+  /// from [`len()`](ExactSizeIterator::len).
+  ///
+  /// See [collect_unique_map_exact()](crate::CollectHash::collect_unique_map_exact).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectIndex;
+  ///
+  /// let map = ["north", "east", "south", "west"]
+  ///   .into_iter()
+  ///   .zip(0..4)
+  ///   .collect_unique_indexmap_exact();
+  /// assert_eq!(map.capacity(), 4);
+  /// assert_eq!(map.get_index(2), Some((&"south", &2)));
+  /// ```
+  fn collect_unique_indexmap_exact<K, V>(self) -> IndexMap<K, V>
+  where
+    Self: Sized + ExactSizeIterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    let len = self.len();
+    let map: IndexMap<K, V> = self.collect_with_exact(|_| len);
+    debug_assert_eq!(
+      map.len(),
+      len,
+      "collect_unique_indexmap_exact: duplicate keys"
+    );
+    map
+  }
+
+  /// Folds a stream of `(key, delta)` updates into an `IndexMap`, keeping
+  /// keys in the order they were first seen.
+  ///
+  /// For each pair, the value at `key` is fetched, or inserted as
+  /// `V::default()` first, and `apply` merges the delta into it. See also
+  /// [collect_aggregate_with()](crate::CollectHash::collect_aggregate_with).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use collect_with::CollectIndex;
+  ///
+  /// let stock = [("apple", 3), ("pear", 1), ("apple", -1)]
+  ///   .into_iter()
+  ///   .collect_indexmap_aggregate_with(|n| n, |qty: &mut i32, d| *qty += d);
+  /// assert_eq!(stock.get_index(0), Some((&"apple", &2)));
+  /// assert_eq!(stock.get_index(1), Some((&"pear", &1)));
+  /// ```
+  fn collect_indexmap_aggregate_with<K, V, D>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    apply: impl FnMut(&mut V, D),
+  ) -> IndexMap<K, V>
+  where
+    Self: Sized + Iterator<Item = (K, D)>,
+    K: Hash + Eq,
+    V: Default,
+  {
+    entry::aggregate(self, capacity, apply)
+  }
+
+  /// Collects items into an `IndexMap` with any default-constructible
+  /// hasher `S`.
+  ///
+  /// The hasher-generic counterpart of
+  /// [collect_indexmap_with()](CollectIndex::collect_indexmap_with), which
+  /// keeps a fixed hasher so that the common case needs no annotation. `S`
+  /// is usually given through the return type.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::hash::{BuildHasherDefault, DefaultHasher};
+  ///
+  /// use collect_with::CollectIndex;
+  /// use indexmap::IndexMap;
+  ///
+  /// type Fixed = BuildHasherDefault<DefaultHasher>;
+  ///
+  /// let map: IndexMap<_, _, Fixed> = ["x", "y"]
+  ///   .into_iter()
+  ///   .zip(1..)
+  ///   .collect_indexmap_with_hasher(|n| n + 2);
+  /// assert_eq!(map.get_index(1), Some((&"y", &2)));
+  /// assert!(map.capacity() >= 4);
+  /// ```
+  fn collect_indexmap_with_hasher<K, V, S>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> IndexMap<K, V, S>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+    S: BuildHasher + Default,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects items into an `IndexMap<K, V, S>`, passing the closure result
+  /// through unchanged.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::hash::RandomState;
+  ///
+  /// use collect_with::CollectIndex;
+  /// use indexmap::IndexMap;
+  ///
+  /// let map: IndexMap<_, _, RandomState> = (0..3)
+  ///   .map(|i| (i, i * 10))
+  ///   .collect_indexmap_with_hasher_exact(|n| n);
+  /// assert_eq!(map.capacity(), 3);
+  /// ```
+  fn collect_indexmap_with_hasher_exact<K, V, S>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> IndexMap<K, V, S>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+    S: BuildHasher + Default,
+  {
+    self.collect_with_exact(capacity)
+  }
+
+  /// Collects items into an `IndexSet` with any default-constructible
+  /// hasher `S`.
+  ///
+  /// See
+  /// [collect_indexmap_with_hasher()](CollectIndex::collect_indexmap_with_hasher).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::hash::RandomState;
+  ///
+  /// use collect_with::CollectIndex;
+  /// use indexmap::IndexSet;
+  ///
+  /// let set: IndexSet<_, RandomState> =
+  ///   [3, 1, 3].into_iter().collect_indexset_with_hasher(|n| n);
+  /// assert_eq!(set.as_slice(), &[3, 1][..]);
+  /// ```
+  fn collect_indexset_with_hasher<K, S>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> IndexSet<K, S>
+  where
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+    S: BuildHasher + Default,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects items into an `IndexSet<K, S>`, passing the closure result
+  /// through unchanged.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::hash::RandomState;
+  ///
+  /// use collect_with::CollectIndex;
+  /// use indexmap::IndexSet;
+  ///
+  /// let set: IndexSet<_, RandomState> =
+  ///   (0..5).collect_indexset_with_hasher_exact(|n| n);
+  /// assert_eq!(set.capacity(), 5);
+  /// ```
+  fn collect_indexset_with_hasher_exact<K, S>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> IndexSet<K, S>
+  where
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+    S: BuildHasher + Default,
   {
     self.collect_with_exact(capacity)
   }
+
+  /// Collects items into an `IndexMap<K, V, ahash::RandomState>` with a
+  /// specified capacity.
+  ///
+  /// The AHash-backed counterpart of
+  /// [collect_indexmap_with()](CollectIndex::collect_indexmap_with), which
+  /// always uses the std hasher.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectIndex;
+  /// use indexmap::IndexMap;
+  ///
+  /// let map: IndexMap<_, _, ahash::RandomState> = ('a'..='i')
+  ///   .zip(100..=109)
+  ///   .collect_indexmap_ahash_with(|u| u + 1);
+  /// assert_eq!(map.get_index(2), Some((&'c', &102)));
+  /// assert_eq!(map.capacity(), 10);
+  /// ```
+  #[cfg(feature = "ahash")]
+  fn collect_indexmap_ahash_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> IndexMap<K, V, ahash::RandomState>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collects items into an `IndexSet<K, ahash::RandomState>` with a
+  /// specified capacity.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectIndex;
+  ///
+  /// let set = [2, 1, 2].into_iter().collect_indexset_ahash_with(|n| n);
+  /// assert_eq!(set.as_slice(), &[2, 1][..]);
+  /// ```
+  #[cfg(feature = "ahash")]
+  fn collect_indexset_ahash_with<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> IndexSet<K, ahash::RandomState>
+  where
+    Self: Sized + Iterator<Item = K>,
+    K: Hash + Eq,
+  {
+    self.collect_with(capacity)
+  }
 }
 
 #[cfg(test)]
@@ -213,4 +563,182 @@ mod tests {
     );
     // dbg!(result);
   }
+
+  #[test]
+  fn collect_indexmap_cloned_from_slice() {
+    let pairs: &[(String, u32)] = &[("y".into(), 2), ("x".into(), 1)];
+    let map = pairs
+      .iter()
+      .collect_indexmap_cloned_with(|u| u);
+    assert_eq!(map.get_index(0), Some((&"y".into(), &2)));
+    assert_eq!(map.capacity(), pairs.len());
+
+    let map = [(1, 2), (3, 4)]
+      .iter()
+      .collect_indexmap_copied_with(|u| u);
+    assert_eq!(map.get_index(1), Some((&3, &4)));
+  }
+
+  #[test]
+  fn collect_indexmap_group_counts_order() {
+    let counts = ["c1", "a1", "c2", "b1", "a2", "c3"]
+      .into_iter()
+      .collect_indexmap_group_counts_with(|n| n, |s| s.as_bytes()[0]);
+    let keys = counts
+      .iter()
+      .map(|(&k, &n)| (k, n))
+      .collect::<Vec<_>>();
+    assert_eq!(keys, [(b'c', 3), (b'a', 2), (b'b', 1)]);
+    assert_eq!(counts.capacity(), 6);
+  }
+
+  #[test]
+  fn collect_indexset_sorted() {
+    let set = [5, 3, 5, 1, 3, 4]
+      .into_iter()
+      .collect_indexset_sorted_with(|n| n);
+    // deduplicated before sorting: each value appears once
+    assert_eq!(set.len(), 4);
+    assert!(set.iter().eq(&[1, 3, 4, 5]));
+    assert_eq!(set.get_index_of(&5), Some(3));
+
+    let set = ["bb", "a", "ccc", "a"]
+      .into_iter()
+      .collect_indexset_sorted_by_with(|n| n, |a, b| b.len().cmp(&a.len()));
+    assert!(
+      set
+        .iter()
+        .eq(&["ccc", "bb", "a"])
+    );
+  }
+
+  #[test]
+  fn collect_indexset_from_references() {
+    use crate::CollectWith;
+
+    let src = [3u8, 1, 3, 2];
+    let set = src
+      .iter()
+      .collect_copied_with::<IndexSet<u8>, _>(|n| n);
+    assert!(set.iter().eq(&[3, 1, 2]));
+
+    let borrowed = src
+      .iter()
+      .collect_with::<IndexSet<_>>(|n| n);
+    assert_eq!(borrowed.first(), Some(&&3));
+  }
+
+  #[test]
+  fn collect_indexmap_grouped_sets_order() {
+    let groups = [(2, 'b'), (1, 'z'), (2, 'a'), (2, 'b'), (1, 'z')]
+      .into_iter()
+      .collect_indexmap_grouped_sets_with(|n| n, 3);
+    assert!(groups.keys().eq(&[2, 1]));
+    assert!(
+      groups[&2]
+        .iter()
+        .eq(&['b', 'a'])
+    );
+    assert_eq!(groups[&1].len(), 1);
+    assert_eq!(groups.capacity(), 5);
+    assert!(
+      groups
+        .values()
+        .all(|set| set.capacity() >= 3)
+    );
+  }
+
+  #[cfg(debug_assertions)]
+  #[test]
+  #[should_panic(expected = "duplicate keys")]
+  fn unique_indexmap_exact_debug_asserts_duplicates() {
+    let _ = [("a", 1), ("b", 2), ("a", 3)]
+      .into_iter()
+      .collect_unique_indexmap_exact();
+  }
+
+  #[test]
+  fn collect_indexmap_aggregate_order() {
+    let updates = [("c", 1u32), ("a", 2), ("c", 3), ("b", 4), ("a", 5)];
+    let sums = updates
+      .into_iter()
+      .collect_indexmap_aggregate_with(|n| n, |v: &mut u32, d| *v += d);
+    let pairs = sums
+      .iter()
+      .map(|(&k, &v)| (k, v))
+      .collect::<Vec<_>>();
+    assert_eq!(pairs, [("c", 4), ("a", 7), ("b", 4)]);
+    assert_eq!(sums.capacity(), updates.len());
+  }
+
+  #[test]
+  fn collect_indexmap_with_custom_hashers() {
+    use core::hash::{BuildHasherDefault, Hasher};
+    use std::hash::DefaultHasher;
+
+    #[derive(Default)]
+    struct Fnv1a(u64);
+    impl Hasher for Fnv1a {
+      fn finish(&self) -> u64 {
+        self.0
+      }
+      fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+          self.0 = (self.0 ^ u64::from(b)).wrapping_mul(0x100_0000_01b3);
+        }
+      }
+    }
+
+    let pairs = || ('a'..='f').zip(0..);
+
+    let sip: IndexMap<_, _, BuildHasherDefault<DefaultHasher>> =
+      pairs().collect_indexmap_with_hasher(|n| n * 2);
+    let fnv: IndexMap<_, _, BuildHasherDefault<Fnv1a>> =
+      pairs().collect_indexmap_with_hasher_exact(|n| n);
+    assert!(sip.iter().eq(fnv.iter()));
+    assert!(sip.capacity() >= 12);
+    assert_eq!(fnv.capacity(), 6);
+    assert_eq!(fnv[&'d'], 3);
+
+    let set: IndexSet<_, BuildHasherDefault<Fnv1a>> = "abca"
+      .chars()
+      .collect_indexset_with_hasher(|n| n);
+    assert_eq!(set.as_slice(), &['a', 'b', 'c'][..]);
+    let set: IndexSet<_, BuildHasherDefault<DefaultHasher>> =
+      (0..4).collect_indexset_with_hasher_exact(|_| 8);
+    assert_eq!(set.capacity(), 8);
+  }
+
+  #[test]
+  fn default_methods_use_std_hasher() {
+    // Spelled out so that this fails to compile if a feature changes the
+    // hasher of the default methods.
+    let map: IndexMap<char, u8, std::hash::RandomState> = ('a'..='c')
+      .zip(0..)
+      .collect_indexmap_with(|n| n);
+    let set: IndexSet<u8, std::hash::RandomState> =
+      (0..3).collect_indexset_with(|n| n);
+    let sorted: IndexSet<u8, std::hash::RandomState> = [2, 0, 1]
+      .into_iter()
+      .collect_indexset_sorted_with(|n| n);
+    assert_eq!(map.len(), 3);
+    assert_eq!(set, sorted);
+  }
+
+  #[cfg(feature = "ahash")]
+  #[test]
+  fn ahash_methods() {
+    let map = ('a'..='c')
+      .zip(0..)
+      .collect_indexmap_ahash_with(|n| n + 1);
+    let _: &ahash::RandomState = map.hasher();
+    assert_eq!(map.get_index(1), Some((&'b', &1)));
+    assert!(map.capacity() >= 4);
+
+    let set = "abca"
+      .chars()
+      .collect_indexset_ahash_with(|n| n);
+    let _: &ahash::RandomState = set.hasher();
+    assert_eq!(set.len(), 3);
+  }
 }
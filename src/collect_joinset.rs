@@ -0,0 +1,169 @@
+use core::iter::once;
+
+use tokio::task::{JoinError, JoinSet};
+
+use crate::ExtendWithCapacity;
+
+/// Awaits every task of a [`JoinSet`] and collects the outputs into a
+/// pre-sized container.
+///
+/// The capacity closure receives [`set.len()`](JoinSet::len), the number of
+/// tasks still in the set. The final capacity is `max(len, capacity(len))`.
+///
+/// Outputs arrive in completion order, not spawn order. The first
+/// [`JoinError`] (a panicked or cancelled task) is returned as soon as it is
+/// observed; the remaining tasks stay in `set`.
+///
+/// ## Example
+///
+/// ```
+/// use collect_with::collect_joinset_with;
+/// use tokio::task::JoinSet;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let mut set = JoinSet::new();
+/// for n in 0..4u32 {
+///   set.spawn(async move { n * 10 });
+/// }
+/// let mut v: Vec<u32> = collect_joinset_with(&mut set, |n| n)
+///   .await
+///   .unwrap();
+/// v.sort_unstable();
+/// assert_eq!(v, [0, 10, 20, 30]);
+/// assert!(set.is_empty());
+/// # }
+/// ```
+pub async fn collect_joinset_with<T, C>(
+  set: &mut JoinSet<T>,
+  capacity: impl FnOnce(usize) -> usize,
+) -> Result<C, JoinError>
+where
+  T: 'static,
+  C: ExtendWithCapacity<T>,
+{
+  let len = set.len();
+  let mut container = C::with_capacity(capacity(len).max(len));
+
+  while let Some(output) = set.join_next().await {
+    container.extend(once(output?));
+  }
+  Ok(container)
+}
+
+/// Like [`collect_joinset_with()`], for tasks that return `Result<T, E>`.
+///
+/// Both a [`JoinError`] and the first inner `Err` stop the collection; the
+/// join error is converted through `E: From<JoinError>`.
+///
+/// ## Example
+///
+/// ```
+/// use collect_with::try_collect_joinset_with;
+/// use tokio::task::{JoinError, JoinSet};
+///
+/// #[derive(Debug)]
+/// enum Error {
+///   Join(JoinError),
+///   Odd(u32),
+/// }
+///
+/// impl From<JoinError> for Error {
+///   fn from(e: JoinError) -> Self {
+///     Self::Join(e)
+///   }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let mut set = JoinSet::new();
+/// for n in [2u32, 3, 4] {
+///   set.spawn(async move { if n % 2 == 0 { Ok(n) } else { Err(Error::Odd(n)) } });
+/// }
+/// let r: Result<Vec<u32>, Error> = try_collect_joinset_with(&mut set, |n| n).await;
+/// assert!(matches!(r, Err(Error::Odd(3))));
+/// # }
+/// ```
+pub async fn try_collect_joinset_with<T, E, C>(
+  set: &mut JoinSet<Result<T, E>>,
+  capacity: impl FnOnce(usize) -> usize,
+) -> Result<C, E>
+where
+  T: 'static,
+  E: From<JoinError> + 'static,
+  C: ExtendWithCapacity<T>,
+{
+  let len = set.len();
+  let mut container = C::with_capacity(capacity(len).max(len));
+
+  while let Some(output) = set.join_next().await {
+    container.extend(once(output??));
+  }
+  Ok(container)
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use super::*;
+
+  #[tokio::test]
+  async fn collect_all_tasks() {
+    let mut set = JoinSet::new();
+    for n in 0..5u8 {
+      set.spawn(async move { n });
+    }
+    let mut v: Vec<u8> = collect_joinset_with(&mut set, |n| n + 3)
+      .await
+      .unwrap();
+    assert!(v.capacity() >= 8);
+    v.sort_unstable();
+    assert_eq!(v, [0, 1, 2, 3, 4]);
+  }
+
+  #[tokio::test]
+  async fn panicking_task_returns_join_error() {
+    let mut set = JoinSet::new();
+    set.spawn(async { 1u8 });
+    set.spawn(async { panic!("task failed") });
+    set.spawn(async { 3u8 });
+
+    let err = collect_joinset_with::<_, Vec<u8>>(&mut set, |n| n)
+      .await
+      .unwrap_err();
+    assert!(err.is_panic());
+  }
+
+  #[derive(Debug)]
+  enum TaskError {
+    Join,
+    Inner(&'static str),
+  }
+
+  impl From<JoinError> for TaskError {
+    fn from(_: JoinError) -> Self {
+      Self::Join
+    }
+  }
+
+  #[tokio::test]
+  async fn try_collect_propagates_both_errors() {
+    let mut set = JoinSet::new();
+    for n in 0..3u8 {
+      set.spawn(async move { Ok::<_, TaskError>(n) });
+    }
+    let v: Vec<u8> = try_collect_joinset_with(&mut set, |n| n)
+      .await
+      .unwrap();
+    assert_eq!(v.len(), 3);
+
+    set.spawn(async { Err(TaskError::Inner("bad")) });
+    let r = try_collect_joinset_with::<u8, _, Vec<_>>(&mut set, |n| n).await;
+    assert!(matches!(r, Err(TaskError::Inner("bad"))));
+
+    set.spawn(async { panic!("task failed") });
+    let r = try_collect_joinset_with::<u8, _, Vec<_>>(&mut set, |n| n).await;
+    assert!(matches!(r, Err(TaskError::Join)));
+  }
+}
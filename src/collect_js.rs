@@ -0,0 +1,165 @@
+use js_sys::{Array, wasm_bindgen::JsValue};
+
+#[cfg(feature = "try")]
+use crate::TryExtract;
+use crate::common::get_max_hint_bound;
+
+impl<I: Iterator> CollectJsArray for I {}
+
+/// Trait for collecting items into a preallocated [`js_sys::Array`].
+///
+/// The array is created with
+/// [`Array::new_with_length()`](js_sys::Array::new_with_length) and filled by
+/// index. If the iterator ends early, the length is trimmed to the number of
+/// items; if it yields more than the calculated length, the extra items are
+/// pushed.
+pub trait CollectJsArray: Iterator {
+  /// Collects items into a JS `Array` with a length calculated from the size
+  /// hint.
+  ///
+  /// The initial length is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```no_run
+  /// use collect_with::CollectJsArray;
+  ///
+  /// let arr = (0..3u32).collect_js_array_with(|n| n);
+  /// assert_eq!(arr.length(), 3);
+  /// assert_eq!(arr.get(2).as_f64(), Some(2.0));
+  /// ```
+  fn collect_js_array_with(self, capacity: impl FnOnce(usize) -> usize) -> Array
+  where
+    Self: Sized,
+    Self::Item: Into<JsValue>,
+  {
+    let mut filler = Filler::new(&self, capacity);
+    for item in self {
+      filler.push(item.into());
+    }
+    filler.finish()
+  }
+
+  /// Collects fallible items into a JS `Array`, short-circuiting on the first
+  /// error.
+  ///
+  /// Each item is extracted on the Rust side before it is converted into a
+  /// `JsValue`, so a failing item never reaches JS.
+  ///
+  /// ## Example
+  ///
+  /// ```no_run
+  /// use collect_with::CollectJsArray;
+  ///
+  /// let arr = ["1", "2"]
+  ///   .into_iter()
+  ///   .map(|s| s.parse::<u32>())
+  ///   .try_collect_js_array_with(|n| n);
+  /// assert_eq!(arr.map(|a| a.length()), Ok(2));
+  /// ```
+  #[cfg(feature = "try")]
+  fn try_collect_js_array_with<'a, T, E>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<Array, E>
+  where
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = T, Err = E>,
+    T: Into<JsValue>,
+  {
+    let mut filler = Filler::new(&self, capacity);
+    for item in self {
+      let value = item.try_extract()?;
+      filler.push(value.into());
+    }
+    Ok(filler.finish())
+  }
+}
+
+/// Fills a preallocated array by index, growing or trimming as needed.
+struct Filler {
+  array: Array,
+  len: u32,
+  index: u32,
+}
+
+impl Filler {
+  fn new<I: Iterator>(iter: &I, capacity: impl FnOnce(usize) -> usize) -> Self {
+    let bound = get_max_hint_bound(iter.size_hint());
+    let len = u32::try_from(capacity(bound).max(bound)).unwrap_or(u32::MAX);
+    Self {
+      array: Array::new_with_length(len),
+      len,
+      index: 0,
+    }
+  }
+
+  fn push(&mut self, value: JsValue) {
+    match self.index < self.len {
+      true => self
+        .array
+        .set(self.index, value),
+      _ => {
+        self.array.push(&value);
+      }
+    }
+    self.index += 1;
+  }
+
+  fn finish(self) -> Array {
+    if self.index < self.len {
+      self
+        .array
+        .set_length(self.index);
+    }
+    self.array
+  }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+  use wasm_bindgen_test::wasm_bindgen_test;
+
+  use super::*;
+  use crate::{HintBehavior, HintedIter};
+
+  #[wasm_bindgen_test]
+  fn exact_length_and_contents() {
+    let arr = ["a", "b", "c"]
+      .into_iter()
+      .collect_js_array_with(|n| n);
+    assert_eq!(arr.length(), 3);
+    assert_eq!(
+      arr
+        .get(1)
+        .as_string()
+        .as_deref(),
+      Some("b")
+    );
+  }
+
+  #[wasm_bindgen_test]
+  fn short_iterator_is_trimmed() {
+    let arr = HintedIter::lying(0..2u32, (10, Some(10)), HintBehavior::Frozen)
+      .collect_js_array_with(|n| n);
+    assert_eq!(arr.length(), 2);
+    assert_eq!(arr.get(1).as_f64(), Some(1.0));
+  }
+
+  #[wasm_bindgen_test]
+  fn lying_iterator_grows() {
+    let arr = HintedIter::lying(0..4u32, (1, Some(1)), HintBehavior::Frozen)
+      .collect_js_array_with(|n| n);
+    assert_eq!(arr.length(), 4);
+    assert_eq!(arr.get(3).as_f64(), Some(3.0));
+  }
+
+  #[cfg(feature = "try")]
+  #[wasm_bindgen_test]
+  fn try_stops_at_error() {
+    let arr = [Some(1u32), None, Some(3)]
+      .into_iter()
+      .try_collect_js_array_with(|n| n);
+    assert!(arr.is_err());
+  }
+}
@@ -0,0 +1,115 @@
+use alloc::vec::Vec;
+
+use crate::common::collect_with_exact_capacity;
+
+/// Split `s` into lines, with a capacity calculated from the actual line
+/// count.
+///
+/// [`str::lines()`] reports a size hint of `(0, Some(len))` at best, so
+/// collecting it directly either over-allocates or reallocates. This function
+/// counts the `\n` bytes first, which is cheap compared to the allocation.
+///
+/// - `capacity`
+///   - Closure that receives the exact number of lines `s.lines()` will yield
+///
+/// The final capacity is `max(line_count, capacity(line_count))`.
+/// Lines are split exactly like [`str::lines()`], i.e. a trailing `\r` is
+/// stripped and a trailing newline does not produce an empty line.
+///
+/// ## Example
+///
+/// ```
+/// use collect_with::collect_lines_vec_with;
+///
+/// let lines = collect_lines_vec_with("a\nb\r\nc\n", |n| n);
+/// assert_eq!(lines, ["a", "b", "c"]);
+/// assert_eq!(lines.capacity(), 3);
+/// ```
+pub fn collect_lines_vec_with(
+  s: &str,
+  capacity: impl FnOnce(usize) -> usize,
+) -> Vec<&str> {
+  let newlines = count_byte(s, b'\n');
+  let line_count = match s.as_bytes().last() {
+    Some(b'\n') | None => newlines,
+    _ => newlines + 1,
+  };
+  collect_with_exact_capacity(s.lines(), capacity(line_count).max(line_count))
+}
+
+/// Split `s` by a single-byte separator, with a capacity calculated from the
+/// actual number of pieces.
+///
+/// The closure receives `separator_count + 1`, which is exactly how many
+/// items [`str::split()`] yields (including empty pieces).
+///
+/// ## Panics
+///
+/// Panics if `sep` is not an ASCII byte, since splitting at a non-ASCII byte
+/// could cut a UTF-8 sequence in half.
+///
+/// ## Example
+///
+/// ```
+/// use collect_with::collect_split_vec_with;
+///
+/// let fields = collect_split_vec_with("a,b,,c", b',', |n| n);
+/// assert_eq!(fields, ["a", "b", "", "c"]);
+/// assert_eq!(fields.capacity(), 4);
+/// ```
+pub fn collect_split_vec_with(
+  s: &str,
+  sep: u8,
+  capacity: impl FnOnce(usize) -> usize,
+) -> Vec<&str> {
+  assert!(sep.is_ascii(), "separator must be an ASCII byte");
+  let count = count_byte(s, sep) + 1;
+  collect_with_exact_capacity(s.split(sep as char), capacity(count).max(count))
+}
+
+fn count_byte(s: &str, byte: u8) -> usize {
+  s.bytes()
+    .filter(|&b| b == byte)
+    .count()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn lines_with_and_without_trailing_newline() {
+    let lines = collect_lines_vec_with("one\ntwo\nthree", |n| n);
+    assert_eq!(lines, ["one", "two", "three"]);
+    assert_eq!(lines.capacity(), 3);
+
+    let lines = collect_lines_vec_with("one\ntwo\n", |n| n);
+    assert_eq!(lines, ["one", "two"]);
+    assert_eq!(lines.capacity(), 2);
+
+    let lines = collect_lines_vec_with("\n\n", |n| n);
+    assert_eq!(lines, ["", ""]);
+    assert_eq!(lines.capacity(), 2);
+  }
+
+  #[test]
+  fn lines_empty_and_crlf() {
+    let lines = collect_lines_vec_with("", |n| n);
+    assert!(lines.is_empty());
+    assert_eq!(lines.capacity(), 0);
+
+    let lines = collect_lines_vec_with("a\r\nb\r\n", |n| n);
+    assert_eq!(lines, ["a", "b"]);
+    assert_eq!(lines.capacity(), 2);
+  }
+
+  #[test]
+  fn split_by_byte() {
+    let parts = collect_split_vec_with("", b':', |n| n);
+    assert_eq!(parts, [""]);
+
+    let parts = collect_split_vec_with("/usr/bin:/bin:", b':', |n| n + 1);
+    assert_eq!(parts, ["/usr/bin", "/bin", ""]);
+    assert_eq!(parts.capacity(), 4);
+  }
+}
@@ -0,0 +1,167 @@
+use alloc::{collections::BinaryHeap, vec::Vec};
+use core::cmp::Ordering;
+
+use crate::common::get_max_hint_bound;
+
+/// Merge already-sorted sources into one sorted `Vec`.
+///
+/// Performs a k-way merge with a small [`BinaryHeap`] holding one cursor per
+/// source, so each element is compared `O(log k)` times.
+///
+/// - `sources`
+///   - Sources that are each sorted in ascending order
+/// - `capacity`
+///   - Closure that receives the sum of all sources' size bounds
+///
+/// The final capacity is `max(total_bound, capacity(total_bound))`.
+///
+/// The merge is stable with respect to source order: when elements compare
+/// equal, those from earlier sources come first.
+///
+/// ## Example
+///
+/// ```
+/// use collect_with::collect_merged_sorted_with;
+///
+/// let merged = collect_merged_sorted_with([vec![1, 4, 7], vec![2, 5], vec![3, 6]], |n| n);
+/// assert_eq!(merged, [1, 2, 3, 4, 5, 6, 7]);
+/// assert_eq!(merged.capacity(), 7);
+/// ```
+pub fn collect_merged_sorted_with<S, T>(
+  sources: impl IntoIterator<Item = S>,
+  capacity: impl FnOnce(usize) -> usize,
+) -> Vec<T>
+where
+  S: IntoIterator<Item = T>,
+  T: Ord,
+{
+  let mut heap = BinaryHeap::new();
+  let mut total = 0usize;
+
+  for (source, iter) in sources.into_iter().enumerate() {
+    let mut rest = iter.into_iter();
+    total = total.saturating_add(get_max_hint_bound(rest.size_hint()));
+    if let Some(head) = rest.next() {
+      heap.push(Cursor { head, source, rest });
+    }
+  }
+
+  let mut merged = Vec::with_capacity(capacity(total).max(total));
+  while let Some(Cursor {
+    head,
+    source,
+    mut rest,
+  }) = heap.pop()
+  {
+    merged.push(head);
+    if let Some(head) = rest.next() {
+      heap.push(Cursor { head, source, rest });
+    }
+  }
+  merged
+}
+
+/// The next element of one source, ordered so that the max-heap pops the
+/// smallest element (and the earliest source among equal elements) first.
+struct Cursor<T, I> {
+  head: T,
+  source: usize,
+  rest: I,
+}
+
+impl<T: Ord, I> Ord for Cursor<T, I> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    other
+      .head
+      .cmp(&self.head)
+      .then_with(|| other.source.cmp(&self.source))
+  }
+}
+
+impl<T: Ord, I> PartialOrd for Cursor<T, I> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<T: Ord, I> PartialEq for Cursor<T, I> {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl<T: Ord, I> Eq for Cursor<T, I> {}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{vec, vec::Vec};
+
+  use super::*;
+
+  #[test]
+  fn merge_two_sources() {
+    let merged =
+      collect_merged_sorted_with([vec![1, 3, 5], vec![2, 4, 6, 8]], |n| n);
+    assert_eq!(merged, [1, 2, 3, 4, 5, 6, 8]);
+    assert_eq!(merged.capacity(), 7);
+  }
+
+  #[test]
+  fn merge_five_sources_with_empty_ones() {
+    let sources: [Vec<u32>; 5] = [
+      vec![10, 20],
+      vec![],
+      vec![5, 15, 25, 35],
+      vec![],
+      vec![0, 30],
+    ];
+    let merged = collect_merged_sorted_with(sources, |n| n);
+    assert_eq!(merged, [0, 5, 10, 15, 20, 25, 30, 35]);
+    assert_eq!(merged.capacity(), merged.len());
+
+    let merged = collect_merged_sorted_with(Vec::<Vec<u8>>::new(), |n| n);
+    assert!(merged.is_empty());
+  }
+
+  #[test]
+  fn merge_duplicates_in_source_order() {
+    let sources = [
+      vec![(1, 'a'), (2, 'a')],
+      vec![(1, 'b'), (2, 'b')],
+      vec![(1, 'c')],
+    ];
+    // Only the first tuple field is compared.
+    let merged = collect_merged_sorted_with(
+      sources.map(|s| {
+        s.into_iter()
+          .map(|(k, src)| ByKey(k, src))
+      }),
+      |n| n,
+    );
+    let order = merged
+      .iter()
+      .map(|ByKey(k, src)| (*k, *src))
+      .collect::<Vec<_>>();
+    assert_eq!(order, [(1, 'a'), (1, 'b'), (1, 'c'), (2, 'a'), (2, 'b')]);
+  }
+
+  #[derive(Debug)]
+  struct ByKey(u8, char);
+
+  impl PartialEq for ByKey {
+    fn eq(&self, other: &Self) -> bool {
+      self.0 == other.0
+    }
+  }
+  impl Eq for ByKey {}
+  impl PartialOrd for ByKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+      Some(self.cmp(other))
+    }
+  }
+  impl Ord for ByKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+      self.0.cmp(&other.0)
+    }
+  }
+}
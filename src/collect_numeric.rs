@@ -0,0 +1,205 @@
+use alloc::string::String;
+
+use crate::common::get_max_hint_bound;
+
+/// Numbers that can be formatted without going through `core::fmt`.
+///
+/// Integers use [itoa](https://docs.rs/itoa) (feature = "itoa") and floats
+/// use [ryu](https://docs.rs/ryu) (feature = "ryu"). This trait is sealed.
+pub trait FastNumber: Copy + sealed::Sealed {
+  #[doc(hidden)]
+  fn with_formatted<R>(self, f: impl FnOnce(&str) -> R) -> R;
+}
+
+mod sealed {
+  pub trait Sealed {}
+}
+
+macro_rules! impl_fast_number {
+  ($buffer:path => $($t:ty),+) => {$(
+    impl sealed::Sealed for $t {}
+
+    impl FastNumber for $t {
+      fn with_formatted<R>(self, f: impl FnOnce(&str) -> R) -> R {
+        let mut buf = <$buffer>::new();
+        f(buf.format(self))
+      }
+    }
+  )+};
+}
+
+#[cfg(feature = "itoa")]
+impl_fast_number!(itoa::Buffer => i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(feature = "ryu")]
+impl_fast_number!(ryu::Buffer => f32, f64);
+
+impl<I: Iterator> CollectNumeric for I {}
+
+/// Trait for joining numbers into a `String` with fast formatting.
+pub trait CollectNumeric: Iterator {
+  /// Formats each number directly into a pre-sized `String`, separated by
+  /// `sep`.
+  ///
+  /// - `capacity`
+  ///   - Closure that calculates the **byte** capacity from the **value** count
+  ///     bound of the size hint. A good estimate is the bound times the typical
+  ///     digit count plus `sep.len()`, e.g. `|n| n * 4` for values below 1000
+  ///     with a 1-byte separator. The worst cases are 20 bytes for `i64`/`u64`
+  ///     (40 for 128-bit) and 24 bytes for `f64`.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// Integer output is identical to `Display`. Float output is ryu's shortest
+  /// round-trip form, which differs from `Display` for some values, e.g.
+  /// `1.0` (`Display`: `1`) and `1e20` (`Display`: `100000000000000000000`).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// # #[cfg(feature = "itoa")] {
+  /// use collect_with::CollectNumeric;
+  ///
+  /// let s = [3, -14, 159]
+  ///   .into_iter()
+  ///   .collect_numeric_string_with(",", |n| n * 4);
+  /// assert_eq!(s, "3,-14,159");
+  /// assert_eq!(s.capacity(), 12);
+  /// # }
+  /// ```
+  fn collect_numeric_string_with(
+    self,
+    sep: &str,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> String
+  where
+    Self: Sized,
+    Self::Item: FastNumber,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut s = String::with_capacity(capacity(bound).max(bound));
+    push_joined(&mut s, self, sep);
+    s
+  }
+
+  /// Like
+  /// [collect_numeric_string_with()](CollectNumeric::collect_numeric_string_with),
+  /// with a capacity of exactly the output length.
+  ///
+  /// The iterator is cloned and every value is formatted twice: once to
+  /// measure, once to write.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// # #[cfg(feature = "itoa")] {
+  /// use collect_with::CollectNumeric;
+  ///
+  /// let s = (98..=101u32).collect_numeric_string_exact(" ");
+  /// assert_eq!(s, "98 99 100 101");
+  /// assert_eq!(s.capacity(), s.len());
+  /// # }
+  /// ```
+  fn collect_numeric_string_exact(self, sep: &str) -> String
+  where
+    Self: Sized + Clone,
+    Self::Item: FastNumber,
+  {
+    let (count, digits): (usize, usize) = self
+      .clone()
+      .fold((0, 0), |(count, len), n| {
+        (count + 1, len + n.with_formatted(str::len))
+      });
+    let total = digits + sep.len() * count.saturating_sub(1);
+
+    let mut s = String::with_capacity(total);
+    push_joined(&mut s, self, sep);
+    s
+  }
+}
+
+fn push_joined<T: FastNumber>(
+  s: &mut String,
+  iter: impl Iterator<Item = T>,
+  sep: &str,
+) {
+  for (i, n) in iter.enumerate() {
+    if i != 0 {
+      s.push_str(sep);
+    }
+    n.with_formatted(|digits| s.push_str(digits));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{string::ToString, vec::Vec};
+
+  use super::*;
+
+  fn display_join<T: ToString>(values: &[T], sep: &str) -> String {
+    values
+      .iter()
+      .map(T::to_string)
+      .collect::<Vec<_>>()
+      .join(sep)
+  }
+
+  #[cfg(feature = "itoa")]
+  #[test]
+  fn integers_match_display() {
+    let values = [0, 1, -1, 42, -999, i64::MIN, i64::MAX];
+    let s = values
+      .iter()
+      .copied()
+      .collect_numeric_string_with(", ", |n| n * 8);
+    assert_eq!(s, display_join(&values, ", "));
+
+    let values = [u128::MIN, 7, u128::MAX];
+    let s = values
+      .iter()
+      .copied()
+      .collect_numeric_string_exact(";");
+    assert_eq!(s, display_join(&values, ";"));
+    assert_eq!(s.capacity(), s.len());
+
+    let values = (-300..300i16).collect::<Vec<_>>();
+    let s = values
+      .iter()
+      .copied()
+      .collect_numeric_string_exact("");
+    assert_eq!(s, display_join(&values, ""));
+    assert_eq!(s.capacity(), s.len());
+  }
+
+  #[cfg(feature = "ryu")]
+  #[test]
+  fn floats_round_trip() {
+    let values = [0.5, -2.25, 1e-7, f64::MAX, f64::MIN_POSITIVE];
+    let s = values
+      .iter()
+      .copied()
+      .collect_numeric_string_exact(" ");
+    assert_eq!(s.capacity(), s.len());
+    let parsed = s
+      .split(' ')
+      .map(|x| x.parse::<f64>().unwrap())
+      .collect::<Vec<_>>();
+    assert_eq!(parsed, values);
+
+    // Display agrees wherever it has a fractional part
+    let values = [0.5f32, -2.25, 3.75];
+    let s = values
+      .into_iter()
+      .collect_numeric_string_with(",", |n| n);
+    assert_eq!(s, display_join(&values, ","));
+  }
+
+  #[cfg(feature = "itoa")]
+  #[test]
+  fn empty_input() {
+    let s = core::iter::empty::<u8>().collect_numeric_string_exact(",");
+    assert!(s.is_empty());
+    assert_eq!(s.capacity(), 0);
+  }
+}
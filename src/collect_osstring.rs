@@ -0,0 +1,197 @@
+use std::ffi::{OsStr, OsString};
+
+use crate::{ExtendWithCapacity, collect::CollectWith};
+#[cfg(feature = "try")]
+use crate::{TryCollectWith, TryExtract};
+
+impl<I: Iterator> CollectOsString for I {}
+
+/// Trait for concatenating fragments into an [`OsString`] with a specified
+/// capacity.
+///
+/// `OsString` capacities and [`OsStr::len()`] are measured in bytes of the
+/// platform's internal encoding (the raw bytes on Unix, WTF-8 on Windows),
+/// not in fragments or chars. The capacity closures receive the fragment
+/// count bound of the size hint and are expected to return bytes.
+pub trait CollectOsString: Iterator {
+  /// Concatenate fragments into an `OsString`.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))` bytes.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::ffi::OsStr;
+  ///
+  /// use collect_with::CollectOsString;
+  ///
+  /// let s = [OsStr::new("PATH="), OsStr::new("/bin")]
+  ///   .into_iter()
+  ///   .collect_osstring_with(|n| n * 8);
+  /// assert_eq!(s, "PATH=/bin");
+  /// assert!(s.capacity() >= 16);
+  /// ```
+  fn collect_osstring_with(self, capacity: impl FnOnce(usize) -> usize) -> OsString
+  where
+    OsString: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Concatenate fragments into an `OsString`, passing the closure result
+  /// through unchanged.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::ffi::OsString;
+  ///
+  /// use collect_with::CollectOsString;
+  ///
+  /// let s = [OsString::from("a"), OsString::from("b")]
+  ///   .into_iter()
+  ///   .collect_osstring_with_exact(|_| 32);
+  /// assert!(s.capacity() >= 32);
+  /// ```
+  fn collect_osstring_with_exact(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> OsString
+  where
+    OsString: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    self.collect_with_exact(capacity)
+  }
+
+  /// Concatenate fragments into an `OsString` of exactly the total length.
+  ///
+  /// The iterator is cloned and walked twice: once to sum the
+  /// [`OsStr::len()`] of the fragments and once to copy them.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::ffi::OsStr;
+  ///
+  /// use collect_with::CollectOsString;
+  ///
+  /// let dirs = ["/usr/bin", ":", "/usr/local/bin"].map(OsStr::new);
+  /// let s = dirs.iter().collect_osstring_exact();
+  /// assert_eq!(s.len(), 23);
+  /// assert_eq!(s.capacity(), s.len());
+  /// ```
+  fn collect_osstring_exact(self) -> OsString
+  where
+    Self: Sized + Clone,
+    Self::Item: AsRef<OsStr>,
+  {
+    let total = self
+      .clone()
+      .map(|fragment| fragment.as_ref().len())
+      .sum();
+    let mut s = OsString::with_capacity(total);
+    self.for_each(|fragment| s.push(fragment));
+    s
+  }
+
+  /// Attempt to concatenate fallible fragments into an `OsString`,
+  /// short-circuiting on the first error.
+  ///
+  /// The capacity rule is that of
+  /// [collect_osstring_with()](CollectOsString::collect_osstring_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::path::Path;
+  ///
+  /// use collect_with::CollectOsString;
+  ///
+  /// let stems = [Path::new("a.rs"), Path::new("b.rs")]
+  ///   .iter()
+  ///   .map(|p| p.file_stem())
+  ///   .try_collect_osstring_with(|n| n);
+  /// assert_eq!(stems.unwrap(), "ab");
+  /// ```
+  #[cfg(feature = "try")]
+  fn try_collect_osstring_with<'a, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<OsString, ERR>
+  where
+    OsString: ExtendWithCapacity<OK>,
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    self.try_collect_with(capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use super::*;
+
+  /// Checks that the exact variant allocated once for `parts`.
+  fn assert_exact(parts: &[OsString]) {
+    let s = parts
+      .iter()
+      .collect_osstring_exact();
+    let total: usize = parts
+      .iter()
+      .map(|p| p.len())
+      .sum();
+    assert_eq!(s.len(), total);
+    assert_eq!(s.capacity(), total);
+  }
+
+  #[test]
+  fn osstring_exact_utf8() {
+    let parts: Vec<OsString> = ["ключ", "=", "値", ";"]
+      .into_iter()
+      .cycle()
+      .take(64)
+      .map(OsString::from)
+      .collect();
+    assert_exact(&parts);
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn osstring_exact_non_utf8_unix() {
+    use std::os::unix::ffi::OsStringExt;
+
+    let parts = [
+      OsString::from_vec(alloc::vec![0x66, 0xff, 0x6f]),
+      OsString::from("/"),
+      OsString::from_vec(alloc::vec![0x80; 5]),
+    ];
+    assert_exact(&parts);
+  }
+
+  #[cfg(windows)]
+  #[test]
+  fn osstring_exact_non_utf16_windows() {
+    use std::os::windows::ffi::OsStringExt;
+
+    // An unpaired surrogate takes 3 bytes in WTF-8.
+    let parts = [
+      OsString::from_wide(&[0x66, 0xd800, 0x6f]),
+      OsString::from("\\"),
+      OsString::from_wide(&[0xdc00]),
+    ];
+    assert_exact(&parts);
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_osstring_short_circuits() {
+    let err = [Some(OsStr::new("a")), None]
+      .into_iter()
+      .try_collect_osstring_with(|n| n);
+    assert_eq!(err, Err(()));
+  }
+}
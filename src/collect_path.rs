@@ -0,0 +1,278 @@
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "camino")]
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::collect::CollectWith;
+#[cfg(feature = "try")]
+use crate::{TryCollectWith, TryExtract};
+
+impl<I: Iterator> CollectPath for I {}
+
+/// Trait for collecting path components into a path buffer with a specified
+/// capacity.
+///
+/// Path buffers are sized in bytes, while the size hint counts components.
+/// [collect_pathbuf_with()](CollectPath::collect_pathbuf_with) therefore
+/// measures the components first and hands the byte total to the capacity
+/// closure; the other methods read the iterator once and expect the closure
+/// to turn a component count into a byte count.
+pub trait CollectPath: Iterator {
+  /// Joins path components into a [`PathBuf`], sizing it from their
+  /// measured byte length.
+  ///
+  /// - `capacity`
+  ///   - Closure that receives the **byte** total of the components, one
+  ///     separator each included, e.g. `|n| n + 16` to leave room for a file
+  ///     name pushed later
+  ///
+  /// The iterator is cloned and walked twice: once to sum
+  /// `as_os_str().len() + 1` per component, and once to push the components.
+  /// The final capacity is `max(total, capacity(total))`, so `|n| n`
+  /// allocates once. Cloning is cheap for borrowing iterators such as
+  /// `slice::Iter`; for iterators that cannot be cloned, use the one-pass
+  /// [collect_pathbuf_with_exact()](CollectPath::collect_pathbuf_with_exact)
+  /// with a byte estimate. As with [`PathBuf::push`], an absolute component
+  /// replaces what came before.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::path::Path;
+  ///
+  /// use collect_with::CollectPath;
+  ///
+  /// let mut path = ["usr", "share", "doc"]
+  ///   .iter()
+  ///   .collect_pathbuf_with(|n| n + 8);
+  /// assert_eq!(path, Path::new("usr/share/doc"));
+  /// assert_eq!(path.capacity(), 14 + 8);
+  ///
+  /// path.push("README");
+  /// assert_eq!(path.capacity(), 22);
+  /// ```
+  fn collect_pathbuf_with(self, capacity: impl FnOnce(usize) -> usize) -> PathBuf
+  where
+    Self: Sized + Clone,
+    Self::Item: AsRef<Path>,
+  {
+    let total = self
+      .clone()
+      .map(|part| part.as_ref().as_os_str().len() + 1)
+      .sum();
+    let mut path = PathBuf::with_capacity(capacity(total).max(total));
+    self.for_each(|part| path.push(part));
+    path
+  }
+
+  /// Joins path components into a [`PathBuf`] in one pass, passing the
+  /// closure result through unchanged.
+  ///
+  /// - `capacity`
+  ///   - Closure that receives the size hint (number of components) and returns
+  ///     the number of **bytes** to reserve
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectPath;
+  ///
+  /// let path = ["a", "b"].into_iter().collect_pathbuf_with_exact(|_| 64);
+  /// assert!(path.capacity() >= 64);
+  /// ```
+  fn collect_pathbuf_with_exact(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> PathBuf
+  where
+    Self: Sized,
+    Self::Item: AsRef<Path>,
+  {
+    self.collect_with_exact(capacity)
+  }
+
+  /// Joins path components into a [`PathBuf`] allocated once from their
+  /// total length.
+  ///
+  /// Shorthand for
+  /// [collect_pathbuf_with(|n| n)](CollectPath::collect_pathbuf_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectPath;
+  ///
+  /// let parts = ["home", "user", ".config", "app"];
+  /// let path = parts.iter().collect_pathbuf_exact();
+  /// assert_eq!(path.as_os_str().len(), 21);
+  /// assert_eq!(path.capacity(), 22);
+  /// ```
+  fn collect_pathbuf_exact(self) -> PathBuf
+  where
+    Self: Sized + Clone,
+    Self::Item: AsRef<Path>,
+  {
+    self.collect_pathbuf_with(|total| total)
+  }
+
+  /// Attempts to join fallible path components into a [`PathBuf`],
+  /// short-circuiting on the first error, e.g. on a `None` from
+  /// [`Path::file_name()`].
+  ///
+  /// The components are read once, so the capacity closure receives the
+  /// component count bound and returns a byte count; the final capacity is
+  /// `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::path::Path;
+  ///
+  /// use collect_with::CollectPath;
+  ///
+  /// let files = [Path::new("/a/x.txt"), Path::new("/b/y.txt")];
+  /// let flat = files
+  ///   .iter()
+  ///   .map(|p| p.file_name())
+  ///   .try_collect_pathbuf_with(|n| n * 8);
+  /// assert_eq!(flat.unwrap(), Path::new("x.txt/y.txt"));
+  ///
+  /// let err = [Path::new("/")]
+  ///   .iter()
+  ///   .map(|p| p.file_name())
+  ///   .try_collect_pathbuf_with(|n| n);
+  /// assert!(err.is_err());
+  /// ```
+  #[cfg(feature = "try")]
+  fn try_collect_pathbuf_with<'a, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<PathBuf, ERR>
+  where
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+    OK: AsRef<Path>,
+  {
+    self.try_collect_with(capacity)
+  }
+
+  /// Joins path components into a [`Utf8PathBuf`].
+  ///
+  /// - `capacity`
+  ///   - Closure that receives the size hint (number of components) and returns
+  ///     the number of **bytes** to reserve
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`, the same
+  /// rule as [collect_with()](crate::CollectWith::collect_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectPath;
+  ///
+  /// let path = ["usr", "local", "bin"]
+  ///   .into_iter()
+  ///   .collect_utf8_pathbuf_with(|n| n * 8);
+  /// assert_eq!(path, "usr/local/bin");
+  /// assert!(path.capacity() >= 24);
+  /// ```
+  #[cfg(feature = "camino")]
+  fn collect_utf8_pathbuf_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Utf8PathBuf
+  where
+    Self: Sized,
+    Self::Item: AsRef<Utf8Path>,
+  {
+    self.collect_with(capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn pathbuf_exact_deep_path() {
+    use alloc::{format, string::String, vec::Vec};
+
+    let parts: Vec<String> = (0..50)
+      .map(|i| format!("dir{i}"))
+      .collect();
+    let expected: usize = parts
+      .iter()
+      .map(|p| p.len() + 1)
+      .sum();
+    let mut seen = None;
+    let path = parts
+      .iter()
+      .collect_pathbuf_with(|n| {
+        seen = Some(n);
+        n
+      });
+    assert_eq!(seen, Some(expected));
+    assert_eq!(path.components().count(), 50);
+    // Allocated once: the capacity is still the measured total.
+    assert_eq!(path.capacity(), expected);
+    assert!(path.as_os_str().len() < expected);
+
+    let exact = parts
+      .iter()
+      .collect_pathbuf_exact();
+    assert_eq!(exact, path);
+    assert_eq!(exact.capacity(), expected);
+
+    // The one-pass method only sees the component count.
+    let one_pass = parts
+      .iter()
+      .collect_pathbuf_with_exact(|n| n * 6);
+    assert_eq!(one_pass, path);
+    assert_eq!(one_pass.capacity(), 50 * 6);
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_pathbuf_from_os_str() {
+    use std::ffi::OsStr;
+
+    let ok = [Some(OsStr::new("a")), Some(OsStr::new("b"))]
+      .into_iter()
+      .try_collect_pathbuf_with(|n| n * 4);
+    assert_eq!(ok, Ok(PathBuf::from("a/b")));
+
+    let err = [Some(OsStr::new("a")), None]
+      .into_iter()
+      .try_collect_pathbuf_with(|n| n);
+    assert_eq!(err, Err(()));
+  }
+
+  #[cfg(feature = "camino")]
+  #[test]
+  fn utf8_pathbuf_from_components() {
+    let parts = ["/", "var", "log", "app", "current.log"];
+    let path = parts
+      .into_iter()
+      .collect_utf8_pathbuf_with(|_| 64);
+    assert_eq!(path, "/var/log/app/current.log");
+    assert!(path.capacity() >= 64);
+
+    // Owned components and a hint-derived byte count.
+    let owned = parts.map(Utf8PathBuf::from);
+    let path = owned
+      .iter()
+      .collect_utf8_pathbuf_with(|n| n * 16);
+    assert_eq!(path.components().count(), 5);
+    assert!(path.capacity() >= parts.len() * 16);
+  }
+
+  #[cfg(feature = "camino")]
+  #[test]
+  fn utf8_pathbuf_absolute_component_resets() {
+    // Matches `Utf8PathBuf::push`: an absolute component replaces the buffer.
+    let path = ["a", "b", "/c"]
+      .into_iter()
+      .collect_utf8_pathbuf_with(|n| n);
+    assert_eq!(path, "/c");
+  }
+}
@@ -0,0 +1,213 @@
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use alloc::{rc::Rc, string::String, vec::Vec};
+
+use crate::{ExtendWithCapacity, common::collect_iterator};
+#[cfg(feature = "try")]
+use crate::{TryExtract, common::try_collect_iterator};
+
+impl<I: Iterator> CollectShared for I {}
+
+/// Trait for collecting items into shared, immutable slices: `Arc<[T]>`,
+/// `Rc<[T]>` and `Arc<str>`.
+///
+/// The reference counts live in the same allocation as the items, so a
+/// `Vec` or `String` cannot be turned into one in place. When the size hint
+/// is exact (`lower == upper`), the items are handed to the standard
+/// `FromIterator` impl, which writes straight into the shared allocation for
+/// iterators like ranges, slices and their `map`s. Otherwise the items are
+/// gathered in a buffer sized by the capacity closure, so collection itself
+/// never regrows, and copied over once at the end.
+pub trait CollectShared: Iterator {
+  /// Collects items into an `Arc<[T]>`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::sync::Arc;
+  ///
+  /// use collect_with::CollectShared;
+  ///
+  /// let ids: Arc<[u32]> = (1..=3).collect_arc_slice_with(|n| n);
+  /// let shared = Arc::clone(&ids);
+  /// std::thread::spawn(move || assert_eq!(*shared, [1, 2, 3]))
+  ///   .join()
+  ///   .unwrap();
+  /// ```
+  #[cfg(target_has_atomic = "ptr")]
+  fn collect_arc_slice_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Arc<[Self::Item]>
+  where
+    Self: Sized,
+  {
+    if has_exact_hint(&self) {
+      return self.collect();
+    }
+    collect_iterator::<Vec<_>, _>(self, false, capacity).into()
+  }
+
+  /// Collects items into an `Rc<[T]>`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectShared;
+  ///
+  /// let words = "a b c".split(' ').collect_rc_slice_with(|n| n.max(3));
+  /// assert_eq!(*words, ["a", "b", "c"]);
+  /// ```
+  fn collect_rc_slice_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Rc<[Self::Item]>
+  where
+    Self: Sized,
+  {
+    if has_exact_hint(&self) {
+      return self.collect();
+    }
+    collect_iterator::<Vec<_>, _>(self, false, capacity).into()
+  }
+
+  /// Collects `char`s or string slices into an `Arc<str>`.
+  ///
+  /// The text is built in a `String` sized by the capacity closure, then
+  /// copied once into the shared allocation.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectShared;
+  ///
+  /// let name = ["collect", "-", "with"]
+  ///   .into_iter()
+  ///   .collect_arc_str_with(|n| n * 8);
+  /// assert_eq!(&*name, "collect-with");
+  /// ```
+  #[cfg(target_has_atomic = "ptr")]
+  fn collect_arc_str_with(self, capacity: impl FnOnce(usize) -> usize) -> Arc<str>
+  where
+    Self: Sized,
+    String: ExtendWithCapacity<Self::Item>,
+  {
+    collect_iterator::<String, _>(self, false, capacity).into()
+  }
+
+  /// Attempts to collect items into an `Arc<[OK]>`, short-circuiting on the
+  /// first error like
+  /// [try_collect_with()](crate::TryCollectWith::try_collect_with).
+  ///
+  /// Items are always gathered in a buffer first, since the error is only
+  /// known once it is reached.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectShared;
+  ///
+  /// let nums = ["1", "2"]
+  ///   .into_iter()
+  ///   .map(str::parse::<u8>)
+  ///   .try_collect_arc_slice_with(|n| n);
+  /// assert_eq!(nums.as_deref(), Ok(&[1, 2][..]));
+  /// ```
+  #[cfg(all(feature = "try", target_has_atomic = "ptr"))]
+  fn try_collect_arc_slice_with<'a, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<Arc<[OK]>, ERR>
+  where
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    try_collect_iterator::<Vec<_>, _, _, _>(self, false, capacity).map(Into::into)
+  }
+
+  /// Attempts to collect items into an `Rc<[OK]>`, short-circuiting on the
+  /// first error.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectShared;
+  ///
+  /// let err = [Some(1), None, Some(3)]
+  ///   .into_iter()
+  ///   .try_collect_rc_slice_with(|n| n);
+  /// assert!(err.is_err());
+  /// ```
+  #[cfg(feature = "try")]
+  fn try_collect_rc_slice_with<'a, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<Rc<[OK]>, ERR>
+  where
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    try_collect_iterator::<Vec<_>, _, _, _>(self, false, capacity).map(Into::into)
+  }
+}
+
+/// Whether the hint pins down the exact number of items.
+fn has_exact_hint(iter: &impl Iterator) -> bool {
+  let (lower, upper) = iter.size_hint();
+  upper == Some(lower)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn shared_slices_from_any_hint() {
+    let exact = (0..5).collect_rc_slice_with(|n| n);
+    assert_eq!(*exact, [0, 1, 2, 3, 4]);
+
+    let mut seen = None;
+    let unknown = HintedIter::bounds(0..5, 0, None).collect_rc_slice_with(|n| {
+      seen = Some(n);
+      8
+    });
+    assert_eq!(*unknown, *exact);
+    // Only the buffered path consults the closure.
+    assert_eq!(seen, Some(0));
+
+    let empty = core::iter::empty::<u8>().collect_rc_slice_with(|n| n);
+    assert!(empty.is_empty());
+  }
+
+  #[cfg(target_has_atomic = "ptr")]
+  #[test]
+  fn arc_slice_and_str() {
+    let arc =
+      HintedIter::bounds("xyz".chars(), 1, None).collect_arc_slice_with(|n| n);
+    assert_eq!(*arc, ['x', 'y', 'z']);
+    let other = Arc::clone(&arc);
+    assert_eq!(Arc::strong_count(&other), 2);
+
+    let s = "héllo"
+      .chars()
+      .rev()
+      .collect_arc_str_with(|n| n);
+    assert_eq!(&*s, "olléh");
+  }
+
+  #[cfg(all(feature = "try", target_has_atomic = "ptr"))]
+  #[test]
+  fn try_shared_slices() {
+    let ok = [Ok::<_, &str>(1), Ok(2)]
+      .into_iter()
+      .try_collect_arc_slice_with(|n| n)
+      .unwrap();
+    assert_eq!(*ok, [1, 2]);
+
+    let err = [Ok(1), Err("bad")]
+      .into_iter()
+      .try_collect_rc_slice_with(|n| n);
+    assert_eq!(err, Err("bad"));
+  }
+}
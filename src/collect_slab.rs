@@ -0,0 +1,115 @@
+use alloc::vec::Vec;
+
+use slab::Slab;
+
+use crate::common::get_max_hint_bound;
+
+impl<I: Iterator> CollectSlab for I {}
+
+/// Trait for collecting items into a [`Slab`] with a specified capacity.
+///
+/// `Slab` implements neither `Extend` nor `FromIterator<T>`, so it cannot be
+/// an [`ExtendWithCapacity`](crate::ExtendWithCapacity) target; these
+/// methods insert the items one by one into a slab allocated up front.
+pub trait CollectSlab: Iterator {
+  /// Collects items into a `Slab`.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`. Items get
+  /// the keys `0..n` in insertion order.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectSlab;
+  ///
+  /// let slab = ["a", "b", "c"]
+  ///   .into_iter()
+  ///   .collect_slab_with(|n| n + 1);
+  /// assert_eq!(slab[1], "b");
+  /// assert!(slab.capacity() >= 4);
+  /// ```
+  fn collect_slab_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Slab<Self::Item>
+  where
+    Self: Sized,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut slab = Slab::with_capacity(capacity(bound).max(bound));
+    for item in self {
+      slab.insert(item);
+    }
+    slab
+  }
+
+  /// Collects items into a `Slab`, also returning the assigned keys in
+  /// insertion order.
+  ///
+  /// The key `Vec` is pre-sized with the same capacity as the slab.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectSlab;
+  ///
+  /// let (conns, keys) = ["10.0.0.1", "10.0.0.2"]
+  ///   .into_iter()
+  ///   .collect_slab_keys_with(|n| n);
+  /// assert_eq!(conns[keys[1]], "10.0.0.2");
+  /// ```
+  fn collect_slab_keys_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (Slab<Self::Item>, Vec<usize>)
+  where
+    Self: Sized,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let cap = capacity(bound).max(bound);
+    let mut slab = Slab::with_capacity(cap);
+    let mut keys = Vec::with_capacity(cap);
+    for item in self {
+      keys.push(slab.insert(item));
+    }
+    (slab, keys)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn slab_keys_dense_in_order() {
+    let (slab, keys) = ('a'..='e').collect_slab_keys_with(|n| n);
+    assert_eq!(keys, [0, 1, 2, 3, 4]);
+    assert!(
+      keys
+        .iter()
+        .zip('a'..)
+        .all(|(&k, c)| slab[k] == c)
+    );
+    assert_eq!(slab.capacity(), 5);
+    assert_eq!(keys.capacity(), 5);
+
+    let (slab, keys) =
+      HintedIter::bounds(0..3, 0, None).collect_slab_keys_with(|_| 16);
+    assert_eq!(slab.len(), 3);
+    assert!(slab.capacity() >= 16);
+    assert!(keys.capacity() >= 16);
+  }
+
+  #[test]
+  fn slab_with_capacity() {
+    let slab = (0..8).collect_slab_with(|n| n * 2);
+    assert!(
+      slab
+        .iter()
+        .map(|(k, &v)| (k, v))
+        .eq((0..8).zip(0..8))
+    );
+    assert!(slab.capacity() >= 16);
+  }
+}
@@ -0,0 +1,287 @@
+use alloc::vec::Vec;
+
+#[allow(deprecated)]
+use slotmap::HopSlotMap;
+use slotmap::{DenseSlotMap, Key, SlotMap};
+
+use crate::common::get_max_hint_bound;
+
+impl<I: Iterator> CollectSlotMap for I {}
+
+/// Trait for collecting items into the slot maps of the [`slotmap`] crate
+/// with a specified capacity.
+///
+/// Like [`Slab`](https://docs.rs/slab), slot maps generate a key for every
+/// inserted value and cannot be built through `Extend`. These methods insert
+/// the items one by one into a map allocated with `with_capacity_and_key`,
+/// so the key type `K` is chosen by the caller, typically one declared with
+/// [`slotmap::new_key_type!`].
+///
+/// [`SlotMap`], [`DenseSlotMap`] and [`HopSlotMap`] each have a plain and a
+/// `_keys` variant. `HopSlotMap` is deprecated upstream in favour of the
+/// other two and is only kept here for code that still uses it.
+pub trait CollectSlotMap: Iterator {
+  /// Collects items into a [`SlotMap`].
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectSlotMap;
+  /// use slotmap::{DefaultKey, SlotMap};
+  ///
+  /// let names: SlotMap<DefaultKey, _> = ["orc", "elf"]
+  ///   .into_iter()
+  ///   .collect_slotmap_with(|n| n + 2);
+  /// assert_eq!(names.len(), 2);
+  /// assert!(names.capacity() >= 4);
+  /// ```
+  fn collect_slotmap_with<K: Key>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> SlotMap<K, Self::Item>
+  where
+    Self: Sized,
+  {
+    insert_all(self, capacity, |_| {})
+  }
+
+  /// Collects items into a [`SlotMap`], also returning the generated keys in
+  /// insertion order.
+  ///
+  /// The key `Vec` is pre-sized with the same capacity as the map.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectSlotMap;
+  /// use slotmap::new_key_type;
+  ///
+  /// new_key_type! { struct Entity; }
+  ///
+  /// let (world, ids) = ["player", "goblin"]
+  ///   .into_iter()
+  ///   .collect_slotmap_keys_with::<Entity>(|n| n);
+  /// assert_eq!(world[ids[1]], "goblin");
+  /// ```
+  fn collect_slotmap_keys_with<K: Key>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (SlotMap<K, Self::Item>, Vec<K>)
+  where
+    Self: Sized,
+  {
+    insert_all_keyed(self, capacity)
+  }
+
+  /// Collects items into a [`DenseSlotMap`].
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectSlotMap;
+  /// use slotmap::{DefaultKey, DenseSlotMap};
+  ///
+  /// let map: DenseSlotMap<DefaultKey, _> = (0..4).collect_dense_slotmap_with(|n| n);
+  /// assert!(map.values().copied().eq(0..4));
+  /// ```
+  fn collect_dense_slotmap_with<K: Key>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> DenseSlotMap<K, Self::Item>
+  where
+    Self: Sized,
+  {
+    insert_all(self, capacity, |_| {})
+  }
+
+  /// Collects items into a [`DenseSlotMap`], also returning the generated
+  /// keys in insertion order.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectSlotMap;
+  /// use slotmap::DefaultKey;
+  ///
+  /// let (map, keys) = "ab".chars().collect_dense_slotmap_keys_with::<DefaultKey>(|n| n);
+  /// assert_eq!(map[keys[0]], 'a');
+  /// ```
+  fn collect_dense_slotmap_keys_with<K: Key>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (DenseSlotMap<K, Self::Item>, Vec<K>)
+  where
+    Self: Sized,
+  {
+    insert_all_keyed(self, capacity)
+  }
+
+  /// Collects items into a [`HopSlotMap`].
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// # #![allow(deprecated)]
+  /// use collect_with::CollectSlotMap;
+  /// use slotmap::{DefaultKey, HopSlotMap};
+  ///
+  /// let map: HopSlotMap<DefaultKey, _> = (0..4).collect_hop_slotmap_with(|n| n * 2);
+  /// assert!(map.capacity() >= 8);
+  /// ```
+  #[allow(deprecated)]
+  fn collect_hop_slotmap_with<K: Key>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> HopSlotMap<K, Self::Item>
+  where
+    Self: Sized,
+  {
+    insert_all(self, capacity, |_| {})
+  }
+
+  /// Collects items into a [`HopSlotMap`], also returning the generated keys
+  /// in insertion order.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectSlotMap;
+  /// use slotmap::DefaultKey;
+  ///
+  /// let (map, keys) = (10..13).collect_hop_slotmap_keys_with::<DefaultKey>(|n| n);
+  /// assert_eq!(map[keys[2]], 12);
+  /// ```
+  #[allow(deprecated)]
+  fn collect_hop_slotmap_keys_with<K: Key>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (HopSlotMap<K, Self::Item>, Vec<K>)
+  where
+    Self: Sized,
+  {
+    insert_all_keyed(self, capacity)
+  }
+}
+
+/// The construction shared by the three slot map flavours.
+trait Slots<K, V> {
+  fn with_capacity_and_key(capacity: usize) -> Self;
+  fn insert(&mut self, value: V) -> K;
+}
+
+macro_rules! impl_slots {
+  ($($map:ident),*) => {$(
+    #[allow(deprecated)]
+    impl<K: Key, V> Slots<K, V> for $map<K, V> {
+      fn with_capacity_and_key(capacity: usize) -> Self {
+        $map::with_capacity_and_key(capacity)
+      }
+
+      fn insert(&mut self, value: V) -> K {
+        $map::insert(self, value)
+      }
+    }
+  )*};
+}
+
+impl_slots!(SlotMap, DenseSlotMap, HopSlotMap);
+
+fn insert_all<I, K, M>(
+  iter: I,
+  capacity: impl FnOnce(usize) -> usize,
+  on_key: impl FnMut(K),
+) -> M
+where
+  I: Iterator,
+  M: Slots<K, I::Item>,
+{
+  let bound = get_max_hint_bound(iter.size_hint());
+  fill(iter, capacity(bound).max(bound), on_key)
+}
+
+fn insert_all_keyed<I, K, M>(
+  iter: I,
+  capacity: impl FnOnce(usize) -> usize,
+) -> (M, Vec<K>)
+where
+  I: Iterator,
+  M: Slots<K, I::Item>,
+{
+  let bound = get_max_hint_bound(iter.size_hint());
+  let cap = capacity(bound).max(bound);
+  let mut keys = Vec::with_capacity(cap);
+  let map = fill(iter, cap, |k| keys.push(k));
+  (map, keys)
+}
+
+fn fill<I, K, M>(iter: I, cap: usize, mut on_key: impl FnMut(K)) -> M
+where
+  I: Iterator,
+  M: Slots<K, I::Item>,
+{
+  let mut map = M::with_capacity_and_key(cap);
+  for item in iter {
+    on_key(map.insert(item));
+  }
+  map
+}
+
+#[cfg(test)]
+mod tests {
+  use slotmap::DefaultKey;
+
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn slotmap_keys_follow_insertion_order() {
+    let (map, keys) = ('a'..='e').collect_slotmap_keys_with::<DefaultKey>(|n| n);
+    assert!(
+      keys
+        .iter()
+        .map(|&k| map[k])
+        .eq('a'..='e')
+    );
+    assert!(map.capacity() >= 5);
+    assert_eq!(keys.capacity(), 5);
+
+    let (map, keys) = (0..3).collect_dense_slotmap_keys_with::<DefaultKey>(|n| n);
+    assert!(
+      keys
+        .iter()
+        .map(|&k| map[k])
+        .eq(0..3)
+    );
+
+    let (map, keys) = (0..3).collect_hop_slotmap_keys_with::<DefaultKey>(|n| n);
+    assert!(
+      keys
+        .iter()
+        .map(|&k| map[k])
+        .eq(0..3)
+    );
+  }
+
+  #[test]
+  fn slotmap_capacity_from_closure() {
+    let hinted = || HintedIter::bounds(0..3, 0, None);
+
+    let map: SlotMap<DefaultKey, _> = hinted().collect_slotmap_with(|_| 16);
+    assert_eq!(map.len(), 3);
+    assert!(map.capacity() >= 16);
+
+    let map: DenseSlotMap<DefaultKey, _> =
+      hinted().collect_dense_slotmap_with(|_| 16);
+    assert!(map.capacity() >= 16);
+
+    let (map, keys) = hinted().collect_hop_slotmap_keys_with::<DefaultKey>(|_| 16);
+    assert!(map.capacity() >= 16);
+    assert!(keys.capacity() >= 16);
+  }
+}
@@ -0,0 +1,181 @@
+use smallvec::{Array, SmallVec};
+
+use crate::{collect::CollectWith, common::get_max_hint_bound};
+
+impl<I: Iterator> CollectSmallVec for I {}
+
+/// Trait for collecting items into a [`SmallVec`] with a specified capacity.
+///
+/// When the final capacity is at most the inline size `N` of `[T; N]`, the
+/// items are stored inline and nothing is allocated on the heap.
+pub trait CollectSmallVec: Iterator {
+  /// Collect iterator elements into a `SmallVec` with a capacity calculated
+  /// from the size hint.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectSmallVec;
+  ///
+  /// let sv = (0..3).collect_smallvec_with::<[_; 4]>(|n| n);
+  /// assert_eq!(sv.as_slice(), [0, 1, 2]);
+  /// assert!(!sv.spilled());
+  ///
+  /// let sv = (0..3).collect_smallvec_with::<[_; 4]>(|n| n + 8);
+  /// assert!(sv.spilled());
+  /// ```
+  fn collect_smallvec_with<A>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> SmallVec<A>
+  where
+    A: Array<Item = Self::Item>,
+    Self: Sized,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collect iterator elements into a `SmallVec` with exact capacity
+  /// calculation.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectSmallVec;
+  ///
+  /// let sv = "a b c"
+  ///   .split(' ')
+  ///   .collect_smallvec_with_exact::<[_; 4]>(|_| 3);
+  /// assert_eq!(sv.len(), 3);
+  /// assert!(!sv.spilled());
+  /// ```
+  fn collect_smallvec_with_exact<A>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> SmallVec<A>
+  where
+    A: Array<Item = Self::Item>,
+    Self: Sized,
+  {
+    self.collect_with_exact(capacity)
+  }
+
+  /// Concatenate slices into a `SmallVec`.
+  ///
+  /// - `capacity`
+  ///   - Closure that receives the **slice** count bound of the size hint and
+  ///     returns the number of **elements** to reserve
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`. Each
+  /// slice is appended with a single reservation for its length, like
+  /// [collect_bytes_with_stats()](crate::CollectVector::collect_bytes_with_stats)
+  /// does for `Vec`. `SmallVec::extend_from_slice` needs `T: Copy`, so the
+  /// elements are cloned through the slice iterator, whose exact length
+  /// provides that reservation.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectSmallVec;
+  ///
+  /// let tokens = [&["let", "x"][..], &["=", "1"], &[";"]];
+  /// let sv = tokens
+  ///   .into_iter()
+  ///   .collect_smallvec_from_slices_with::<[_; 8], _>(|n| n * 2);
+  /// assert_eq!(sv.as_slice(), ["let", "x", "=", "1", ";"]);
+  /// assert!(!sv.spilled());
+  /// ```
+  fn collect_smallvec_from_slices_with<'a, A, T>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> SmallVec<A>
+  where
+    A: Array<Item = T>,
+    T: Clone + 'a,
+    Self: Sized + Iterator<Item = &'a [T]>,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut sv = SmallVec::with_capacity(capacity(bound).max(bound));
+    for chunk in self {
+      sv.reserve(chunk.len());
+      sv.extend(chunk.iter().cloned());
+    }
+    sv
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn inline_when_capacity_fits() {
+    let sv = (0..8u8).collect_smallvec_with::<[_; 8]>(|n| n);
+    assert_eq!(sv.len(), 8);
+    assert!(!sv.spilled());
+
+    let sv = HintedIter::bounds(0..2u8, 0, None)
+      .collect_smallvec_with_exact::<[_; 8]>(|_| 6);
+    assert_eq!(sv.as_slice(), [0, 1]);
+    assert!(!sv.spilled());
+    assert_eq!(sv.capacity(), 8);
+  }
+
+  #[test]
+  fn spills_when_capacity_exceeds_inline() {
+    let sv = (0..2u8).collect_smallvec_with::<[_; 4]>(|_| 16);
+    assert!(sv.spilled());
+    assert_eq!(sv.capacity(), 16);
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_collect_into_smallvec() {
+    use crate::TryCollectWith;
+
+    let sv = [Some(1), Some(2)]
+      .into_iter()
+      .try_collect_with::<SmallVec<[u8; 4]>, _, _>(|n| n)
+      .unwrap();
+    assert_eq!(sv.as_slice(), [1, 2]);
+    assert!(!sv.spilled());
+
+    let err = [Ok(1), Err('x')]
+      .into_iter()
+      .try_collect_with::<SmallVec<[u8; 4]>, _, _>(|n| n);
+    assert_eq!(err, Err('x'));
+  }
+
+  #[test]
+  fn from_slices_matches_flatten() {
+    let chunks: [&[u16]; 5] = [&[1, 2, 3], &[], &[4, 5], &[], &[6]];
+    let flat = chunks
+      .into_iter()
+      .flatten()
+      .copied()
+      .collect_smallvec_with::<[_; 4]>(|n| n);
+
+    // Crosses the inline size of 4 in the middle of the second run.
+    let sv = chunks
+      .into_iter()
+      .collect_smallvec_from_slices_with::<[_; 4], _>(|n| n);
+    assert_eq!(sv, flat);
+    assert_eq!(sv.len(), 6);
+    assert!(sv.spilled());
+
+    let sv = HintedIter::bounds(chunks.into_iter(), 0, None)
+      .collect_smallvec_from_slices_with::<[_; 8], _>(|_| 8);
+    assert_eq!(sv.as_slice(), [1, 2, 3, 4, 5, 6]);
+    assert!(!sv.spilled());
+
+    let empty: [&[u16]; 2] = [&[], &[]];
+    let sv = empty
+      .into_iter()
+      .collect_smallvec_from_slices_with::<[_; 4], _>(|n| n);
+    assert!(sv.is_empty());
+    assert!(!sv.spilled());
+  }
+}
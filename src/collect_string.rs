@@ -0,0 +1,802 @@
+use alloc::string::String;
+
+use crate::{
+  ExtendWithCapacity, LenStats, collect::CollectWith, common::get_max_hint_bound,
+};
+#[cfg(feature = "try")]
+use crate::{TryCollectWith, TryExtract};
+
+/// Blanket implementation for iterators to add string collection capabilities.
+impl<I: Iterator> CollectString for I {}
+
+/// Trait providing enhanced `String` collection strategies for iterators.
+///
+/// Works for any item type `String` can be extended with, e.g. `char`,
+/// `&str`, `String` and `Cow<str>`.
+pub trait CollectString: Iterator {
+  /// Collect iterator elements into a `String` with a capacity calculated
+  /// from the size hint.
+  ///
+  /// Shorthand for `collect_with::<String>()`. The final capacity is
+  /// `max(size_bound, capacity(size_bound))`, in bytes.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectString;
+  ///
+  /// let s = [vec!["a"], vec!["b", "c", "d"]]
+  ///   .into_iter()
+  ///   .flatten()
+  ///   .collect_string_with(|size| match size {
+  ///     0 => 8,
+  ///     n => n,
+  ///   });
+  /// assert_eq!(s.len(), 4);
+  /// assert_eq!(s.capacity(), 8);
+  /// ```
+  fn collect_string_with(self, capacity: impl FnOnce(usize) -> usize) -> String
+  where
+    String: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collect iterator elements into a `String`, passing the closure result
+  /// through unchanged.
+  ///
+  /// Shorthand for `collect_with_exact::<String>()`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::borrow::Cow;
+  ///
+  /// use collect_with::CollectString;
+  ///
+  /// let parts = [Cow::Borrowed("ab"), Cow::Owned("cd".to_owned())];
+  /// let s = parts
+  ///   .into_iter()
+  ///   .collect_string_with_exact(|_| 4);
+  /// assert_eq!(s, "abcd");
+  /// assert_eq!(s.capacity(), 4);
+  /// ```
+  fn collect_string_with_exact(self, capacity: impl FnOnce(usize) -> usize) -> String
+  where
+    String: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    self.collect_with_exact(capacity)
+  }
+
+  /// Attempt to collect iterator elements into a `String`, short-circuiting
+  /// on the first error.
+  ///
+  /// Shorthand for `try_collect_with::<String, _, _>()`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectString;
+  ///
+  /// let s = [65u32, 66, 67]
+  ///   .into_iter()
+  ///   .map(char::from_u32)
+  ///   .try_collect_string_with(|n| n);
+  /// assert_eq!(s.as_deref(), Ok("ABC"));
+  ///
+  /// let err = [65u32, 0xD800]
+  ///   .into_iter()
+  ///   .map(char::from_u32)
+  ///   .try_collect_string_with(|n| n);
+  /// assert!(err.is_err());
+  /// ```
+  #[cfg(feature = "try")]
+  fn try_collect_string_with<'a, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<String, ERR>
+  where
+    String: ExtendWithCapacity<OK>,
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    self.try_collect_with(capacity)
+  }
+
+  /// Collect iterator elements into a `String` using the size hint bound as
+  /// the capacity, or
+  /// [`String::FALLBACK_CAPACITY`](crate::ExtendWithCapacity) (16 bytes) when
+  /// the bound is 0.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectString;
+  ///
+  /// let s = ['a', 'b', 'c'].into_iter().collect_string_hinted();
+  /// assert_eq!(s, "abc");
+  /// assert_eq!(s.capacity(), 3);
+  ///
+  /// let s = "a,b,c"
+  ///   .split(',')
+  ///   .filter(|x| !x.is_empty())
+  ///   .collect_string_hinted();
+  /// assert_eq!(s, "abc");
+  /// assert_eq!(s.capacity(), 16);
+  /// ```
+  fn collect_string_hinted(self) -> String
+  where
+    String: Extend<Self::Item>,
+    Self: Sized,
+  {
+    self.collect_hinted()
+  }
+
+  /// Hex-encode a byte iterator into a lowercase `String`.
+  ///
+  /// The output size is known up front (2 bytes per input byte), so the
+  /// `String` is created with `2 * size_bound` bytes and only grows if the
+  /// size hint was too low.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectString;
+  ///
+  /// let hex = [0xde, 0xad, 0xbe, 0xef]
+  ///   .into_iter()
+  ///   .collect_hex_string_with();
+  /// assert_eq!(hex, "deadbeef");
+  /// assert_eq!(hex.capacity(), hex.len());
+  /// ```
+  fn collect_hex_string_with(self) -> String
+  where
+    Self: Sized + Iterator<Item = u8>,
+  {
+    collect_hex(self, b"0123456789abcdef")
+  }
+
+  /// Hex-encode a byte iterator into an uppercase `String`.
+  ///
+  /// See [collect_hex_string_with()](crate::CollectString::collect_hex_string_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectString;
+  ///
+  /// let hex = [0x0a, 0xff]
+  ///   .into_iter()
+  ///   .collect_upper_hex_string_with();
+  /// assert_eq!(hex, "0AFF");
+  /// ```
+  fn collect_upper_hex_string_with(self) -> String
+  where
+    Self: Sized + Iterator<Item = u8>,
+  {
+    collect_hex(self, b"0123456789ABCDEF")
+  }
+
+  /// Concatenate string fragments into a pre-sized `String`.
+  ///
+  /// Accepts any `AsRef<str>` item, e.g. `Box<str>`, `Rc<str>`, `Arc<str>` or
+  /// `Cow<str>`, which `String` cannot be extended with directly. Each
+  /// fragment is appended with [`String::push_str()`], so nothing is
+  /// allocated per fragment.
+  ///
+  /// - `capacity`
+  ///   - Closure that calculates the byte capacity from the size hint (counted
+  ///     in fragments); the final capacity is `max(size_bound,
+  ///     capacity(size_bound))`
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::rc::Rc;
+  ///
+  /// use collect_with::CollectString;
+  ///
+  /// let parts: [Rc<str>; 3] = ["ab".into(), "c".into(), "de".into()];
+  /// let s = parts
+  ///   .iter()
+  ///   .collect_string_fragments_with(|n| n * 4);
+  /// assert_eq!(s, "abcde");
+  /// assert_eq!(s.capacity(), 12);
+  /// ```
+  fn collect_string_fragments_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> String
+  where
+    Self: Sized,
+    Self::Item: AsRef<str>,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut s = String::with_capacity(capacity(bound).max(bound));
+    self.for_each(|fragment| s.push_str(fragment.as_ref()));
+    s
+  }
+
+  /// Concatenate string fragments into a `String` of exactly the total
+  /// length.
+  ///
+  /// The iterator is cloned and walked twice: once to sum the byte lengths and
+  /// once to copy the fragments. Cloning is cheap for borrowing iterators such
+  /// as `slice::Iter`. For iterators that cannot be cloned, use the one-pass
+  /// [collect_string_fragments_with()](CollectString::collect_string_fragments_with)
+  /// with a byte estimate instead.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectString;
+  ///
+  /// let parts: Vec<Box<str>> = vec!["hello".into(), ", ".into(), "world".into()];
+  /// let s = parts.iter().collect_string_fragments_exact();
+  /// assert_eq!(s, "hello, world");
+  /// assert_eq!(s.capacity(), s.len());
+  /// ```
+  fn collect_string_fragments_exact(self) -> String
+  where
+    Self: Sized + Clone,
+    Self::Item: AsRef<str>,
+  {
+    self.collect_string_concat_with(|total| total)
+  }
+
+  /// Concatenate string fragments into a `String`, sizing it from their
+  /// measured byte length.
+  ///
+  /// - `capacity`
+  ///   - Closure that receives the **exact** total byte length of the
+  ///     fragments, e.g. `|n| n + 2` to leave room for a suffix
+  ///
+  /// The iterator is cloned and walked twice: once to sum the byte lengths and
+  /// once to copy the fragments. The final capacity is
+  /// `max(total, capacity(total))`, so `|n| n` allocates exactly once, as
+  /// [collect_string_fragments_exact()](CollectString::collect_string_fragments_exact)
+  /// does. Iterators that cannot be cloned have no total to measure; use the
+  /// one-pass
+  /// [collect_string_fragments_with()](CollectString::collect_string_fragments_with)
+  /// with a byte estimate for them.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectString;
+  ///
+  /// let words = ["GET", " ", "/index.html"];
+  /// let mut line = words.iter().collect_string_concat_with(|n| n + 2);
+  /// assert_eq!(line.capacity(), 17);
+  /// line.push_str("\r\n");
+  /// assert_eq!(line.capacity(), 17);
+  /// ```
+  fn collect_string_concat_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> String
+  where
+    Self: Sized + Clone,
+    Self::Item: AsRef<str>,
+  {
+    let total = self
+      .clone()
+      .map(|fragment| fragment.as_ref().len())
+      .sum();
+    let mut s = String::with_capacity(capacity(total).max(total));
+    self.for_each(|fragment| s.push_str(fragment.as_ref()));
+    s
+  }
+
+  /// Like
+  /// [collect_string_fragments_with()](CollectString::collect_string_fragments_with),
+  /// also returning the [`LenStats`] of the appended fragments.
+  ///
+  /// The stats are gathered while appending, so the input is still read only
+  /// once. Use them to tune the byte capacity of later calls, e.g. from
+  /// `count * mean`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectString;
+  ///
+  /// let (s, stats) = ["ab", "", "cdef"]
+  ///   .into_iter()
+  ///   .collect_string_with_stats(|n| n * 2);
+  /// assert_eq!(s, "abcdef");
+  /// assert_eq!((stats.min, stats.max, stats.total), (0, 4, 6));
+  /// assert_eq!(stats.mean(), Some(2.0));
+  /// ```
+  fn collect_string_with_stats(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (String, LenStats)
+  where
+    Self: Sized,
+    Self::Item: AsRef<str>,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut s = String::with_capacity(capacity(bound).max(bound));
+    let mut stats = LenStats::default();
+    for fragment in self {
+      let fragment = fragment.as_ref();
+      stats.record(fragment.len());
+      s.push_str(fragment);
+    }
+    (s, stats)
+  }
+
+  /// Collect `char`s into a `String`, converting them to lowercase on the
+  /// fly.
+  ///
+  /// ASCII chars take a fast path with
+  /// [`char::to_ascii_lowercase()`]; other chars go through
+  /// [`char::to_lowercase()`], which may expand into several chars. Being
+  /// char-wise, this differs from [`str::to_lowercase()`] only for the Greek
+  /// final sigma, which is mapped to `σ` rather than `ς`.
+  ///
+  /// - `capacity`
+  ///   - Closure that receives the size bound in **chars** and returns the
+  ///     capacity in **bytes**. `|n| n` is exact for ASCII input. The worst
+  ///     case is 3 chars of 4 bytes each per input char, i.e. `|n| n * 12`,
+  ///     which never reallocates but usually over-allocates a lot.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectString;
+  ///
+  /// let id = "HTTP_Header".chars().collect_string_lowercase_with(|n| n);
+  /// assert_eq!(id, "http_header");
+  /// assert_eq!(id.capacity(), 11);
+  /// ```
+  fn collect_string_lowercase_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> String
+  where
+    Self: Sized + Iterator<Item = char>,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut s = String::with_capacity(capacity(bound).max(bound));
+    for c in self {
+      match c.is_ascii() {
+        true => s.push(c.to_ascii_lowercase()),
+        false => s.extend(c.to_lowercase()),
+      }
+    }
+    s
+  }
+
+  /// Collect `char`s into a `String`, converting them to uppercase on the
+  /// fly.
+  ///
+  /// The uppercase counterpart of
+  /// [collect_string_lowercase_with()](CollectString::collect_string_lowercase_with),
+  /// with the same capacity heuristic. Expansion is more common here, e.g.
+  /// `ß` becomes `SS`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectString;
+  ///
+  /// let s = "straße".chars().collect_string_uppercase_with(|n| n + 2);
+  /// assert_eq!(s, "STRASSE");
+  /// ```
+  fn collect_string_uppercase_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> String
+  where
+    Self: Sized + Iterator<Item = char>,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut s = String::with_capacity(capacity(bound).max(bound));
+    for c in self {
+      match c.is_ascii() {
+        true => s.push(c.to_ascii_uppercase()),
+        false => s.extend(c.to_uppercase()),
+      }
+    }
+    s
+  }
+
+  /// Collect iterator elements into a
+  /// [`CompactString`](compact_str::CompactString) with a capacity calculated
+  /// from the size hint.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))` bytes.
+  /// Up to 24 bytes (on 64-bit targets) the string is stored inline and
+  /// nothing is allocated on the heap; beyond that it allocates once.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectString;
+  ///
+  /// let tag = ["v", "1", ".", "2"]
+  ///   .into_iter()
+  ///   .collect_compact_string_with(|n| n);
+  /// assert_eq!(tag, "v1.2");
+  /// assert!(!tag.is_heap_allocated());
+  /// ```
+  #[cfg(feature = "compact_str")]
+  fn collect_compact_string_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> compact_str::CompactString
+  where
+    Self: Sized,
+    compact_str::CompactString: Extend<Self::Item>,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collect `char`s into a `String`, sizing it in bytes from the char count.
+  ///
+  /// - `capacity`
+  ///   - Closure that receives the **char** count bound of the size hint and
+  ///     the worst case in **bytes** (`4 * char_bound`, saturating) and returns
+  ///     the number of bytes to reserve
+  ///
+  /// [collect_string_with()](CollectString::collect_string_with) compares a
+  /// byte capacity with a char bound, so non-ASCII text grows past it.
+  /// Returning `worst_case_bytes` here never reallocates for an accurate
+  /// hint; returning `char_bound` matches ASCII exactly. The final capacity
+  /// is `max(char_bound, capacity(char_bound, worst_case_bytes))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectString;
+  ///
+  /// let s = ['日', '本', '語', '🦀']
+  ///   .into_iter()
+  ///   .collect_string_utf8_with(|_, worst| worst);
+  /// assert_eq!(s, "日本語🦀");
+  /// assert_eq!(s.capacity(), 16);
+  /// assert!(s.len() <= s.capacity());
+  /// ```
+  fn collect_string_utf8_with(
+    self,
+    capacity: impl FnOnce(usize, usize) -> usize,
+  ) -> String
+  where
+    Self: Sized + Iterator<Item = char>,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let bytes = capacity(bound, bound.saturating_mul(4)).max(bound);
+    let mut s = String::with_capacity(bytes);
+    s.extend(self);
+    s
+  }
+}
+
+fn collect_hex(iter: impl Iterator<Item = u8>, digits: &[u8; 16]) -> String {
+  let bound = get_max_hint_bound(iter.size_hint());
+  let mut hex = String::with_capacity(bound.saturating_mul(2));
+
+  for byte in iter {
+    hex.push(digits[usize::from(byte >> 4)] as char);
+    hex.push(digits[usize::from(byte & 0xf)] as char);
+  }
+  hex
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{HintBehavior, HintedIter};
+
+  #[test]
+  fn collect_string_hinted_fallback() {
+    let s = HintedIter::bounds(core::iter::empty::<char>(), 0, None)
+      .collect_string_hinted();
+    assert!(s.is_empty());
+    assert_eq!(s.capacity(), 16);
+
+    let s = "hello"
+      .chars()
+      .rev()
+      .collect_string_hinted();
+    assert_eq!(s, "olleh");
+    // Chars reports `((len + 3) / 4, Some(len))`, so the upper bound wins.
+    assert_eq!(s.capacity(), 5);
+  }
+
+  #[test]
+  fn collect_hex_known_vectors() {
+    let hex = core::iter::empty().collect_hex_string_with();
+    assert_eq!(hex, "");
+    assert_eq!(hex.capacity(), 0);
+
+    let hex = [0x00]
+      .into_iter()
+      .collect_hex_string_with();
+    assert_eq!(hex, "00");
+
+    let hex = [0xde, 0xad, 0xbe, 0xef]
+      .into_iter()
+      .collect_upper_hex_string_with();
+    assert_eq!(hex, "DEADBEEF");
+    assert_eq!(hex.capacity(), hex.len());
+  }
+
+  #[test]
+  fn collect_hex_lying_hint() {
+    let hex = HintedIter::lying(0..=255u8, (1, Some(1)), HintBehavior::Frozen)
+      .collect_hex_string_with();
+    assert_eq!(hex.len(), 512);
+    assert!(hex.starts_with("000102"));
+    assert!(hex.ends_with("fdfeff"));
+  }
+
+  #[test]
+  fn collect_string_fragments_each_type() {
+    use alloc::{borrow::Cow, boxed::Box, rc::Rc, sync::Arc};
+
+    let boxed: [Box<str>; 2] = ["ab".into(), "cd".into()];
+    assert_eq!(
+      boxed
+        .iter()
+        .collect_string_fragments_with(|n| n),
+      "abcd"
+    );
+
+    let rc: [Rc<str>; 2] = ["x".into(), "yz".into()];
+    assert_eq!(
+      rc.into_iter()
+        .collect_string_fragments_with(|n| n),
+      "xyz"
+    );
+
+    let arc: [Arc<str>; 3] = ["1".into(), "".into(), "23".into()];
+    let s = arc
+      .iter()
+      .collect_string_fragments_with(|n| n + 10);
+    assert_eq!(s, "123");
+    assert_eq!(s.capacity(), 13);
+
+    let cow = [Cow::Borrowed("a"), Cow::Owned(String::from("bc"))];
+    assert_eq!(
+      cow
+        .iter()
+        .collect_string_fragments_with(|n| n),
+      "abc"
+    );
+  }
+
+  #[test]
+  fn collect_string_fragments_exact_capacity() {
+    use alloc::boxed::Box;
+
+    let parts: [Box<str>; 3] = ["αβ".into(), "γ".into(), "".into()];
+    let s = parts
+      .iter()
+      .collect_string_fragments_exact();
+    assert_eq!(s, "αβγ");
+    assert_eq!(s.len(), 6);
+    assert_eq!(s.capacity(), 6);
+
+    let s = HintedIter::lying(parts.iter(), (0, None), HintBehavior::Frozen)
+      .collect_string_fragments_exact();
+    assert_eq!(s.capacity(), 6);
+
+    // Hundreds of fragments of varying length still allocate exactly once.
+    let many: alloc::vec::Vec<String> = (0..300)
+      .map(|i| "ab€".repeat(i % 7))
+      .collect();
+    let s = many
+      .iter()
+      .collect_string_fragments_exact();
+    assert_eq!(
+      s.len(),
+      many
+        .iter()
+        .map(String::len)
+        .sum()
+    );
+    assert_eq!(s.capacity(), s.len());
+  }
+
+  #[test]
+  fn collect_string_concat_hundreds_of_fragments() {
+    let parts: alloc::vec::Vec<String> = (0..400)
+      .map(|i| "x".repeat(i % 13) + &"ü".repeat(i % 3))
+      .collect();
+    let s = parts
+      .iter()
+      .collect_string_concat_with(|n| n);
+    assert_eq!(s, parts.concat());
+    assert_eq!(s.capacity(), s.len());
+
+    // The closure sees the byte total, not the fragment count.
+    let mut seen = None;
+    let padded = HintedIter::bounds(parts.iter(), 0, None)
+      .collect_string_concat_with(|n| {
+        seen = Some(n);
+        n + 8
+      });
+    assert_eq!(seen, Some(s.len()));
+    assert_eq!(padded.capacity(), s.len() + 8);
+
+    let smaller = parts
+      .iter()
+      .collect_string_concat_with(|_| 0);
+    assert_eq!(smaller.capacity(), s.len());
+  }
+
+  #[test]
+  fn collect_string_with_stats_matches_plain() {
+    let words = ["lorem", "ipsum", "", "dolor", "sit", "amet"];
+    let plain = words
+      .into_iter()
+      .collect_string_fragments_with(|n| n);
+    let (s, stats) = words
+      .into_iter()
+      .collect_string_with_stats(|n| n);
+    assert_eq!(s, plain);
+    assert_eq!(
+      stats,
+      LenStats {
+        count: 6,
+        total: 22,
+        min: 0,
+        max: 5
+      }
+    );
+
+    let (s, stats) = core::iter::empty::<&str>().collect_string_with_stats(|n| n);
+    assert!(s.is_empty());
+    assert_eq!(stats, LenStats::default());
+    assert_eq!(stats.mean(), None);
+  }
+
+  #[test]
+  fn collect_string_case_mapping() {
+    // ASCII with factor 1: exactly one byte per char, no growth.
+    let src = "Mixed_Case_42";
+    let lower =
+      HintedIter::exact(src.chars(), src.len()).collect_string_lowercase_with(|n| n);
+    assert_eq!(lower, "mixed_case_42");
+    assert_eq!(lower.capacity(), src.len());
+    let upper =
+      HintedIter::exact(src.chars(), src.len()).collect_string_uppercase_with(|n| n);
+    assert_eq!(upper, "MIXED_CASE_42");
+    assert_eq!(upper.capacity(), src.len());
+
+    // Expanding and non-ASCII cases match the std conversions.
+    for word in ["Fußball", "İstanbul", "ΆΣΤΡΟ", "ﬁne"] {
+      assert_eq!(
+        word
+          .chars()
+          .collect_string_lowercase_with(|n| n * 2),
+        word.to_lowercase()
+      );
+      assert_eq!(
+        word
+          .chars()
+          .collect_string_uppercase_with(|n| n * 2),
+        word.to_uppercase()
+      );
+    }
+    assert_eq!(
+      "ß"
+        .chars()
+        .collect_string_uppercase_with(|n| n),
+      "SS"
+    );
+  }
+
+  #[cfg(all(feature = "compact_str", target_pointer_width = "64"))]
+  #[test]
+  fn collect_compact_string_inline_or_heap() {
+    use compact_str::CompactString;
+
+    let inline = "abc"
+      .chars()
+      .collect_compact_string_with(|_| 24);
+    assert_eq!(inline, "abc");
+    assert!(!inline.is_heap_allocated());
+
+    let heap = "abc"
+      .chars()
+      .collect_compact_string_with(|_| 25);
+    assert!(heap.is_heap_allocated());
+    assert!(heap.capacity() >= 25);
+
+    // 40 bytes from owned `String` fragments: one heap buffer, no growth.
+    let words = ["0123456789"; 4].map(String::from);
+    let joined = words
+      .into_iter()
+      .collect_compact_string_with(|n| n * 10);
+    assert_eq!(joined.len(), 40);
+    assert_eq!(joined.capacity(), 40);
+
+    let hinted: CompactString = core::iter::empty::<&str>().collect_hinted();
+    assert!(!hinted.is_heap_allocated());
+  }
+
+  #[test]
+  fn collect_string_with_item_types() {
+    use alloc::borrow::{Cow, ToOwned};
+
+    use crate::HintedIter;
+
+    let s = HintedIter::bounds("héllo".chars(), 0, None).collect_string_with(|_| 12);
+    assert_eq!(s, "héllo");
+    assert_eq!(s.capacity(), 12);
+
+    let owned = ["ab".to_owned(), "c".to_owned()];
+    let s = owned
+      .into_iter()
+      .collect_string_with(|n| n * 2);
+    assert_eq!(s, "abc");
+    assert!(s.capacity() >= 4);
+
+    let s = [Cow::Borrowed("x"), Cow::Owned("yz".to_owned())]
+      .into_iter()
+      .collect_string_with_exact(|_| 1);
+    assert_eq!(s, "xyz");
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_collect_string_with_short_circuits() {
+    let s = [Ok::<_, u8>("a"), Ok("b")]
+      .into_iter()
+      .try_collect_string_with(|n| n + 6);
+    assert_eq!(s.as_deref(), Ok("ab"));
+    assert_eq!(s.unwrap().capacity(), 8);
+
+    let err = [Ok("a"), Err(3), Ok("c")]
+      .into_iter()
+      .try_collect_string_with(|n| n);
+    assert_eq!(err, Err(3));
+  }
+
+  #[test]
+  fn collect_string_utf8_without_reallocation() {
+    let text = "漢字かな🙂é";
+    let chars = text.chars().count();
+
+    let mut seen = None;
+    let s = text
+      .chars()
+      .collect_string_utf8_with(|bound, worst| {
+        seen = Some((bound, worst));
+        worst
+      });
+    assert_eq!(s, text);
+    let (bound, worst) = seen.unwrap();
+    // `Chars` reports a byte-derived range; the bound covers the char count.
+    assert!(bound >= chars);
+    assert_eq!(worst, bound * 4);
+    assert_eq!(s.capacity(), worst);
+
+    let exact = text
+      .chars()
+      .collect::<alloc::vec::Vec<_>>()
+      .into_iter()
+      .collect_string_utf8_with(|_, worst| worst);
+    assert_eq!(exact.capacity(), chars * 4);
+
+    // ASCII-sized request is raised to the char bound, never below.
+    let s = "abc"
+      .chars()
+      .collect_string_utf8_with(|_, _| 0);
+    assert!(s.capacity() >= 3);
+
+    // collect_string_with is unchanged: its byte capacity is the char bound.
+    let s = ['é', 'è']
+      .into_iter()
+      .collect_string_with(|n| n);
+    assert_eq!(s, "éè");
+  }
+}
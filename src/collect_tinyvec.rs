@@ -0,0 +1,101 @@
+use tinyvec::{Array, TinyVec};
+
+use crate::collect::CollectWith;
+
+impl<I: Iterator> CollectTinyVec for I {}
+
+/// Trait for collecting items into a [`TinyVec`] with a specified capacity.
+///
+/// When the final capacity is at most `A::CAPACITY`, the result is the inline
+/// variant and nothing is allocated on the heap.
+pub trait CollectTinyVec: Iterator {
+  /// Collect iterator elements into a `TinyVec` with a capacity calculated
+  /// from the size hint.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectTinyVec;
+  ///
+  /// let tv = (0..3u8).collect_tinyvec_with::<[u8; 4]>(|n| n);
+  /// assert_eq!(tv.as_slice(), [0, 1, 2]);
+  /// assert!(tv.is_inline());
+  ///
+  /// let tv = (0..3u8).collect_tinyvec_with::<[u8; 4]>(|n| n + 8);
+  /// assert!(tv.is_heap());
+  /// ```
+  fn collect_tinyvec_with<A>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> TinyVec<A>
+  where
+    A: Array<Item = Self::Item>,
+    Self: Sized,
+  {
+    self.collect_with(capacity)
+  }
+
+  /// Collect iterator elements into a `TinyVec` with exact capacity
+  /// calculation.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectTinyVec;
+  ///
+  /// let tv = "a b c"
+  ///   .split(' ')
+  ///   .collect_tinyvec_with_exact::<[&str; 4]>(|_| 3);
+  /// assert_eq!(tv.len(), 3);
+  /// assert!(tv.is_inline());
+  /// ```
+  fn collect_tinyvec_with_exact<A>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> TinyVec<A>
+  where
+    A: Array<Item = Self::Item>,
+    Self: Sized,
+  {
+    self.collect_with_exact(capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use tinyvec::ArrayVec;
+
+  use super::*;
+  use crate::HintedIter;
+
+  #[test]
+  fn inline_when_capacity_fits() {
+    let tv = (0..8u8).collect_tinyvec_with::<[_; 8]>(|n| n);
+    assert!(tv.is_inline());
+
+    // The closure receives the max hint bound, i.e. the upper bound here.
+    let tv =
+      HintedIter::bounds(0..2u8, 0, Some(6)).collect_tinyvec_with::<[_; 8]>(|n| {
+        assert_eq!(n, 6);
+        n
+      });
+    assert_eq!(tv.as_slice(), [0, 1]);
+    assert!(tv.is_inline());
+  }
+
+  #[test]
+  fn heap_when_capacity_exceeds_inline() {
+    let tv = (0..2u8).collect_tinyvec_with_exact::<[_; 4]>(|_| 16);
+    assert!(tv.is_heap());
+    assert_eq!(tv.capacity(), 16);
+  }
+
+  #[test]
+  fn tinyvec_arrayvec_without_heap() {
+    let av = (1..=3u16).collect_with::<ArrayVec<[u16; 4]>>(|n| n + 100);
+    assert_eq!(av.as_slice(), [1, 2, 3]);
+    assert_eq!(av.capacity(), 4);
+  }
+}
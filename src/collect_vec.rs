@@ -1,6 +1,10 @@
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 
-use crate::collect::CollectWith;
+use crate::{
+  ExactViolated, TrailingBytes,
+  collect::CollectWith,
+  common::{collect_iterator, get_max_hint_bound},
+};
 
 /// Blanket implementation for iterators to add vector collection capabilities.
 ///
@@ -69,6 +73,11 @@ pub trait CollectVector: Iterator {
   ///
   /// Preferred when iterator provides exact size information via size_hint()
   /// and precise allocation is critical.
+  ///
+  /// "Exact" refers to the initial allocation: if the closure returns less
+  /// than the number of items, the `Vec` still grows to hold them all. Use
+  /// [collect_vec_with_exact_checked()](CollectVector::collect_vec_with_exact_checked)
+  /// to turn such a miss into an error.
   fn collect_vec_with_exact<F>(self, capacity: F) -> Vec<Self::Item>
   where
     F: FnOnce(usize) -> usize,
@@ -76,4 +85,1483 @@ pub trait CollectVector: Iterator {
   {
     self.collect_with_exact(capacity)
   }
+
+  /// Collect iterator elements into a `Vec` with exact capacity calculation,
+  /// failing if the initial allocation was too small.
+  ///
+  /// Behaves like
+  /// [collect_vec_with_exact()](CollectVector::collect_vec_with_exact), then
+  /// checks that the items fit in the capacity allocated up front. If the
+  /// `Vec` had to grow, the collected items are dropped and
+  /// [`ExactViolated`] reports the requested capacity and the final length.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::{CollectVector, ExactViolated};
+  ///
+  /// let v = (0..8).collect_vec_with_exact_checked(|n| n).unwrap();
+  /// assert_eq!(v.capacity(), 8);
+  ///
+  /// let err = (0..8).collect_vec_with_exact_checked(|_| 5).unwrap_err();
+  /// assert_eq!(err, ExactViolated { requested: 5, final_len: 8 });
+  /// ```
+  fn collect_vec_with_exact_checked<F>(
+    self,
+    capacity: F,
+  ) -> Result<Vec<Self::Item>, ExactViolated>
+  where
+    F: FnOnce(usize) -> usize,
+    Self: Sized,
+  {
+    let requested = capacity(get_max_hint_bound(self.size_hint()));
+    let mut vec = Vec::with_capacity(requested);
+    let allocated = vec.capacity();
+    vec.extend(self);
+    if vec.len() > allocated {
+      return Err(ExactViolated {
+        requested,
+        final_len: vec.len(),
+      });
+    }
+    Ok(vec)
+  }
+
+  /// Collect iterator elements into a `Vec` using the size hint bound as the
+  /// capacity, or [`Vec::FALLBACK_CAPACITY`](crate::ExtendWithCapacity) (8)
+  /// when the bound is 0.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let v = (0..10).collect_vec_hinted();
+  /// assert_eq!(v.capacity(), 10);
+  ///
+  /// let v = core::iter::from_fn(|| None::<u8>).collect_vec_hinted();
+  /// assert!(v.is_empty());
+  /// assert_eq!(v.capacity(), 8);
+  /// ```
+  fn collect_vec_hinted(self) -> Vec<Self::Item>
+  where
+    Self: Sized,
+  {
+    self.collect_hinted()
+  }
+
+  /// Collect iterator elements into a `Vec`, using the size hint bound as the
+  /// exact capacity, or `fallback` when the bound is 0.
+  ///
+  /// See [collect_with_fallback()](crate::CollectWith::collect_with_fallback).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let v = "a b".split(' ').collect_vec_with_fallback(10);
+  /// assert_eq!(v.capacity(), 10);
+  /// ```
+  fn collect_vec_with_fallback(self, fallback: usize) -> Vec<Self::Item>
+  where
+    Self: Sized,
+  {
+    self.collect_with_fallback(fallback)
+  }
+
+  /// Collect borrowed `Copy` elements into a `Vec<T>`.
+  ///
+  /// Equivalent to `.copied().collect_vec_with(capacity)`, the capacity
+  /// closure receiving the size bound of the borrowing iterator.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let src = [1, 2, 3];
+  /// let nums = src.iter().collect_copied_vec_with(|u| u);
+  /// assert_eq!(nums, src);
+  /// assert_eq!(nums.capacity(), 3);
+  /// ```
+  fn collect_copied_vec_with<'a, T>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<T>
+  where
+    Self: Sized + Iterator<Item = &'a T>,
+    T: Copy + 'a,
+  {
+    self
+      .copied()
+      .collect_with(capacity)
+  }
+
+  /// Collect borrowed `Copy` elements into a `Vec<T>` with exact capacity
+  /// calculation.
+  fn collect_copied_vec_with_exact<'a, T>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<T>
+  where
+    Self: Sized + Iterator<Item = &'a T>,
+    T: Copy + 'a,
+  {
+    self
+      .copied()
+      .collect_with_exact(capacity)
+  }
+
+  /// Collect borrowed `Clone` elements into a `Vec<T>`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let src = ["a".to_owned(), "b".to_owned()];
+  /// let strings = src.iter().collect_cloned_vec_with(|u| u);
+  /// assert_eq!(strings, src);
+  /// assert_eq!(strings.capacity(), 2);
+  /// ```
+  fn collect_cloned_vec_with<'a, T>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<T>
+  where
+    Self: Sized + Iterator<Item = &'a T>,
+    T: Clone + 'a,
+  {
+    self
+      .cloned()
+      .collect_with(capacity)
+  }
+
+  /// Collect borrowed `Clone` elements into a `Vec<T>` with exact capacity
+  /// calculation.
+  fn collect_cloned_vec_with_exact<'a, T>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<T>
+  where
+    Self: Sized + Iterator<Item = &'a T>,
+    T: Clone + 'a,
+  {
+    self
+      .cloned()
+      .collect_with_exact(capacity)
+  }
+
+  /// Encode a `char` iterator into its UTF-8 bytes.
+  ///
+  /// - `capacity`
+  ///   - Closure that calculates the byte capacity from the **char** count
+  ///     bound of the size hint
+  ///
+  /// Each char takes 1 to 4 bytes, so `|n| n` is exact for ASCII input, and
+  /// `|n| n * 4` is the worst case that never reallocates.
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let bytes = ['a', 'ñ', '世']
+  ///   .into_iter()
+  ///   .collect_utf8_bytes_with(|n| n * 4);
+  /// assert_eq!(bytes, "añ世".as_bytes());
+  /// assert_eq!(bytes.capacity(), 3 * 4);
+  /// ```
+  fn collect_utf8_bytes_with(self, capacity: impl FnOnce(usize) -> usize) -> Vec<u8>
+  where
+    Self: Sized + Iterator<Item = char>,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut bytes = Vec::with_capacity(capacity(bound).max(bound));
+    let mut buf = [0; 4];
+
+    for c in self {
+      bytes.extend_from_slice(
+        c.encode_utf8(&mut buf)
+          .as_bytes(),
+      );
+    }
+    bytes
+  }
+
+  /// Collect iterator elements into a `Vec` and guarantee
+  /// `capacity() - len() >= extra` afterwards.
+  ///
+  /// See [collect_with_headroom()](crate::CollectWith::collect_with_headroom).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let mut v = (0..4).collect_vec_with_headroom(1, |n| n);
+  /// assert!(v.capacity() >= 5);
+  /// v.push(4); // room for the sentinel
+  /// ```
+  fn collect_vec_with_headroom(
+    self,
+    extra: usize,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<Self::Item>
+  where
+    Self: Sized,
+  {
+    self.collect_with_headroom(extra, capacity)
+  }
+
+  /// Collect into a `Vec`, keeping only the first item of each run of
+  /// consecutive items with equal keys.
+  ///
+  /// - `capacity`
+  ///   - Closure that calculates capacity based on iterator size hints
+  /// - `key`
+  ///   - Closure that derives the comparison key from an item
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let records = [(1, "a"), (1, "b"), (2, "c"), (1, "d")];
+  /// let v = records
+  ///   .into_iter()
+  ///   .collect_dedup_by_key_vec_with(|n| n, |&(sec, _)| sec);
+  /// assert_eq!(v, [(1, "a"), (2, "c"), (1, "d")]);
+  /// ```
+  fn collect_dedup_by_key_vec_with<K: PartialEq>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    mut key: impl FnMut(&Self::Item) -> K,
+  ) -> Vec<Self::Item>
+  where
+    Self: Sized,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut vec = Vec::with_capacity(capacity(bound).max(bound));
+    let mut last_key = None;
+
+    for item in self {
+      let k = key(&item);
+      if last_key.as_ref() != Some(&k) {
+        vec.push(item);
+        last_key = Some(k);
+      }
+    }
+    vec
+  }
+
+  /// Collect into a `Vec`, keeping only the **last** item of each run of
+  /// consecutive items with equal keys.
+  ///
+  /// One pending item is held back until the next key differs (or the
+  /// iterator ends).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let records = [(1, "a"), (1, "b"), (2, "c"), (1, "d")];
+  /// let v = records
+  ///   .into_iter()
+  ///   .collect_dedup_by_key_last_vec_with(|n| n, |&(sec, _)| sec);
+  /// assert_eq!(v, [(1, "b"), (2, "c"), (1, "d")]);
+  /// ```
+  fn collect_dedup_by_key_last_vec_with<K: PartialEq>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    mut key: impl FnMut(&Self::Item) -> K,
+  ) -> Vec<Self::Item>
+  where
+    Self: Sized,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut vec = Vec::with_capacity(capacity(bound).max(bound));
+    let mut pending: Option<(K, Self::Item)> = None;
+
+    for item in self {
+      let k = key(&item);
+      match pending.take() {
+        Some((pk, prev)) if pk != k => vec.push(prev),
+        _ => {}
+      }
+      pending = Some((k, item));
+    }
+    vec.extend(pending.map(|(_, item)| item));
+    vec
+  }
+
+  /// Collect items alternately from `self` and `other` into a `Vec`.
+  ///
+  /// When one side runs out, the remaining items of the other side are
+  /// appended in order.
+  ///
+  /// - `capacity`
+  ///   - Closure that receives the sum of both size bounds
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let v = [1, 3, 5, 7].into_iter().collect_interleaved_with([2, 4], |n| n);
+  /// assert_eq!(v, [1, 2, 3, 4, 5, 7]);
+  /// assert_eq!(v.capacity(), 6);
+  /// ```
+  fn collect_interleaved_with<I2>(
+    self,
+    other: I2,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<Self::Item>
+  where
+    I2: IntoIterator<Item = Self::Item>,
+    Self: Sized,
+  {
+    let mut this = self;
+    let mut other = other.into_iter();
+    let bound = get_max_hint_bound(this.size_hint())
+      .saturating_add(get_max_hint_bound(other.size_hint()));
+    let mut vec = Vec::with_capacity(capacity(bound).max(bound));
+
+    loop {
+      match this.next() {
+        Some(item) => vec.push(item),
+        None => {
+          vec.extend(other);
+          break;
+        }
+      }
+      match other.next() {
+        Some(item) => vec.push(item),
+        None => {
+          vec.extend(this);
+          break;
+        }
+      }
+    }
+    vec
+  }
+
+  /// Collect into a `Vec` and pad it with clones of `pad` up to `target_len`.
+  ///
+  /// - `target_len`
+  ///   - Minimum length of the result; inputs that are already longer are left
+  ///     untouched (never truncated)
+  /// - `capacity`
+  ///   - Closure that calculates capacity based on iterator size hints
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound),
+  /// target_len)`, so padding never reallocates.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let v = b"abc".iter().copied().collect_padded_vec_with(6, b' ', |n| n);
+  /// assert_eq!(v, b"abc   ");
+  /// assert_eq!(v.capacity(), 6);
+  /// ```
+  fn collect_padded_vec_with(
+    self,
+    target_len: usize,
+    pad: Self::Item,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<Self::Item>
+  where
+    Self: Sized,
+    Self::Item: Clone,
+  {
+    let mut vec: Vec<_> =
+      collect_iterator(self, false, |bound| capacity(bound).max(target_len));
+    if vec.len() < target_len {
+      vec.resize(target_len, pad);
+    }
+    vec
+  }
+
+  /// Collect iterator elements into a `Box<[T]>`.
+  ///
+  /// Items are collected into a `Vec` with the same capacity rule as
+  /// [collect_vec_with()](CollectVector::collect_vec_with), then converted
+  /// with [`Vec::into_boxed_slice()`]. That conversion is reallocation-free
+  /// exactly when the final capacity equals the number of items, e.g. for an
+  /// exact size hint and `|n| n`; any headroom is shrunk away with a
+  /// reallocation.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let squares = (1..=4).map(|x| x * x).collect_boxed_slice_with(|n| n);
+  /// assert_eq!(*squares, [1, 4, 9, 16]);
+  /// ```
+  fn collect_boxed_slice_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Box<[Self::Item]>
+  where
+    Self: Sized,
+  {
+    self
+      .collect_vec_with(capacity)
+      .into_boxed_slice()
+  }
+
+  /// Collect iterator elements into a `Box<[T]>` with exact capacity
+  /// calculation.
+  ///
+  /// Same as
+  /// [collect_boxed_slice_with()](CollectVector::collect_boxed_slice_with),
+  /// but based on
+  /// [collect_vec_with_exact()](CollectVector::collect_vec_with_exact):
+  /// when `capacity` returns the actual number of items, the conversion
+  /// never reallocates, whatever the size hint says.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let words = "a b c".split(' ').collect_boxed_slice_with_exact(|_| 3);
+  /// assert_eq!(&*words, ["a", "b", "c"]);
+  /// ```
+  fn collect_boxed_slice_with_exact(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Box<[Self::Item]>
+  where
+    Self: Sized,
+  {
+    self
+      .collect_vec_with_exact(capacity)
+      .into_boxed_slice()
+  }
+
+  /// Splits byte payloads into chunks whose total length stays within
+  /// `max_bytes`.
+  ///
+  /// Items are measured with [`AsRef<[u8]>`](AsRef), which covers `&[u8]`,
+  /// `Vec<u8>`, `&str` and `String`. A new chunk starts whenever adding the
+  /// next item would exceed `max_bytes`; an item that is larger than the
+  /// budget on its own gets a chunk of its own. Each new chunk is pre-sized
+  /// to the item count of the previous one.
+  ///
+  /// - `capacity`
+  ///   - Closure that calculates the **outer** `Vec` capacity. It receives the
+  ///     size bound in items, which is an upper estimate of the number of
+  ///     chunks, and its result is used as is.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let batches = ["aaa", "bb", "c", "dddd", "ee"]
+  ///   .into_iter()
+  ///   .collect_chunks_by_bytes_with(5, |n| n / 2);
+  /// assert_eq!(batches, [vec!["aaa", "bb"], vec!["c", "dddd"], vec!["ee"]]);
+  /// ```
+  fn collect_chunks_by_bytes_with(
+    self,
+    max_bytes: usize,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<Vec<Self::Item>>
+  where
+    Self: Sized,
+    Self::Item: AsRef<[u8]>,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut chunks = Vec::with_capacity(capacity(bound));
+    let mut chunk = Vec::new();
+    let mut chunk_bytes = 0;
+
+    for item in self {
+      let len = item.as_ref().len();
+      if !chunk.is_empty() && chunk_bytes + len > max_bytes {
+        let next = Vec::with_capacity(chunk.len());
+        chunks.push(core::mem::replace(&mut chunk, next));
+        chunk_bytes = 0;
+      }
+      chunk_bytes += len;
+      chunk.push(item);
+    }
+
+    if !chunk.is_empty() {
+      chunks.push(chunk);
+    }
+    chunks
+  }
+
+  /// Pairs items with those of `other` position by position, filling the
+  /// shorter side with copies of `fill_a` or `fill_b`.
+  ///
+  /// The `Vec` is pre-sized from the larger of both size bounds, since that
+  /// is the number of pairs when the hints are accurate. The final capacity
+  /// is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let rows = ["id", "name", "email"]
+  ///   .into_iter()
+  ///   .collect_zip_longest_with([1, 2], "", 0, |n| n);
+  /// assert_eq!(rows, [("id", 1), ("name", 2), ("email", 0)]);
+  /// ```
+  fn collect_zip_longest_with<I2, B>(
+    self,
+    other: I2,
+    fill_a: Self::Item,
+    fill_b: B,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<(Self::Item, B)>
+  where
+    I2: IntoIterator<Item = B>,
+    Self: Sized,
+    Self::Item: Clone,
+    B: Clone,
+  {
+    // Fused, since the exhausted side keeps being polled.
+    let mut this = self.fuse();
+    let mut other = other.into_iter().fuse();
+    let bound = get_max_hint_bound(this.size_hint())
+      .max(get_max_hint_bound(other.size_hint()));
+    let mut vec = Vec::with_capacity(capacity(bound).max(bound));
+
+    loop {
+      let pair = match (this.next(), other.next()) {
+        (Some(a), Some(b)) => (a, b),
+        (Some(a), None) => (a, fill_b.clone()),
+        (None, Some(b)) => (fill_a.clone(), b),
+        (None, None) => break,
+      };
+      vec.push(pair);
+    }
+    vec
+  }
+
+  /// Collect iterator elements into a `Vec<T, A>` that allocates through
+  /// `alloc`.
+  ///
+  /// The capacity follows the same rule as
+  /// [collect_vec_with()](CollectVector::collect_vec_with):
+  /// `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// #![feature(allocator_api)]
+  /// use std::alloc::Global;
+  ///
+  /// use collect_with::CollectVector;
+  ///
+  /// let v = (0..4).collect_vec_in_with(Global, |n| n + 4);
+  /// assert_eq!(v, [0, 1, 2, 3]);
+  /// assert_eq!(v.capacity(), 8);
+  /// ```
+  #[cfg(feature = "allocator_api")]
+  fn collect_vec_in_with<A>(
+    self,
+    alloc: A,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<Self::Item, A>
+  where
+    A: core::alloc::Allocator,
+    Self: Sized,
+  {
+    use crate::ExtendWithCapacityIn;
+
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut vec: Vec<_, A> =
+      ExtendWithCapacityIn::with_capacity_in(capacity(bound).max(bound), alloc);
+    vec.extend(self);
+    vec
+  }
+
+  /// Concatenate byte fragments into a `Vec<u8>`, also returning the
+  /// [`LenStats`](crate::LenStats) of the fragments.
+  ///
+  /// The byte counterpart of
+  /// [collect_string_with_stats()](crate::CollectString::collect_string_with_stats).
+  /// The capacity closure receives the fragment count bound and returns a
+  /// byte count; the final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let (buf, stats) = [&b"GET "[..], b"/", b" HTTP/1.1"]
+  ///   .into_iter()
+  ///   .collect_bytes_with_stats(|n| n * 8);
+  /// assert_eq!(buf, b"GET / HTTP/1.1");
+  /// assert_eq!(stats.count, 3);
+  /// assert_eq!(stats.max, 9);
+  /// ```
+  fn collect_bytes_with_stats(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (Vec<u8>, crate::LenStats)
+  where
+    Self: Sized,
+    Self::Item: AsRef<[u8]>,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut buf = Vec::with_capacity(capacity(bound).max(bound));
+    let mut stats = crate::LenStats::default();
+    for fragment in self {
+      let fragment = fragment.as_ref();
+      stats.record(fragment.len());
+      buf.extend_from_slice(fragment);
+    }
+    (buf, stats)
+  }
+
+  /// Collect an [`ExactSizeIterator`] into a `Vec` whose capacity is exactly
+  /// [`len()`](ExactSizeIterator::len).
+  ///
+  /// No closure and no max rule: ranges, slices and `vec::IntoIter` report
+  /// their length, so there is nothing to guess. An iterator that yields a
+  /// different number of items than its `len()` trips a `debug_assert!`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let v = (0..100).map(|x| x * 2).collect_vec_len();
+  /// assert_eq!(v.len(), 100);
+  /// assert_eq!(v.capacity(), 100);
+  /// ```
+  fn collect_vec_len(self) -> Vec<Self::Item>
+  where
+    Self: Sized + ExactSizeIterator,
+  {
+    let len = self.len();
+    let vec: Vec<_> = collect_iterator(self, true, |_| len);
+    debug_assert_eq!(
+      vec.len(),
+      len,
+      "collect_vec_len: iterator yielded a different number of items than its len()"
+    );
+    vec
+  }
+
+  /// Splits interleaved items into `channels` vectors: item `i` goes to
+  /// channel `i % channels`.
+  ///
+  /// The outer `Vec` has exactly `channels` entries. The total capacity is
+  /// `max(size_bound, capacity(size_bound))`, and each channel is pre-sized
+  /// to its share, `total.div_ceil(channels)`.
+  ///
+  /// ## Panics
+  ///
+  /// Panics if `channels` is 0.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let rgb = [255, 0, 0, 0, 255, 0]
+  ///   .into_iter()
+  ///   .collect_deinterleave_with(3, |n| n);
+  /// assert_eq!(rgb, [vec![255, 0], vec![0, 255], vec![0, 0]]);
+  /// ```
+  fn collect_deinterleave_with(
+    self,
+    channels: usize,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<Vec<Self::Item>>
+  where
+    Self: Sized,
+  {
+    assert!(
+      channels > 0,
+      "collect_deinterleave_with: channels must be > 0"
+    );
+    let bound = get_max_hint_bound(self.size_hint());
+    let per_channel = capacity(bound)
+      .max(bound)
+      .div_ceil(channels);
+    let mut out: Vec<Vec<_>> = (0..channels)
+      .map(|_| Vec::with_capacity(per_channel))
+      .collect();
+    for (i, item) in self.enumerate() {
+      out[i % channels].push(item);
+    }
+    out
+  }
+
+  /// Splits interleaved items into even and odd positions, e.g. the left and
+  /// right channels of stereo samples.
+  ///
+  /// The two-channel case of
+  /// [collect_deinterleave_with()](CollectVector::collect_deinterleave_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let (left, right) = [0.1, -0.1, 0.2, -0.2, 0.3]
+  ///   .into_iter()
+  ///   .collect_split_even_odd_with(|n| n);
+  /// assert_eq!(left, [0.1, 0.2, 0.3]);
+  /// assert_eq!(right, [-0.1, -0.2]);
+  /// ```
+  fn collect_split_even_odd_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (Vec<Self::Item>, Vec<Self::Item>)
+  where
+    Self: Sized,
+  {
+    let mut channels = self.collect_deinterleave_with(2, capacity);
+    let odd = channels
+      .pop()
+      .unwrap_or_default();
+    let even = channels
+      .pop()
+      .unwrap_or_default();
+    (even, odd)
+  }
+
+  /// Flatten an iterator of arrays `[T; N]` into a `Vec<T>`.
+  ///
+  /// `flatten()` loses the array length in its size hint, while here the
+  /// `Vec` is pre-sized to `size_bound * N` (saturating) elements and each
+  /// array is appended as a whole. No capacity closure is needed, since the
+  /// element count follows from the array count.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let rgb = [[255, 0, 0], [0, 255, 0]]
+  ///   .into_iter()
+  ///   .collect_flat_arrays_with();
+  /// assert_eq!(rgb, [255, 0, 0, 0, 255, 0]);
+  /// assert_eq!(rgb.capacity(), 6);
+  /// ```
+  fn collect_flat_arrays_with<T, const N: usize>(self) -> Vec<T>
+  where
+    Self: Sized + Iterator<Item = [T; N]>,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut vec = Vec::with_capacity(bound.saturating_mul(N));
+    for array in self {
+      vec.extend(array);
+    }
+    vec
+  }
+
+  /// Collect the first halves of pairs into a `Vec`, dropping the second
+  /// halves.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::collections::BTreeMap;
+  ///
+  /// use collect_with::CollectVector;
+  ///
+  /// let ages = BTreeMap::from([("ada", 36), ("bob", 29)]);
+  /// let names = ages.iter().collect_keys_vec_with(|n| n);
+  /// assert_eq!(names, [&"ada", &"bob"]);
+  /// ```
+  fn collect_keys_vec_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<K>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+  {
+    self
+      .map(|(k, _)| k)
+      .collect_with(capacity)
+  }
+
+  /// Collect the second halves of pairs into a `Vec`, dropping the first
+  /// halves.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let scores = ["ada", "bob"]
+  ///   .into_iter()
+  ///   .zip([90, 72])
+  ///   .collect_values_vec_with(|n| n + 1);
+  /// assert_eq!(scores, [90, 72]);
+  /// assert_eq!(scores.capacity(), 3);
+  /// ```
+  fn collect_values_vec_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<V>
+  where
+    Self: Sized + Iterator<Item = (K, V)>,
+  {
+    self
+      .map(|(_, v)| v)
+      .collect_with(capacity)
+  }
+
+  /// Group bytes into fixed-size records of `N` bytes.
+  ///
+  /// - `capacity`
+  ///   - Closure that receives the **record** bound (`size_bound / N`) and
+  ///     returns the number of records to reserve
+  ///
+  /// The final capacity is `max(record_bound, capacity(record_bound))`. The
+  /// bytes of a trailing partial record are returned separately; use
+  /// [try_collect_packed_with()](CollectVector::try_collect_packed_with) to
+  /// treat them as an error. `N` must not be 0.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let (records, rest) = (0..10u8).collect_packed_with::<4>(|n| n);
+  /// assert_eq!(records, [[0, 1, 2, 3], [4, 5, 6, 7]]);
+  /// assert_eq!(rest, [8, 9]);
+  /// assert_eq!(records.capacity(), 2);
+  /// ```
+  fn collect_packed_with<const N: usize>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (Vec<[u8; N]>, Vec<u8>)
+  where
+    Self: Sized + Iterator<Item = u8>,
+  {
+    const { assert!(N > 0, "records must be at least one byte long") };
+
+    let bound = get_max_hint_bound(self.size_hint()) / N;
+    let mut records = Vec::with_capacity(capacity(bound).max(bound));
+    let mut record = [0; N];
+    let mut filled = 0;
+    for byte in self {
+      record[filled] = byte;
+      filled += 1;
+      if filled == N {
+        records.push(record);
+        filled = 0;
+      }
+    }
+    (records, record[..filled].to_vec())
+  }
+
+  /// Group bytes into fixed-size records of `N` bytes, failing with
+  /// [`TrailingBytes`] if the input ends in a partial record.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::{CollectVector, TrailingBytes};
+  ///
+  /// let entries = (0..24u8).try_collect_packed_with::<12>(|n| n).unwrap();
+  /// assert_eq!(entries.len(), 2);
+  ///
+  /// let err = (0..13u8).try_collect_packed_with::<12>(|n| n).unwrap_err();
+  /// assert_eq!(err, TrailingBytes { count: 1 });
+  /// ```
+  fn try_collect_packed_with<const N: usize>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<Vec<[u8; N]>, TrailingBytes>
+  where
+    Self: Sized + Iterator<Item = u8>,
+  {
+    match self.collect_packed_with(capacity) {
+      (records, rest) if rest.is_empty() => Ok(records),
+      (_, rest) => Err(TrailingBytes { count: rest.len() }),
+    }
+  }
+
+  /// Concatenate slices of `Copy` items into one `Vec<T>`.
+  ///
+  /// Each slice is appended with [`Vec::extend_from_slice()`] instead of
+  /// going through `flatten()`, whose size hint does not know the slice
+  /// lengths.
+  ///
+  /// - `capacity`
+  ///   - Closure that receives the **slice** count bound and returns the number
+  ///     of **items** to reserve, e.g. `|n| n * 1500` for MTU-sized frames
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`. Use
+  /// [collect_flat_slices_exact()](CollectVector::collect_flat_slices_exact)
+  /// to size the `Vec` from the actual slice lengths.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let frames: [&[u8]; 3] = [b"he", b"", b"llo"];
+  /// let buf = frames
+  ///   .into_iter()
+  ///   .collect_flat_slices_with(|n| n * 4);
+  /// assert_eq!(buf, b"hello");
+  /// assert_eq!(buf.capacity(), 12);
+  /// ```
+  fn collect_flat_slices_with<T>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Vec<T>
+  where
+    Self: Sized,
+    Self::Item: AsRef<[T]>,
+    T: Copy,
+  {
+    let bound = get_max_hint_bound(self.size_hint());
+    let mut vec = Vec::with_capacity(capacity(bound).max(bound));
+    for slice in self {
+      vec.extend_from_slice(slice.as_ref());
+    }
+    vec
+  }
+
+  /// Concatenate slices of `Copy` items into a `Vec<T>` allocated once from
+  /// their total length.
+  ///
+  /// The iterator is cloned and walked twice: once to sum the slice lengths,
+  /// and once to copy the slices. Cloning is cheap for borrowing iterators
+  /// such as `slice::Iter`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVector;
+  ///
+  /// let lines = ["GET / HTTP/1.1\r\n", "Host: a\r\n", "\r\n"];
+  /// let request = lines.iter().collect_flat_slices_exact::<u8>();
+  /// assert_eq!(request.len(), 27);
+  /// assert_eq!(request.capacity(), 27);
+  /// ```
+  fn collect_flat_slices_exact<T>(self) -> Vec<T>
+  where
+    Self: Sized + Clone,
+    Self::Item: AsRef<[T]>,
+    T: Copy,
+  {
+    let total = self
+      .clone()
+      .map(|slice| slice.as_ref().len())
+      .sum();
+    let mut vec = Vec::with_capacity(total);
+    for slice in self {
+      vec.extend_from_slice(slice.as_ref());
+    }
+    vec
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{string::String, vec};
+
+  use super::*;
+  use crate::{ExtendWithCapacity, HintBehavior, HintedIter};
+
+  #[test]
+  fn collect_copied_from_slice() {
+    let src = [3u32, 1, 4, 1, 5];
+    let v = src
+      .iter()
+      .collect_copied_vec_with(|u| u);
+    assert_eq!(v, src);
+    assert_eq!(v.capacity(), src.len());
+
+    let v = src[..2]
+      .iter()
+      .collect_copied_vec_with_exact(|u| u);
+    assert_eq!(v, [3, 1]);
+    assert_eq!(v.capacity(), 2);
+  }
+
+  #[test]
+  fn collect_cloned_from_slice() {
+    let src = vec![String::from("x"), String::from("yz")];
+    let v = src
+      .iter()
+      .collect_cloned_vec_with(|u| u);
+    assert_eq!(v, src);
+    assert_eq!(v.capacity(), src.len());
+
+    let v = src
+      .iter()
+      .collect_cloned_vec_with_exact(|u| u);
+    assert_eq!(v.capacity(), src.len());
+  }
+
+  #[test]
+  fn collect_vec_hinted_fallback() {
+    let v = HintedIter::bounds(core::iter::repeat_n(1, 3), 0, Some(3))
+      .collect_vec_hinted();
+    assert_eq!(v.capacity(), 3);
+
+    let v =
+      HintedIter::bounds(core::iter::empty::<u8>(), 0, None).collect_vec_hinted();
+    assert_eq!(
+      v.capacity(),
+      <Vec<u8> as ExtendWithCapacity<u8>>::FALLBACK_CAPACITY
+    );
+  }
+
+  #[test]
+  fn collect_utf8_bytes_ascii() {
+    let src = "hello, world";
+    let bytes = src
+      .chars()
+      .collect_vec_with(|n| n)
+      .into_iter()
+      .collect_utf8_bytes_with(|n| n);
+    assert_eq!(bytes, String::from_iter(src.chars()).into_bytes());
+    assert_eq!(bytes.capacity(), src.len());
+  }
+
+  #[test]
+  fn collect_utf8_bytes_multibyte() {
+    let src = "ß → 世界 🦀";
+    let bytes = src
+      .chars()
+      .collect_utf8_bytes_with(|n| n);
+    assert_eq!(bytes, String::from_iter(src.chars()).into_bytes());
+
+    let chars = src.chars().count();
+    let bytes = src
+      .chars()
+      .collect_vec_with(|n| n)
+      .into_iter()
+      .collect_utf8_bytes_with(|n| n * 4);
+    assert_eq!(bytes, src.as_bytes());
+    assert_eq!(bytes.capacity(), chars * 4);
+  }
+
+  #[test]
+  fn collect_vec_with_headroom_hints() {
+    let spare = |v: &Vec<_>| v.capacity() - v.len();
+
+    let v = (0..8).collect_vec_with_headroom(4, |n| n);
+    assert!(spare(&v) >= 4);
+
+    let v = HintedIter::bounds(0..8, 0, Some(8)).collect_vec_with_headroom(4, |_| 0);
+    assert_eq!(v.len(), 8);
+    assert!(spare(&v) >= 4);
+
+    let v = HintedIter::bounds(core::iter::empty::<u8>(), 0, None)
+      .collect_vec_with_headroom(4, |n| n);
+    assert!(spare(&v) >= 4);
+  }
+
+  #[test]
+  fn collect_dedup_by_key_runs() {
+    let key = |x: &(u8, char)| x.0;
+    // runs at the boundaries
+    let src = [(1, 'a'), (1, 'b'), (2, 'c'), (3, 'd'), (3, 'e')];
+    let first = src
+      .into_iter()
+      .collect_dedup_by_key_vec_with(|n| n, key);
+    let last = src
+      .into_iter()
+      .collect_dedup_by_key_last_vec_with(|n| n, key);
+    assert_eq!(first, [(1, 'a'), (2, 'c'), (3, 'd')]);
+    assert_eq!(last, [(1, 'b'), (2, 'c'), (3, 'e')]);
+    assert_eq!(first.capacity(), src.len());
+
+    // alternating keys: nothing to drop
+    let src = [(1, 'a'), (2, 'b'), (1, 'c'), (2, 'd')];
+    let first = src
+      .into_iter()
+      .collect_dedup_by_key_vec_with(|n| n, key);
+    let last = src
+      .into_iter()
+      .collect_dedup_by_key_last_vec_with(|n| n, key);
+    assert_eq!(first, src);
+    assert_eq!(last, src);
+
+    // a single run and an empty input
+    let v = [(7, 'x'), (7, 'y'), (7, 'z')]
+      .into_iter()
+      .collect_dedup_by_key_last_vec_with(|n| n, key);
+    assert_eq!(v, [(7, 'z')]);
+    let v = core::iter::empty().collect_dedup_by_key_vec_with(|n| n, key);
+    assert!(v.is_empty());
+  }
+
+  #[test]
+  fn collect_interleaved_lengths() {
+    let v = [1, 3]
+      .into_iter()
+      .collect_interleaved_with([2, 4], |n| n);
+    assert_eq!(v, [1, 2, 3, 4]);
+    assert_eq!(v.capacity(), 4);
+
+    let v = [1, 3, 5, 6]
+      .into_iter()
+      .collect_interleaved_with([2, 4], |n| n);
+    assert_eq!(v, [1, 2, 3, 4, 5, 6]);
+    assert_eq!(v.capacity(), 6);
+
+    let v = [1]
+      .into_iter()
+      .collect_interleaved_with([2, 3, 4], |n| n);
+    assert_eq!(v, [1, 2, 3, 4]);
+    assert_eq!(v.capacity(), 4);
+
+    let v = core::iter::empty().collect_interleaved_with([1, 2], |n| n);
+    assert_eq!(v, [1, 2]);
+    assert_eq!(v.capacity(), 2);
+
+    let v = [1, 2]
+      .into_iter()
+      .collect_interleaved_with([], |n| n);
+    assert_eq!(v, [1, 2]);
+    assert_eq!(v.capacity(), 2);
+  }
+
+  #[test]
+  fn collect_padded_vec_policy() {
+    let v = [1, 2]
+      .into_iter()
+      .collect_padded_vec_with(5, 0, |n| n);
+    assert_eq!(v, [1, 2, 0, 0, 0]);
+    assert_eq!(v.capacity(), 5);
+
+    let v = [1, 2, 3]
+      .into_iter()
+      .collect_padded_vec_with(3, 0, |n| n);
+    assert_eq!(v, [1, 2, 3]);
+    assert_eq!(v.capacity(), 3);
+
+    // longer inputs are kept as-is
+    let v = (1..=6).collect_padded_vec_with(3, 0, |n| n);
+    assert_eq!(v, [1, 2, 3, 4, 5, 6]);
+
+    // a zero hint still reserves room for the padding up front
+    let v =
+      HintedIter::bounds(1..=2, 0, Some(2)).collect_padded_vec_with(4, 9, |_| 0);
+    assert_eq!(v, [1, 2, 9, 9]);
+    assert_eq!(v.capacity(), 4);
+  }
+
+  #[test]
+  fn collect_boxed_slice_shrink() {
+    // Whether boxing reallocates is checked in `tests/boxed_slice.rs`, which
+    // installs a counting allocator.
+    let boxed = HintedIter::exact(0..16, 16).collect_boxed_slice_with(|n| n);
+    assert!(boxed.iter().copied().eq(0..16));
+
+    // `(0, None)` with headroom: the spare capacity has to be shrunk away.
+    let boxed = HintedIter::bounds(0..5, 0, None).collect_boxed_slice_with(|_| 32);
+    assert_eq!(*boxed, [0, 1, 2, 3, 4]);
+
+    let boxed =
+      HintedIter::bounds(0..5, 0, None).collect_boxed_slice_with_exact(|_| 5);
+    assert_eq!(boxed.len(), 5);
+  }
+
+  #[test]
+  fn collect_chunks_by_bytes_boundaries() {
+    // Exact fit: 2 + 3 == 5 stays in one chunk.
+    let chunks = [&b"ab"[..], b"cde", b"f", b"ghij"]
+      .into_iter()
+      .collect_chunks_by_bytes_with(5, |n| n);
+    assert_eq!(chunks, [vec![&b"ab"[..], b"cde"], vec![b"f", b"ghij"]]);
+    assert_eq!(chunks.capacity(), 4);
+
+    // An oversized item is isolated, even as the first one.
+    let chunks = ["toolarge", "ok", "x", "waytoolarge", "y"]
+      .into_iter()
+      .collect_chunks_by_bytes_with(4, |_| 0);
+    assert_eq!(
+      chunks,
+      [
+        vec!["toolarge"],
+        vec!["ok", "x"],
+        vec!["waytoolarge"],
+        vec!["y"]
+      ]
+    );
+
+    let empty = core::iter::empty::<String>().collect_chunks_by_bytes_with(8, |n| n);
+    assert!(empty.is_empty());
+  }
+
+  #[test]
+  fn collect_zip_longest_fills() {
+    let same = (0..3).collect_zip_longest_with('a'..'d', -1, '_', |n| n);
+    assert_eq!(same, [(0, 'a'), (1, 'b'), (2, 'c')]);
+    assert_eq!(same.capacity(), 3);
+
+    let left = (0..4).collect_zip_longest_with(['x'], -1, '_', |n| n);
+    assert_eq!(left, [(0, 'x'), (1, '_'), (2, '_'), (3, '_')]);
+    assert_eq!(left.capacity(), 4);
+
+    let right = (0..1).collect_zip_longest_with("pq".chars(), -1, '_', |n| n);
+    assert_eq!(right, [(0, 'p'), (-1, 'q')]);
+
+    let empty = core::iter::empty::<u8>().collect_zip_longest_with(
+      core::iter::empty::<u8>(),
+      0,
+      0,
+      |n| n,
+    );
+    assert!(empty.is_empty());
+    assert_eq!(empty.capacity(), 0);
+  }
+
+  #[cfg(feature = "allocator_api")]
+  #[test]
+  fn collect_vec_in_matches_collect_vec_with() {
+    use alloc::alloc::Global;
+
+    for hint in [(0, None), (3, Some(3)), (10, Some(10))] {
+      let (lower, upper) = hint;
+      let with = HintedIter::bounds(0..3, lower, upper).collect_vec_with(|n| n + 1);
+      let in_global = HintedIter::bounds(0..3, lower, upper)
+        .collect_vec_in_with(Global, |n| n + 1);
+      assert_eq!(*with, *in_global);
+      assert_eq!(with.capacity(), in_global.capacity());
+    }
+  }
+
+  #[test]
+  fn collect_bytes_with_stats_lengths() {
+    let frames = [vec![0u8; 3], vec![1; 7], vec![2; 2]];
+    let (buf, stats) = frames
+      .iter()
+      .collect_bytes_with_stats(|n| n * 4);
+    assert_eq!(buf, frames.concat());
+    assert_eq!(buf.capacity(), 12);
+    assert_eq!((stats.count, stats.total), (3, 12));
+    assert_eq!((stats.min, stats.max), (2, 7));
+    assert_eq!(stats.mean(), Some(4.0));
+  }
+
+  #[test]
+  fn collect_vec_len_exact() {
+    let v = (3..10).collect_vec_len();
+    assert_eq!(v.capacity(), v.len());
+    assert_eq!(v.len(), 7);
+
+    let src = [1.5, 2.5, 3.5];
+    let v = src.iter().collect_vec_len();
+    assert_eq!(v, [&1.5, &2.5, &3.5]);
+    assert_eq!(v.capacity(), 3);
+
+    let v = vec![String::from("a"); 5]
+      .into_iter()
+      .collect_vec_len();
+    assert_eq!(v.capacity(), v.len());
+
+    let v = core::iter::empty::<u8>().collect_vec_len();
+    assert_eq!(v.capacity(), 0);
+  }
+
+  #[test]
+  fn collect_deinterleave_channels() {
+    let mono = (0..5).collect_deinterleave_with(1, |n| n);
+    assert_eq!(mono, [vec![0, 1, 2, 3, 4]]);
+    assert_eq!(mono[0].capacity(), 5);
+
+    let (even, odd) = (0..7).collect_split_even_odd_with(|n| n);
+    assert_eq!(even, [0, 2, 4, 6]);
+    assert_eq!(odd, [1, 3, 5]);
+    assert_eq!(even.capacity(), 4);
+    assert_eq!(odd.capacity(), 4);
+
+    let three = (0..8).collect_deinterleave_with(3, |n| n + 1);
+    assert_eq!(three, [vec![0, 3, 6], vec![1, 4, 7], vec![2, 5]]);
+    assert!(
+      three
+        .iter()
+        .all(|ch| ch.capacity() == 3)
+    );
+
+    let empty = core::iter::empty::<u8>().collect_deinterleave_with(4, |n| n);
+    assert_eq!(empty.len(), 4);
+    assert!(empty.iter().all(Vec::is_empty));
+  }
+
+  #[test]
+  #[should_panic(expected = "channels must be > 0")]
+  fn collect_deinterleave_zero_channels() {
+    let _ = (0..3).collect_deinterleave_with(0, |n| n);
+  }
+
+  #[test]
+  fn collect_flat_arrays_capacity() {
+    let v = (0..4u16)
+      .map(|i| [i, i + 10])
+      .collect_flat_arrays_with();
+    assert_eq!(v, [0, 10, 1, 11, 2, 12, 3, 13]);
+    assert_eq!(v.capacity(), v.len());
+
+    // A low hint only costs a regrowth.
+    let v =
+      HintedIter::lying((0..5).map(|i| [i; 3]), (1, Some(1)), HintBehavior::Frozen)
+        .collect_flat_arrays_with();
+    assert_eq!(v.len(), 15);
+    assert_eq!(v[12..], [4, 4, 4]);
+
+    let v = (0..3)
+      .map(|i| [i])
+      .collect_flat_arrays_with();
+    assert_eq!(v, [0, 1, 2]);
+    assert_eq!(v.capacity(), 3);
+
+    let v: Vec<u8> = (0..3)
+      .map(|_| [])
+      .collect_flat_arrays_with();
+    assert!(v.is_empty());
+    assert_eq!(v.capacity(), 0);
+  }
+
+  #[test]
+  fn collect_keys_and_values_vec() {
+    use alloc::{string::String, vec};
+
+    let owned = vec![String::from("x"), String::from("yz")];
+    // The keys are moved out; nothing is cloned.
+    let keys = owned
+      .into_iter()
+      .zip(0..)
+      .collect_keys_vec_with(|n| n * 2);
+    assert_eq!(keys, ["x", "yz"]);
+    assert_eq!(keys.capacity(), 4);
+
+    let values = ('a'..='d')
+      .zip(10..)
+      .collect_values_vec_with(|n| n);
+    assert_eq!(values, [10, 11, 12, 13]);
+    assert_eq!(values.capacity(), 4);
+
+    let map = alloc::collections::BTreeMap::from([(1, 'p'), (2, 'q')]);
+    let refs: Vec<&char> = map
+      .iter()
+      .collect_values_vec_with(|n| n);
+    assert_eq!(refs, [&'p', &'q']);
+    assert_eq!(refs.capacity(), 2);
+  }
+
+  #[test]
+  fn collect_vec_with_exact_checked_reports_growth() {
+    let v = HintedIter::bounds(0..6, 0, None)
+      .collect_vec_with_exact_checked(|_| 6)
+      .unwrap();
+    assert_eq!(v, [0, 1, 2, 3, 4, 5]);
+    assert_eq!(v.capacity(), 6);
+
+    // More room than needed is fine.
+    assert!(
+      (0..3)
+        .collect_vec_with_exact_checked(|n| n + 10)
+        .is_ok()
+    );
+
+    let err = HintedIter::lying(0..10, (4, Some(4)), HintBehavior::Frozen)
+      .collect_vec_with_exact_checked(|n| n)
+      .unwrap_err();
+    assert_eq!(
+      err,
+      ExactViolated {
+        requested: 4,
+        final_len: 10
+      }
+    );
+  }
+
+  #[test]
+  fn collect_packed_records() {
+    let (records, rest) = (0..12u8).collect_packed_with::<3>(|n| n);
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[3], [9, 10, 11]);
+    assert!(rest.is_empty());
+    assert_eq!(records.capacity(), 4);
+
+    let (records, rest) = (0..14u8).collect_packed_with::<4>(|n| n + 1);
+    assert_eq!(records.len(), 3);
+    assert_eq!(rest, [12, 13]);
+    assert_eq!(records.capacity(), 4);
+
+    // Fewer bytes than one record.
+    let (records, rest) = (0..5u8).collect_packed_with::<8>(|n| n);
+    assert!(records.is_empty());
+    assert_eq!(rest, [0, 1, 2, 3, 4]);
+
+    let (records, _) =
+      HintedIter::bounds(0..32u8, 0, None).collect_packed_with::<16>(|_| 2);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records.capacity(), 2);
+
+    assert_eq!(
+      (0..7u8).try_collect_packed_with::<2>(|n| n),
+      Err(TrailingBytes { count: 1 })
+    );
+    assert_eq!(
+      core::iter::empty().try_collect_packed_with::<2>(|n| n),
+      Ok(Vec::new())
+    );
+  }
+
+  #[test]
+  fn flat_slices_match_flatten() {
+    use alloc::{vec, vec::Vec};
+
+    let chunks: Vec<Vec<u16>> = (0..64u16)
+      .map(|i| vec![i; usize::from(i % 7)])
+      .collect();
+    let total: usize = chunks
+      .iter()
+      .map(Vec::len)
+      .sum();
+
+    let flattened = chunks
+      .iter()
+      .flatten()
+      .copied()
+      .collect_vec_with(|n| n);
+    let fast = chunks
+      .iter()
+      .collect_flat_slices_with(|n| n * 4);
+    let exact = chunks
+      .iter()
+      .collect_flat_slices_exact();
+    assert_eq!(fast, flattened);
+    assert_eq!(exact, flattened);
+
+    // `flatten()` starts from a zero hint and regrows, while the slice
+    // count gives the closure something to scale.
+    assert_eq!(fast.capacity(), 64 * 4);
+    assert_eq!(exact.capacity(), total);
+
+    // With a `(0, None)` hint only the closure result is left.
+    let unknown = HintedIter::bounds(chunks.iter(), 0, None)
+      .collect_flat_slices_with(|n| {
+        assert_eq!(n, 0);
+        total
+      });
+    assert_eq!(unknown, flattened);
+    assert_eq!(unknown.capacity(), total);
+
+    let empty = core::iter::empty::<&[u8]>().collect_flat_slices_exact();
+    assert_eq!(empty.capacity(), 0);
+  }
 }
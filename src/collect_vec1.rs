@@ -0,0 +1,152 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use vec1::{Size0Error, Vec1};
+
+use crate::common::collect_iterator;
+#[cfg(feature = "try")]
+use crate::{TryExtract, common::try_collect_iterator};
+
+impl<I: Iterator> CollectVec1 for I {}
+
+/// Trait for collecting items into a non-empty [`Vec1`].
+///
+/// Items are collected into a `Vec` with the usual capacity rule, then
+/// converted without copying. Empty input is reported as an error instead
+/// of panicking.
+pub trait CollectVec1: Iterator {
+  /// Collect iterator elements into a `Vec1`, or fail with [`Size0Error`]
+  /// when there are none.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::CollectVec1;
+  ///
+  /// let hosts = "a.example b.example"
+  ///   .split(' ')
+  ///   .collect_vec1_with(|n| n)
+  ///   .unwrap();
+  /// assert_eq!(hosts.first(), &"a.example");
+  ///
+  /// assert!(core::iter::empty::<u8>().collect_vec1_with(|n| n).is_err());
+  /// ```
+  fn collect_vec1_with(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<Vec1<Self::Item>, Size0Error>
+  where
+    Self: Sized,
+  {
+    Vec1::try_from_vec(collect_iterator::<Vec<_>, _>(self, false, capacity))
+  }
+
+  /// Attempt to collect iterator elements into a `Vec1`, short-circuiting on
+  /// the first item error.
+  ///
+  /// [`CollectVec1Error`] tells a failed item apart from empty input.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::{CollectVec1, CollectVec1Error};
+  ///
+  /// let ports = ["80", "443"]
+  ///   .into_iter()
+  ///   .map(str::parse::<u16>)
+  ///   .try_collect_vec1_with(|n| n)
+  ///   .unwrap();
+  /// assert_eq!(ports.len(), 2);
+  ///
+  /// let err = ["80", "http"]
+  ///   .into_iter()
+  ///   .map(str::parse::<u16>)
+  ///   .try_collect_vec1_with(|n| n)
+  ///   .unwrap_err();
+  /// assert!(matches!(err, CollectVec1Error::Inner(_)));
+  /// ```
+  #[cfg(feature = "try")]
+  fn try_collect_vec1_with<'a, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<Vec1<OK>, CollectVec1Error<ERR>>
+  where
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    let vec: Vec<_> = try_collect_iterator(self, false, capacity)
+      .map_err(CollectVec1Error::Inner)?;
+    Vec1::try_from_vec(vec).map_err(|Size0Error| CollectVec1Error::Empty)
+  }
+}
+
+/// Error returned by
+/// [try_collect_vec1_with()](CollectVec1::try_collect_vec1_with).
+///
+/// Empty input only fails because the target is a `Vec1`, so the case lives
+/// next to it rather than in [`CollectError`](crate::CollectError), and the
+/// two arms cover every outcome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CollectVec1Error<E> {
+  /// The iterator yielded no items.
+  Empty,
+  /// An item could not be extracted.
+  Inner(E),
+}
+
+impl<E: fmt::Display> fmt::Display for CollectVec1Error<E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Empty => f.write_str("no items for Vec1"),
+      Self::Inner(e) => e.fmt(f),
+    }
+  }
+}
+
+impl<E> core::error::Error for CollectVec1Error<E>
+where
+  E: core::error::Error + 'static,
+{
+  fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+    match self {
+      Self::Inner(e) => Some(e),
+      Self::Empty => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn collect_vec1_sizes() {
+    let empty = core::iter::empty::<char>().collect_vec1_with(|n| n + 4);
+    assert_eq!(empty, Err(Size0Error));
+
+    let one = core::iter::once('x')
+      .collect_vec1_with(|n| n + 4)
+      .unwrap();
+    assert_eq!(one.as_slice(), ['x']);
+    assert!(one.capacity() >= 5);
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_collect_vec1_errors() {
+    let empty = core::iter::empty::<Result<u8, &str>>().try_collect_vec1_with(|n| n);
+    assert_eq!(empty, Err(CollectVec1Error::Empty));
+
+    let one = [Some(7)]
+      .into_iter()
+      .try_collect_vec1_with(|n| n);
+    assert_eq!(one.map(Vec1::into_vec), Ok(alloc::vec![7]));
+
+    let failed = [Ok(1), Err("bad"), Ok(3)]
+      .into_iter()
+      .try_collect_vec1_with(|n| n);
+    assert_eq!(failed, Err(CollectVec1Error::Inner("bad")));
+  }
+}
@@ -1,4 +1,6 @@
-use crate::ExtendWithCapacity;
+#[cfg(feature = "try")]
+use crate::TryExtract;
+use crate::{ExtendWithCapacity, ExtendWithCapacityAndHasher};
 
 pub(crate) fn collect_iterator<T, I>(
   iter: I,
@@ -9,12 +11,38 @@ where
   T: ExtendWithCapacity<I::Item>,
   I: Iterator,
 {
-  let bound = get_max_hint_bound(iter.size_hint());
-  let real_capacity = match capacity(bound) {
+  let real_capacity = resolve_capacity(iter.size_hint(), exact, capacity);
+  collect_with_exact_capacity(iter, real_capacity)
+}
+
+pub(crate) fn collect_iterator_with_hasher<T, I>(
+  iter: I,
+  hasher: T::Hasher,
+  exact: bool,
+  capacity: impl FnOnce(usize) -> usize,
+) -> T
+where
+  T: ExtendWithCapacityAndHasher<I::Item>,
+  I: Iterator,
+{
+  let real_capacity = resolve_capacity(iter.size_hint(), exact, capacity);
+  let mut container = T::with_capacity_and_hasher(real_capacity, hasher);
+  container.extend(iter);
+  container
+}
+
+/// Applies the capacity closure to the size bound: the result is raised to
+/// the bound, unless `exact` is set.
+fn resolve_capacity(
+  size_hint: (usize, Option<usize>),
+  exact: bool,
+  capacity: impl FnOnce(usize) -> usize,
+) -> usize {
+  let bound = get_max_hint_bound(size_hint);
+  match capacity(bound) {
     n if exact => n,
     n => n.max(bound),
-  };
-  collect_with_exact_capacity(iter, real_capacity)
+  }
 }
 
 pub(crate) fn collect_with_exact_capacity<T, I>(iter: I, capacity: usize) -> T
@@ -27,6 +55,43 @@ where
   container
 }
 
+#[cfg(feature = "try")]
+pub(crate) fn try_collect_iterator<'a, T, I, OK, ERR>(
+  iter: I,
+  exact: bool,
+  capacity: impl FnOnce(usize) -> usize,
+) -> Result<T, ERR>
+where
+  T: ExtendWithCapacity<OK>,
+  I: Iterator,
+  I::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+{
+  let real_capacity = resolve_capacity(iter.size_hint(), exact, capacity);
+  let mut container = T::with_capacity(real_capacity);
+
+  for item in iter {
+    let value = item.try_extract()?;
+    container.extend(core::iter::once(value));
+  }
+
+  Ok(container)
+}
+
+/// Clamps a capacity in items of `T` to the largest any allocation can hold,
+/// `isize::MAX` bytes.
+pub(crate) fn saturating_capacity<T>(capacity: usize) -> usize {
+  capacity.min(isize::MAX as usize / size_of::<T>().max(1))
+}
+
+/// Capacity closure that uses the size bound as is, or `fallback` when the
+/// bound is 0.
+pub(crate) fn bound_or(fallback: usize) -> impl FnOnce(usize) -> usize {
+  move |bound| match bound {
+    0 => fallback,
+    n => n,
+  }
+}
+
 /// Calculate maximum potential element count from iterator size hints.
 ///
 /// - `size_hint`
@@ -0,0 +1,128 @@
+//! Shared "get or insert, then update" core for the map-building collectors.
+
+use core::hash::{BuildHasher, Hash};
+use std::collections::HashMap;
+
+use crate::{ExtendWithCapacity, common::get_max_hint_bound};
+
+/// Maps that can hand out a mutable reference to the value at `key`,
+/// inserting one first if needed.
+pub(crate) trait EntryMap<K, V>: ExtendWithCapacity<(K, V)> {
+  fn entry_or_insert_with(&mut self, key: K, default: impl FnOnce() -> V) -> &mut V;
+}
+
+impl<K, V, S> EntryMap<K, V> for HashMap<K, V, S>
+where
+  K: Eq + Hash,
+  S: BuildHasher + Default,
+{
+  fn entry_or_insert_with(&mut self, key: K, default: impl FnOnce() -> V) -> &mut V {
+    self
+      .entry(key)
+      .or_insert_with(default)
+  }
+}
+
+#[cfg(feature = "ahash")]
+impl<K: Eq + Hash, V> EntryMap<K, V> for ahash::AHashMap<K, V> {
+  fn entry_or_insert_with(&mut self, key: K, default: impl FnOnce() -> V) -> &mut V {
+    self
+      .entry(key)
+      .or_insert_with(default)
+  }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> EntryMap<K, V> for indexmap::IndexMap<K, V, S>
+where
+  K: Eq + Hash,
+  S: BuildHasher + Default,
+{
+  fn entry_or_insert_with(&mut self, key: K, default: impl FnOnce() -> V) -> &mut V {
+    self
+      .entry(key)
+      .or_insert_with(default)
+  }
+}
+
+/// Creates a map pre-sized from the hint of `iter`.
+pub(crate) fn map_for<M, K, V>(
+  iter: &impl Iterator,
+  capacity: impl FnOnce(usize) -> usize,
+) -> M
+where
+  M: EntryMap<K, V>,
+{
+  let bound = get_max_hint_bound(iter.size_hint());
+  M::with_capacity(capacity(bound).max(bound))
+}
+
+/// Folds `(key, delta)` pairs into per-key values, starting each key from
+/// `V::default()`.
+///
+/// The general primitive behind the counting and grouping collectors.
+pub(crate) fn aggregate<M, I, K, V, D>(
+  iter: I,
+  capacity: impl FnOnce(usize) -> usize,
+  mut apply: impl FnMut(&mut V, D),
+) -> M
+where
+  M: EntryMap<K, V>,
+  I: Iterator<Item = (K, D)>,
+  V: Default,
+{
+  let mut map: M = map_for(&iter, capacity);
+  for (key, delta) in iter {
+    apply(map.entry_or_insert_with(key, V::default), delta);
+  }
+  map
+}
+
+/// Counts the items of `iter` per derived key.
+pub(crate) fn group_counts<M, I, K>(
+  iter: I,
+  capacity: impl FnOnce(usize) -> usize,
+  mut key: impl FnMut(&I::Item) -> K,
+) -> M
+where
+  M: EntryMap<K, usize>,
+  I: Iterator,
+{
+  aggregate(iter.map(|item| (key(&item), ())), capacity, |n, ()| *n += 1)
+}
+
+/// Counts equal items of `iter`.
+pub(crate) fn group_counts_by_item<M, I>(
+  iter: I,
+  capacity: impl FnOnce(usize) -> usize,
+) -> M
+where
+  M: EntryMap<I::Item, usize>,
+  I: Iterator,
+{
+  aggregate(iter.map(|item| (item, ())), capacity, |n, ()| *n += 1)
+}
+
+/// Groups the values of `(key, value)` pairs into per-key sets, each new set
+/// pre-sized to `set_capacity`.
+///
+/// Any [`ExtendWithCapacity`] container works as the "set", e.g. an inner
+/// map for two-level grouping.
+pub(crate) fn group_sets<M, S, I, K, V>(
+  iter: I,
+  capacity: impl FnOnce(usize) -> usize,
+  set_capacity: usize,
+) -> M
+where
+  M: EntryMap<K, S>,
+  S: ExtendWithCapacity<V>,
+  I: Iterator<Item = (K, V)>,
+{
+  let mut map: M = map_for(&iter, capacity);
+  for (key, value) in iter {
+    map
+      .entry_or_insert_with(key, || S::with_capacity(set_capacity))
+      .extend(core::iter::once(value));
+  }
+  map
+}
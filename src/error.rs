@@ -0,0 +1,393 @@
+use alloc::vec::Vec;
+use core::{convert::Infallible, fmt};
+
+/// Error type shared by collectors that validate what they collect.
+///
+/// `E` is the error of the items themselves (e.g. a parse error); collectors
+/// whose items cannot fail use the default, [`Infallible`]. Any `E` converts
+/// into [`CollectError::Inner`], so `?` works on both item errors and
+/// collection errors in the same function.
+///
+/// A few collectors report failures that do not fit these variants and
+/// return their own type instead: [`ShortInput`], [`ExactViolated`],
+/// [`TrailingBytes`] and `CollectVec1Error` (feature `vec1`). Each of them
+/// has a fixed set of cases, so unlike this `#[non_exhaustive]` enum it can
+/// be matched without a wildcard arm. The type docs say why each one is kept
+/// apart.
+///
+/// ## Example
+///
+/// ```
+/// use core::num::ParseIntError;
+///
+/// use collect_with::CollectError;
+///
+/// fn parse_pair(s: &str) -> Result<[u8; 2], CollectError<ParseIntError>> {
+///   let nums = s
+///     .split(',')
+///     .map(str::parse)
+///     .collect::<Result<Vec<u8>, _>>()?;
+///   nums
+///     .try_into()
+///     .map_err(|v: Vec<_>| CollectError::LengthMismatch {
+///       expected: 2,
+///       actual: v.len(),
+///     })
+/// }
+///
+/// assert_eq!(parse_pair("1,2"), Ok([1, 2]));
+/// assert_eq!(
+///   parse_pair("1,2,3").unwrap_err().to_string(),
+///   "expected 2 items, got 3"
+/// );
+/// assert!(matches!(parse_pair("1,x"), Err(CollectError::Inner(_))));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CollectError<E = Infallible> {
+  /// A key was seen more than once where keys must be unique.
+  DuplicateKey,
+  /// More than `limit` items were yielded.
+  LimitExceeded { limit: usize },
+  /// The number of items differs from the expected length.
+  LengthMismatch { expected: usize, actual: usize },
+  /// The container cannot hold `requested` items.
+  CapacityOverflow { requested: usize },
+  /// The item at `index` is out of order.
+  Unsorted { index: usize },
+  /// An item could not be extracted.
+  Inner(E),
+}
+
+impl<E> CollectError<E> {
+  /// Maps the `Inner` error, leaving the other variants untouched.
+  pub fn map_inner<F>(self, f: impl FnOnce(E) -> F) -> CollectError<F> {
+    match self {
+      Self::DuplicateKey => CollectError::DuplicateKey,
+      Self::LimitExceeded { limit } => CollectError::LimitExceeded { limit },
+      Self::LengthMismatch { expected, actual } => {
+        CollectError::LengthMismatch { expected, actual }
+      }
+      Self::CapacityOverflow { requested } => {
+        CollectError::CapacityOverflow { requested }
+      }
+      Self::Unsorted { index } => CollectError::Unsorted { index },
+      Self::Inner(e) => CollectError::Inner(f(e)),
+    }
+  }
+}
+
+impl<E> From<E> for CollectError<E> {
+  fn from(e: E) -> Self {
+    Self::Inner(e)
+  }
+}
+
+impl<E: fmt::Display> fmt::Display for CollectError<E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::DuplicateKey => f.write_str("duplicate key"),
+      Self::LimitExceeded { limit } => {
+        write!(f, "more than {limit} items")
+      }
+      Self::LengthMismatch { expected, actual } => {
+        write!(f, "expected {expected} items, got {actual}")
+      }
+      Self::CapacityOverflow { requested } => {
+        write!(f, "capacity overflow: {requested} items requested")
+      }
+      Self::Unsorted { index } => write!(f, "item {index} is out of order"),
+      Self::Inner(e) => e.fmt(f),
+    }
+  }
+}
+
+impl<E> core::error::Error for CollectError<E>
+where
+  E: core::error::Error + 'static,
+{
+  fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+    match self {
+      Self::Inner(e) => Some(e),
+      _ => None,
+    }
+  }
+}
+
+/// Error returned when an `Option` item is `None`.
+///
+/// Collecting `Option`s through [`TryExtract`](crate::TryExtract) fails with
+/// `()`, which carries no message and does not implement
+/// [`Error`](core::error::Error). `NoneError` is a drop-in replacement that
+/// does, so it converts into `Box<dyn Error>` or `anyhow::Error` with `?`.
+/// See [try_collect_with_none_err()](crate::TryCollectWith::try_collect_with_none_err).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NoneError;
+
+impl fmt::Display for NoneError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("encountered a `None` item")
+  }
+}
+
+impl core::error::Error for NoneError {}
+
+impl From<()> for NoneError {
+  fn from((): ()) -> Self {
+    Self
+  }
+}
+
+/// Error returned by
+/// [collect_exact_take()](crate::CollectWith::collect_exact_take)
+/// when the iterator ends before `expected` items.
+///
+/// The items that were taken are dropped before the error is returned.
+///
+/// This is not a [`CollectError::LengthMismatch`]: that variant counts the
+/// whole input, while `collect_exact_take()` stops after `expected` items
+/// and leaves the rest of the iterator alone, so only a short input fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShortInput {
+  pub expected: usize,
+  pub actual: usize,
+}
+
+impl fmt::Display for ShortInput {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let Self { expected, actual } = self;
+    write!(f, "expected {expected} items, input ended after {actual}")
+  }
+}
+
+impl core::error::Error for ShortInput {}
+
+/// Error returned by
+/// [collect_vec_with_exact_checked()](crate::CollectVector::collect_vec_with_exact_checked)
+/// when the items did not fit in the requested capacity.
+///
+/// The items themselves were collected fine; what failed is the capacity
+/// estimate of the closure. [`CollectError::CapacityOverflow`] is about the
+/// limit of the container instead, so this gets a type of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExactViolated {
+  /// The capacity returned by the closure.
+  pub requested: usize,
+  /// The number of items that were collected.
+  pub final_len: usize,
+}
+
+impl fmt::Display for ExactViolated {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let Self {
+      requested,
+      final_len,
+    } = self;
+    write!(
+      f,
+      "requested capacity {requested}, but collected {final_len} items"
+    )
+  }
+}
+
+impl core::error::Error for ExactViolated {}
+
+/// Error returned by
+/// [try_collect_packed_with()](crate::CollectVector::try_collect_packed_with)
+/// when the input ends in a partial record of `count` bytes.
+///
+/// `count` is a number of bytes, while every [`CollectError`] variant counts
+/// items, so the framing error is kept out of that enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrailingBytes {
+  pub count: usize,
+}
+
+impl fmt::Display for TrailingBytes {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} trailing bytes after the last record", self.count)
+  }
+}
+
+impl core::error::Error for TrailingBytes {}
+
+/// Bounded summary of the item errors of a fallible collection, returned by
+/// [try_collect_with_error_summary()](crate::TryCollectWith::try_collect_with_error_summary).
+///
+/// Every error is counted, but only the first few are kept as `examples`,
+/// each with the index of the item that produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorSummary<E> {
+  /// Number of failed items in the whole input.
+  pub total_errors: usize,
+  /// Index of the first failed item.
+  pub first_index: usize,
+  /// The first errors, in input order, at most as many as requested.
+  pub examples: Vec<(usize, E)>,
+}
+
+impl<E: fmt::Display> fmt::Display for ErrorSummary<E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let Self {
+      total_errors,
+      first_index,
+      ..
+    } = self;
+    write!(
+      f,
+      "{total_errors} failed items, first at index {first_index}"
+    )?;
+    if let Some((_, first)) = self.examples.first() {
+      write!(f, ": {first}")?;
+    }
+    Ok(())
+  }
+}
+
+impl<E> core::error::Error for ErrorSummary<E>
+where
+  E: core::error::Error + 'static,
+{
+  fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+    self
+      .examples
+      .first()
+      .map(|(_, e)| e as _)
+  }
+}
+
+/// Error of a [`Poll<Result<T, E>>`](core::task::Poll) item, as extracted by
+/// [`TryExtract`](crate::TryExtract).
+///
+/// A `Pending` item is not a failure of the value itself, but it still has
+/// no value to collect, so it is reported separately from errors of
+/// `Ready(Err(_))` items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PollError<E> {
+  /// The item was [`Poll::Pending`](core::task::Poll::Pending).
+  Pending,
+  /// The item was `Ready(Err(e))`.
+  Inner(E),
+}
+
+impl<E> From<E> for PollError<E> {
+  fn from(e: E) -> Self {
+    Self::Inner(e)
+  }
+}
+
+impl<E: fmt::Display> fmt::Display for PollError<E> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Pending => f.write_str("item is still pending"),
+      Self::Inner(e) => e.fmt(f),
+    }
+  }
+}
+
+impl<E> core::error::Error for PollError<E>
+where
+  E: core::error::Error + 'static,
+{
+  fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+    match self {
+      Self::Inner(e) => Some(e),
+      Self::Pending => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::string::ToString;
+  use core::{error::Error, num::ParseIntError};
+
+  use super::*;
+
+  #[test]
+  fn display_every_variant() {
+    let cases: [(CollectError, &str); 5] = [
+      (CollectError::DuplicateKey, "duplicate key"),
+      (
+        CollectError::LimitExceeded { limit: 3 },
+        "more than 3 items",
+      ),
+      (
+        CollectError::LengthMismatch {
+          expected: 4,
+          actual: 2,
+        },
+        "expected 4 items, got 2",
+      ),
+      (
+        CollectError::CapacityOverflow { requested: 9 },
+        "capacity overflow: 9 items requested",
+      ),
+      (
+        CollectError::Unsorted { index: 1 },
+        "item 1 is out of order",
+      ),
+    ];
+    for (err, msg) in cases {
+      assert_eq!(err.to_string(), msg);
+      assert!(err.source().is_none());
+    }
+
+    let inner = "x".parse::<u8>().unwrap_err();
+    let err = CollectError::Inner(inner.clone());
+    assert_eq!(err.to_string(), inner.to_string());
+    assert!(err.source().is_some());
+  }
+
+  #[test]
+  fn question_mark_converts_inner() {
+    fn parse(s: &str) -> Result<u8, CollectError<ParseIntError>> {
+      let n = s.parse::<u8>()?;
+      match n {
+        0 => Err(CollectError::LimitExceeded { limit: 0 }),
+        n => Ok(n),
+      }
+    }
+    assert_eq!(parse("7"), Ok(7));
+    assert!(matches!(parse("y"), Err(CollectError::Inner(_))));
+    assert_eq!(parse("0"), Err(CollectError::LimitExceeded { limit: 0 }));
+
+    let mapped = parse("y")
+      .unwrap_err()
+      .map_inner(|_| ());
+    assert_eq!(mapped, CollectError::Inner(()));
+  }
+
+  #[test]
+  fn none_error_boxes() {
+    fn lookup(v: Option<u8>) -> Result<u8, alloc::boxed::Box<dyn Error>> {
+      Ok(v.ok_or(NoneError)?)
+    }
+    assert_eq!(lookup(Some(4)).unwrap(), 4);
+    let err = lookup(None).unwrap_err();
+    assert!(err.is::<NoneError>());
+    assert_eq!(err.to_string(), "encountered a `None` item");
+    assert_eq!(NoneError::from(()), NoneError);
+  }
+
+  #[test]
+  fn poll_error_display_and_source() {
+    let pending = PollError::<ParseIntError>::Pending;
+    assert_eq!(pending.to_string(), "item is still pending");
+    assert!(pending.source().is_none());
+
+    let inner = "x".parse::<u8>().unwrap_err();
+    let err = PollError::from(inner.clone());
+    assert_eq!(err.to_string(), inner.to_string());
+    assert!(err.source().is_some());
+  }
+
+  #[test]
+  fn short_input_display() {
+    let err = ShortInput {
+      expected: 4,
+      actual: 1,
+    };
+    assert_eq!(err.to_string(), "expected 4 items, input ended after 1");
+  }
+}
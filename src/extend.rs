@@ -1,5 +1,5 @@
 use alloc::{
-  collections::{BinaryHeap, VecDeque},
+  collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque},
   string::String,
   vec::Vec,
 };
@@ -16,41 +16,242 @@ use alloc::{
 /// might return `(0, None)`, pre-allocating capacity can be particularly
 /// useful.
 ///
+/// Containers are implemented for their owned item type only, so that
+/// `Vec<_>` or `HashSet<_>` stays inferable when the items are references
+/// (the container then holds the references). To copy borrowed items into
+/// owned ones, use
+/// [collect_copied_with()](crate::CollectWith::collect_copied_with) or
+/// [collect_copied_pairs_with()](crate::CollectWith::collect_copied_pairs_with).
+///
 /// # Implementors
 /// - alloc collections: `Vec<T>`, `String`, `VecDeque<T>`, `BinaryHeap<T>`
+/// - `BTreeMap` and `BTreeSet` (capacity is ignored)
 /// - std types (with `std` feature): `OsString`, `HashMap`, `HashSet`,
 ///   `PathBuf`
 /// - AHash collections (with `ahash` & `std` features): `AHashMap`, `AHashSet`
+/// - hashbrown collections (with `hashbrown` feature, no_std): `HashMap`,
+///   `HashSet`
+/// - `SmallVec` (with `smallvec` feature)
+/// - `ArrayVec` (with `arrayvec` feature)
+/// - `TinyVec`, tinyvec's `ArrayVec` (with `tinyvec` feature)
+/// - `BytesMut` from `u8` and `&u8` (with `bytes` feature)
+/// - `CompactString` (with `compact_str` feature)
+/// - `BitVec` from `bool`, sized in bits (with `bitvec` feature)
+/// - `DashMap` (with `dashmap` feature)
+/// - `heapless::Vec` (with `heapless` feature)
+/// - `Utf8PathBuf` (with `camino` feature)
+/// - `im::Vector`, `im::HashMap`, `im::HashSet` (with `im` feature; capacity is
+///   ignored)
+/// - `HashMap`/`HashSet` with any `S: BuildHasher + Default`, which covers
+///   `foldhash::fast::RandomState` (with `foldhash` feature),
+///   `rustc_hash::FxBuildHasher` (with `rustc-hash` feature) and
+///   `fnv::FnvBuildHasher` (with `fnv` feature)
 pub trait ExtendWithCapacity<T>: Extend<T> {
+  /// Capacity used by the `*_hinted()` collection methods when the iterator's
+  /// size hint is `(0, Some(0))` or `(0, None)`.
+  ///
+  /// Defaults to 0 (no pre-allocation).
+  const FALLBACK_CAPACITY: usize = 0;
+
   fn with_capacity(capacity: usize) -> Self;
+
+  /// Reserves capacity for at least `additional` more elements.
+  ///
+  /// Used by [collect_with_headroom()](crate::CollectWith::collect_with_headroom)
+  /// to guarantee spare capacity after collection. The default implementation
+  /// does nothing, which suits containers that cannot grow up front.
+  fn reserve(&mut self, additional: usize) {
+    let _ = additional;
+  }
+}
+
+/// A trait for hashed collections that can be pre-allocated with a given
+/// hasher instance.
+///
+/// [`ExtendWithCapacity`] builds the hasher with `Default`, which cannot
+/// express a seeded or otherwise pre-configured hasher. This trait takes the
+/// instance instead; see
+/// [collect_with_hasher()](crate::CollectWithHasher::collect_with_hasher).
+///
+/// # Implementors
+/// - std `HashMap` and `HashSet` (with `std` feature)
+/// - `AHashMap` and `AHashSet` (with `ahash` feature)
+/// - `IndexMap` and `IndexSet` (with `indexmap` feature)
+pub trait ExtendWithCapacityAndHasher<T>: Extend<T> {
+  /// The hasher builder stored in the collection.
+  type Hasher;
+
+  fn with_capacity_and_hasher(capacity: usize, hasher: Self::Hasher) -> Self;
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> ExtendWithCapacityAndHasher<(K, V)>
+  for std::collections::HashMap<K, V, S>
+where
+  K: Eq + core::hash::Hash,
+  S: core::hash::BuildHasher,
+{
+  type Hasher = S;
+
+  fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    std::collections::HashMap::with_capacity_and_hasher(capacity, hasher)
+  }
+}
+
+#[cfg(feature = "std")]
+impl<K, S> ExtendWithCapacityAndHasher<K> for std::collections::HashSet<K, S>
+where
+  K: Eq + core::hash::Hash,
+  S: core::hash::BuildHasher,
+{
+  type Hasher = S;
+
+  fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    std::collections::HashSet::with_capacity_and_hasher(capacity, hasher)
+  }
+}
+
+#[cfg(feature = "ahash")]
+impl<K: Eq + core::hash::Hash, V> ExtendWithCapacityAndHasher<(K, V)>
+  for ahash::AHashMap<K, V>
+{
+  type Hasher = ahash::RandomState;
+
+  fn with_capacity_and_hasher(capacity: usize, hasher: Self::Hasher) -> Self {
+    ahash::AHashMap::with_capacity_and_hasher(capacity, hasher)
+  }
+}
+
+#[cfg(feature = "ahash")]
+impl<K: Eq + core::hash::Hash> ExtendWithCapacityAndHasher<K>
+  for ahash::AHashSet<K>
+{
+  type Hasher = ahash::RandomState;
+
+  fn with_capacity_and_hasher(capacity: usize, hasher: Self::Hasher) -> Self {
+    ahash::AHashSet::with_capacity_and_hasher(capacity, hasher)
+  }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> ExtendWithCapacityAndHasher<(K, V)> for indexmap::IndexMap<K, V, S>
+where
+  K: Eq + core::hash::Hash,
+  S: core::hash::BuildHasher,
+{
+  type Hasher = S;
+
+  fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    indexmap::IndexMap::with_capacity_and_hasher(capacity, hasher)
+  }
+}
+
+#[cfg(feature = "indexmap")]
+impl<K, S> ExtendWithCapacityAndHasher<K> for indexmap::IndexSet<K, S>
+where
+  K: Eq + core::hash::Hash,
+  S: core::hash::BuildHasher,
+{
+  type Hasher = S;
+
+  fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+    indexmap::IndexSet::with_capacity_and_hasher(capacity, hasher)
+  }
+}
+
+/// Like [`ExtendWithCapacity`], for containers that allocate through a
+/// custom [`Allocator`](core::alloc::Allocator).
+///
+/// Requires a nightly compiler (`allocator_api` feature).
+///
+/// # Implementors
+/// - `Vec<T, A>`, `VecDeque<T, A>`
+#[cfg(feature = "allocator_api")]
+pub trait ExtendWithCapacityIn<T, A: core::alloc::Allocator>: Extend<T> {
+  fn with_capacity_in(capacity: usize, alloc: A) -> Self;
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A: core::alloc::Allocator> ExtendWithCapacityIn<T, A> for Vec<T, A> {
+  fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+    Vec::with_capacity_in(capacity, alloc)
+  }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A: core::alloc::Allocator> ExtendWithCapacityIn<T, A> for VecDeque<T, A> {
+  fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+    VecDeque::with_capacity_in(capacity, alloc)
+  }
 }
 // ----------
 
 impl<T> ExtendWithCapacity<T> for Vec<T> {
+  /// 8 elements
+  const FALLBACK_CAPACITY: usize = 8;
+
   fn with_capacity(capacity: usize) -> Self {
     Vec::with_capacity(capacity)
   }
+
+  fn reserve(&mut self, additional: usize) {
+    Vec::reserve(self, additional)
+  }
 }
 
 impl<T> ExtendWithCapacity<T> for String
 where
   String: Extend<T>,
 {
+  /// 16 bytes
+  const FALLBACK_CAPACITY: usize = 16;
+
   fn with_capacity(capacity: usize) -> Self {
     String::with_capacity(capacity)
   }
+
+  fn reserve(&mut self, additional: usize) {
+    String::reserve(self, additional)
+  }
 }
 
 impl<T> ExtendWithCapacity<T> for VecDeque<T> {
   fn with_capacity(capacity: usize) -> Self {
     VecDeque::with_capacity(capacity)
   }
+
+  fn reserve(&mut self, additional: usize) {
+    VecDeque::reserve(self, additional)
+  }
 }
 
 impl<T: Ord> ExtendWithCapacity<T> for BinaryHeap<T> {
   fn with_capacity(capacity: usize) -> Self {
     BinaryHeap::with_capacity(capacity)
   }
+
+  fn reserve(&mut self, additional: usize) {
+    BinaryHeap::reserve(self, additional)
+  }
+}
+
+// BTree{Map, Set}
+
+/// B-trees allocate node by node and have no notion of capacity, so the
+/// capacity is ignored. The impl exists so B-trees work with the generic
+/// collectors, e.g.
+/// [try_collect_with()](crate::TryCollectWith::try_collect_with).
+impl<K: Ord, V> ExtendWithCapacity<(K, V)> for BTreeMap<K, V> {
+  fn with_capacity(_capacity: usize) -> Self {
+    BTreeMap::new()
+  }
+}
+
+/// See the note on `BTreeMap`: the capacity is ignored.
+impl<T: Ord> ExtendWithCapacity<T> for BTreeSet<T> {
+  fn with_capacity(_capacity: usize) -> Self {
+    BTreeSet::new()
+  }
 }
 
 // OsString
@@ -63,6 +264,10 @@ where
   fn with_capacity(capacity: usize) -> Self {
     std::ffi::OsString::with_capacity(capacity)
   }
+
+  fn reserve(&mut self, additional: usize) {
+    std::ffi::OsString::reserve(self, additional)
+  }
 }
 
 // Hash{Map, Set}
@@ -76,6 +281,10 @@ where
   fn with_capacity(capacity: usize) -> Self {
     std::collections::HashMap::with_capacity_and_hasher(capacity, S::default())
   }
+
+  fn reserve(&mut self, additional: usize) {
+    std::collections::HashMap::reserve(self, additional)
+  }
 }
 #[cfg(feature = "std")]
 impl<K, S> ExtendWithCapacity<K> for std::collections::HashSet<K, S>
@@ -86,6 +295,10 @@ where
   fn with_capacity(capacity: usize) -> Self {
     std::collections::HashSet::with_capacity_and_hasher(capacity, S::default())
   }
+
+  fn reserve(&mut self, additional: usize) {
+    std::collections::HashSet::reserve(self, additional)
+  }
 }
 
 // ahash{map, set}
@@ -97,12 +310,232 @@ impl<K: Eq + core::hash::Hash, V> ExtendWithCapacity<(K, V)>
   fn with_capacity(capacity: usize) -> Self {
     ahash::AHashMap::with_capacity(capacity)
   }
+
+  fn reserve(&mut self, additional: usize) {
+    (**self).reserve(additional)
+  }
 }
 #[cfg(feature = "ahash")]
 impl<K: Eq + core::hash::Hash> ExtendWithCapacity<K> for ahash::AHashSet<K> {
   fn with_capacity(capacity: usize) -> Self {
     ahash::AHashSet::with_capacity(capacity)
   }
+
+  fn reserve(&mut self, additional: usize) {
+    (**self).reserve(additional)
+  }
+}
+
+// hashbrown{map, set}
+
+#[cfg(feature = "hashbrown")]
+impl<K, V, S> ExtendWithCapacity<(K, V)> for hashbrown::HashMap<K, V, S>
+where
+  K: Eq + core::hash::Hash,
+  S: core::hash::BuildHasher + Default,
+{
+  fn with_capacity(capacity: usize) -> Self {
+    hashbrown::HashMap::with_capacity_and_hasher(capacity, S::default())
+  }
+
+  fn reserve(&mut self, additional: usize) {
+    hashbrown::HashMap::reserve(self, additional)
+  }
+}
+
+#[cfg(feature = "hashbrown")]
+impl<K, S> ExtendWithCapacity<K> for hashbrown::HashSet<K, S>
+where
+  K: Eq + core::hash::Hash,
+  S: core::hash::BuildHasher + Default,
+{
+  fn with_capacity(capacity: usize) -> Self {
+    hashbrown::HashSet::with_capacity_and_hasher(capacity, S::default())
+  }
+
+  fn reserve(&mut self, additional: usize) {
+    hashbrown::HashSet::reserve(self, additional)
+  }
+}
+
+// SmallVec
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> ExtendWithCapacity<A::Item> for smallvec::SmallVec<A> {
+  fn with_capacity(capacity: usize) -> Self {
+    smallvec::SmallVec::with_capacity(capacity)
+  }
+
+  fn reserve(&mut self, additional: usize) {
+    smallvec::SmallVec::reserve(self, additional)
+  }
+}
+
+// ArrayVec
+
+/// The capacity is fixed at `N`, so the requested capacity is ignored.
+///
+/// Extending past `N` items panics; use
+/// [collect_arrayvec_with()](crate::CollectArrayVec::collect_arrayvec_with) to
+/// get an error instead.
+#[cfg(feature = "arrayvec")]
+impl<T, const N: usize> ExtendWithCapacity<T> for arrayvec::ArrayVec<T, N> {
+  fn with_capacity(_capacity: usize) -> Self {
+    arrayvec::ArrayVec::new()
+  }
+}
+
+// TinyVec
+
+#[cfg(feature = "tinyvec")]
+impl<A: tinyvec::Array> ExtendWithCapacity<A::Item> for tinyvec::TinyVec<A> {
+  fn with_capacity(capacity: usize) -> Self {
+    tinyvec::TinyVec::with_capacity(capacity)
+  }
+
+  fn reserve(&mut self, additional: usize) {
+    tinyvec::TinyVec::reserve(self, additional)
+  }
+}
+
+/// The capacity is fixed at `A::CAPACITY`, so the requested capacity is
+/// ignored. Extending past it panics.
+#[cfg(feature = "tinyvec")]
+impl<A: tinyvec::Array> ExtendWithCapacity<A::Item> for tinyvec::ArrayVec<A> {
+  fn with_capacity(_capacity: usize) -> Self {
+    tinyvec::ArrayVec::new()
+  }
+}
+
+// heapless::Vec
+
+/// The capacity is fixed at `N`, so the requested capacity is ignored.
+/// Extending past `N` items panics; use
+/// [collect_heapless_with()](crate::CollectHeapless::collect_heapless_with) to
+/// get an error instead.
+#[cfg(feature = "heapless")]
+impl<T, const N: usize> ExtendWithCapacity<T> for heapless::Vec<T, N> {
+  fn with_capacity(_capacity: usize) -> Self {
+    heapless::Vec::new()
+  }
+}
+
+// BytesMut
+
+#[cfg(feature = "bytes")]
+impl ExtendWithCapacity<u8> for bytes::BytesMut {
+  fn with_capacity(capacity: usize) -> Self {
+    bytes::BytesMut::with_capacity(capacity)
+  }
+
+  fn reserve(&mut self, additional: usize) {
+    bytes::BytesMut::reserve(self, additional)
+  }
+}
+
+#[cfg(feature = "bytes")]
+impl ExtendWithCapacity<&u8> for bytes::BytesMut {
+  fn with_capacity(capacity: usize) -> Self {
+    bytes::BytesMut::with_capacity(capacity)
+  }
+
+  fn reserve(&mut self, additional: usize) {
+    bytes::BytesMut::reserve(self, additional)
+  }
+}
+
+// im{Vector, HashMap, HashSet}
+
+/// Persistent collections are built from shared chunks and nodes, so there is
+/// nothing to pre-size: the capacity is ignored and an empty collection is
+/// returned, to be filled by `extend()`.
+#[cfg(feature = "im")]
+impl<T: Clone> ExtendWithCapacity<T> for im::Vector<T> {
+  fn with_capacity(_capacity: usize) -> Self {
+    im::Vector::new()
+  }
+}
+
+/// See the note on `im::Vector`: the capacity is ignored.
+#[cfg(feature = "im")]
+impl<K, V, S> ExtendWithCapacity<(K, V)> for im::HashMap<K, V, S>
+where
+  K: core::hash::Hash + Eq + Clone,
+  V: Clone,
+  S: core::hash::BuildHasher + Default,
+{
+  fn with_capacity(_capacity: usize) -> Self {
+    im::HashMap::default()
+  }
+}
+
+/// See the note on `im::Vector`: the capacity is ignored.
+#[cfg(feature = "im")]
+impl<T, S> ExtendWithCapacity<T> for im::HashSet<T, S>
+where
+  T: core::hash::Hash + Eq + Clone,
+  S: core::hash::BuildHasher + Default,
+{
+  fn with_capacity(_capacity: usize) -> Self {
+    im::HashSet::default()
+  }
+}
+
+// CompactString
+
+/// Covers `char`, `&char`, `&str`, `String`, `Box<str>`, `Cow<str>` and
+/// `CompactString` items. Capacities up to the inline size (24 bytes on
+/// 64-bit targets) do not allocate.
+#[cfg(feature = "compact_str")]
+impl<T> ExtendWithCapacity<T> for compact_str::CompactString
+where
+  compact_str::CompactString: Extend<T>,
+{
+  /// 24 bytes, the inline size on 64-bit targets
+  const FALLBACK_CAPACITY: usize =
+    core::mem::size_of::<compact_str::CompactString>();
+
+  fn with_capacity(capacity: usize) -> Self {
+    compact_str::CompactString::with_capacity(capacity)
+  }
+
+  fn reserve(&mut self, additional: usize) {
+    compact_str::CompactString::reserve(self, additional)
+  }
+}
+
+// BitVec
+
+/// The capacity is in bits, like
+/// [`BitVec::with_capacity()`](bitvec::vec::BitVec::with_capacity).
+#[cfg(feature = "bitvec")]
+impl<T, O> ExtendWithCapacity<bool> for bitvec::vec::BitVec<T, O>
+where
+  T: bitvec::store::BitStore,
+  O: bitvec::order::BitOrder,
+{
+  fn with_capacity(capacity: usize) -> Self {
+    bitvec::vec::BitVec::with_capacity(capacity)
+  }
+
+  fn reserve(&mut self, additional: usize) {
+    bitvec::vec::BitVec::reserve(self, additional)
+  }
+}
+
+// DashMap
+
+/// The capacity is split evenly across the shards; see
+/// [collect_dashmap_with_exact()](crate::CollectDash::collect_dashmap_with_exact).
+#[cfg(feature = "dashmap")]
+impl<K, V, S> ExtendWithCapacity<(K, V)> for dashmap::DashMap<K, V, S>
+where
+  K: core::hash::Hash + Eq,
+  S: core::hash::BuildHasher + Clone + Default,
+{
+  fn with_capacity(capacity: usize) -> Self {
+    dashmap::DashMap::with_capacity_and_hasher(capacity, S::default())
+  }
 }
 
 // PathBuf
@@ -112,6 +545,21 @@ impl<T: AsRef<std::path::Path>> ExtendWithCapacity<T> for std::path::PathBuf {
   fn with_capacity(capacity: usize) -> Self {
     std::path::PathBuf::with_capacity(capacity)
   }
+
+  fn reserve(&mut self, additional: usize) {
+    std::path::PathBuf::reserve(self, additional)
+  }
+}
+
+#[cfg(feature = "camino")]
+impl<T: AsRef<camino::Utf8Path>> ExtendWithCapacity<T> for camino::Utf8PathBuf {
+  fn with_capacity(capacity: usize) -> Self {
+    camino::Utf8PathBuf::with_capacity(capacity)
+  }
+
+  fn reserve(&mut self, additional: usize) {
+    camino::Utf8PathBuf::reserve(self, additional)
+  }
 }
 
 // index{map, set}
@@ -125,6 +573,10 @@ where
   fn with_capacity(capacity: usize) -> Self {
     indexmap::IndexMap::with_capacity_and_hasher(capacity, S::default())
   }
+
+  fn reserve(&mut self, additional: usize) {
+    indexmap::IndexMap::reserve(self, additional)
+  }
 }
 
 #[cfg(feature = "indexmap")]
@@ -136,4 +588,8 @@ where
   fn with_capacity(capacity: usize) -> Self {
     indexmap::IndexSet::with_capacity_and_hasher(capacity, S::default())
   }
+
+  fn reserve(&mut self, additional: usize) {
+    indexmap::IndexSet::reserve(self, additional)
+  }
 }
@@ -0,0 +1,142 @@
+use crate::{ExtendWithCapacity, common::collect_iterator};
+#[cfg(feature = "try")]
+use crate::{TryExtract, common::try_collect_iterator};
+
+/// Container-side counterpart of [`CollectWith`](crate::CollectWith).
+///
+/// Where `CollectWith` is called on the iterator, this trait is called on the
+/// target type, mirroring [`FromIterator`]. Implementing
+/// [`ExtendWithCapacity`] is enough to get it for free.
+pub trait FromIteratorWithCapacity<T>: Sized {
+  /// Creates a container from `iter` with a capacity calculated from its size
+  /// hint.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`, the same
+  /// rule as [collect_with()](crate::CollectWith::collect_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::FromIteratorWithCapacity;
+  ///
+  /// let v = Vec::from_iter_with_capacity(0..4, |n| n + 8);
+  /// assert_eq!(v, [0, 1, 2, 3]);
+  /// assert_eq!(v.capacity(), 12);
+  /// ```
+  fn from_iter_with_capacity<I>(
+    iter: I,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Self
+  where
+    I: IntoIterator<Item = T>;
+}
+
+/// Blanket implementation for every `ExtendWithCapacity` container.
+impl<T, C: ExtendWithCapacity<T>> FromIteratorWithCapacity<T> for C {
+  fn from_iter_with_capacity<I>(
+    iter: I,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Self
+  where
+    I: IntoIterator<Item = T>,
+  {
+    collect_iterator(iter.into_iter(), false, capacity)
+  }
+}
+
+/// Fallible sibling of [`FromIteratorWithCapacity`].
+///
+/// Items are unwrapped through [`TryExtract`]; the first error stops the
+/// collection and is returned.
+#[cfg(feature = "try")]
+pub trait TryFromIteratorWithCapacity<T>: Sized {
+  /// Creates a container from fallible items, short-circuiting on the first
+  /// error.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::TryFromIteratorWithCapacity;
+  ///
+  /// let ok = Vec::try_from_iter_with_capacity([Some(1), Some(2)], |n| n);
+  /// assert_eq!(ok, Ok(vec![1, 2]));
+  ///
+  /// let err = String::try_from_iter_with_capacity(
+  ///   ["4", "x"].map(|s| s.parse::<u8>().map(char::from)),
+  ///   |n| n,
+  /// );
+  /// assert!(err.is_err());
+  /// ```
+  fn try_from_iter_with_capacity<'a, I, E>(
+    iter: I,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<Self, E>
+  where
+    I: IntoIterator,
+    I::Item: TryExtract<'a, Ok = T, Err = E>;
+}
+
+/// Blanket implementation for every `ExtendWithCapacity` container.
+#[cfg(feature = "try")]
+impl<T, C: ExtendWithCapacity<T>> TryFromIteratorWithCapacity<T> for C {
+  fn try_from_iter_with_capacity<'a, I, E>(
+    iter: I,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<Self, E>
+  where
+    I: IntoIterator,
+    I::Item: TryExtract<'a, Ok = T, Err = E>,
+  {
+    try_collect_iterator(iter.into_iter(), false, capacity)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{string::String, vec::Vec};
+
+  use super::*;
+
+  fn build<C: FromIteratorWithCapacity<u8>>(n: u8) -> C {
+    C::from_iter_with_capacity(0..n, |u| u * 2)
+  }
+
+  #[test]
+  fn constructors_on_containers() {
+    let v = Vec::from_iter_with_capacity([1, 2, 3], |n| n);
+    assert_eq!(v, [1, 2, 3]);
+    assert_eq!(v.capacity(), 3);
+
+    let s = String::from_iter_with_capacity("abc".chars(), |n| n + 1);
+    assert_eq!(s, "abc");
+    assert!(s.capacity() >= 4);
+
+    let v: Vec<u8> = build(3);
+    assert_eq!(v, [0, 1, 2]);
+    assert_eq!(v.capacity(), 6);
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn constructors_on_hashmap() {
+    use std::collections::{HashMap, HashSet};
+
+    let map: HashMap<_, _> =
+      HashMap::from_iter_with_capacity([(1, 'a'), (2, 'b')], |n| n + 6);
+    assert_eq!(map[&2], 'b');
+    assert!(map.capacity() >= 8);
+
+    let set: HashSet<u8> = build(4);
+    assert_eq!(set.len(), 4);
+  }
+
+  #[cfg(feature = "try")]
+  #[test]
+  fn try_constructors() {
+    let v = Vec::try_from_iter_with_capacity([Ok(1), Ok(2)], |n| n);
+    assert_eq!(v, Ok::<_, ()>(alloc::vec![1, 2]));
+
+    let v = Vec::<u8>::try_from_iter_with_capacity([Some(1), None], |n| n);
+    assert_eq!(v, Err(()));
+  }
+}
@@ -0,0 +1,135 @@
+use crate::{
+  ExtendWithCapacity,
+  common::{collect_with_exact_capacity, get_max_hint_bound},
+};
+
+/// A size bound computed once and reused across several collections of the
+/// same source.
+///
+/// When one source is cloned and each clone is adapted differently (e.g.
+/// filtered or mapped into different fields), the adapted clones often report
+/// weaker hints than the source. `HintCache` remembers the bound of the
+/// source, and [collect_from()](HintCache::collect_from) feeds it to the
+/// capacity closure instead of whatever the adapted iterator reports.
+///
+/// ## Example
+///
+/// ```
+/// use collect_with::HintCache;
+///
+/// let users = [(1, "ada", true), (2, "bob", false), (3, "cy", true)];
+/// let cache = HintCache::of(&users.iter());
+///
+/// let ids: Vec<_> = cache.collect_from(users.iter().map(|u| u.0), |n| n);
+/// let active: Vec<_> = cache.collect_from(users.iter().filter(|u| u.2).map(|u| u.1), |n| n);
+/// assert_eq!(ids, [1, 2, 3]);
+/// assert_eq!(active, ["ada", "cy"]);
+/// assert_eq!(active.capacity(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HintCache {
+  bound: usize,
+}
+
+impl HintCache {
+  /// Caches the size bound of a [size_hint()](Iterator::size_hint) tuple.
+  pub fn new(size_hint: (usize, Option<usize>)) -> Self {
+    Self {
+      bound: get_max_hint_bound(size_hint),
+    }
+  }
+
+  /// Caches the size bound of `iter`'s current hint.
+  pub fn of(iter: &impl Iterator) -> Self {
+    Self::new(iter.size_hint())
+  }
+
+  /// The cached size bound.
+  pub fn bound(&self) -> usize {
+    self.bound
+  }
+
+  /// Collects `iter` with the cached bound in place of its own hint.
+  ///
+  /// The capacity closure receives the cached bound, and the final capacity
+  /// is `max(bound, capacity(bound))`.
+  pub fn collect_from<I, T>(
+    &self,
+    iter: I,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> T
+  where
+    I: IntoIterator,
+    T: ExtendWithCapacity<I::Item>,
+  {
+    let bound = self.bound;
+    collect_with_exact_capacity(iter.into_iter(), capacity(bound).max(bound))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::{string::String, vec::Vec};
+
+  use super::*;
+  use crate::{HintBehavior, HintedIter};
+
+  #[test]
+  fn derived_clones_use_cached_bound() {
+    let source = HintedIter::exact(0..20u32, 20);
+    let cache = HintCache::of(&source);
+    assert_eq!(cache.bound(), 20);
+
+    let mut seen = Vec::new();
+    let evens: Vec<_> = cache.collect_from(
+      source
+        .clone()
+        .filter(|n| n % 2 == 0),
+      |n| {
+        seen.push(n);
+        n
+      },
+    );
+    let odds: Vec<_> = cache.collect_from(
+      source
+        .clone()
+        .filter(|n| n % 2 == 1),
+      |n| {
+        seen.push(n);
+        n
+      },
+    );
+    let letters: String = cache.collect_from(
+      HintedIter::lying(
+        source.map(|n| char::from(b'a' + n as u8)),
+        (0, None),
+        HintBehavior::Frozen,
+      ),
+      |n| {
+        seen.push(n);
+        n + 4
+      },
+    );
+    assert_eq!(seen, [20, 20, 20]);
+
+    assert_eq!(
+      evens,
+      (0..20)
+        .step_by(2)
+        .collect::<Vec<_>>()
+    );
+    assert_eq!(odds.len(), 10);
+    assert_eq!(letters, "abcdefghijklmnopqrst");
+    assert_eq!(evens.capacity(), 20);
+    assert_eq!(odds.capacity(), 20);
+    assert_eq!(letters.capacity(), 24);
+  }
+
+  #[test]
+  fn cache_from_tuple() {
+    let cache = HintCache::new((2, Some(8)));
+    assert_eq!(cache.bound(), 8);
+    let v: Vec<_> = cache.collect_from([1, 2], |_| 0);
+    assert_eq!(v.capacity(), 8);
+  }
+}
@@ -0,0 +1,163 @@
+use core::{cell::RefCell, iter::FusedIterator};
+
+impl<I: Iterator> InspectHint for I {}
+
+/// Trait for observing the size hints that flow through an iterator pipeline.
+pub trait InspectHint: Iterator {
+  /// Wrap the iterator, calling `f` with its current size hint once at
+  /// construction and every time [size_hint()](Iterator::size_hint) is
+  /// queried.
+  ///
+  /// The wrapper is transparent: items and the hint itself are forwarded
+  /// untouched. Place it right before a collect call to see exactly which
+  /// bound the capacity logic received.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::{CollectWith, InspectHint};
+  ///
+  /// let mut seen = Vec::new();
+  /// let v = (0..10)
+  ///   .filter(|x| x % 3 == 0)
+  ///   .hint_inspect(|lower, upper| seen.push((lower, upper)))
+  ///   .collect_with::<Vec<_>>(|n| n);
+  ///
+  /// assert_eq!(v, [0, 3, 6, 9]);
+  /// assert_eq!(seen[0], (0, Some(10)));
+  /// ```
+  fn hint_inspect<F>(self, f: F) -> HintInspect<Self, F>
+  where
+    F: FnMut(usize, Option<usize>),
+    Self: Sized,
+  {
+    HintInspect::new(self, f)
+  }
+}
+
+/// Iterator returned by [hint_inspect()](crate::InspectHint::hint_inspect).
+pub struct HintInspect<I, F> {
+  iter: I,
+  f: RefCell<F>,
+}
+
+impl<I, F> HintInspect<I, F>
+where
+  I: Iterator,
+  F: FnMut(usize, Option<usize>),
+{
+  fn new(iter: I, mut f: F) -> Self {
+    let (lower, upper) = iter.size_hint();
+    f(lower, upper);
+    Self {
+      iter,
+      f: RefCell::new(f),
+    }
+  }
+
+  /// Returns the inner iterator.
+  pub fn into_inner(self) -> I {
+    self.iter
+  }
+}
+
+impl<I, F> Iterator for HintInspect<I, F>
+where
+  I: Iterator,
+  F: FnMut(usize, Option<usize>),
+{
+  type Item = I::Item;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next()
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let (lower, upper) = self.iter.size_hint();
+    (self.f.borrow_mut())(lower, upper);
+    (lower, upper)
+  }
+}
+
+impl<I, F> DoubleEndedIterator for HintInspect<I, F>
+where
+  I: DoubleEndedIterator,
+  F: FnMut(usize, Option<usize>),
+{
+  #[inline]
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.iter.next_back()
+  }
+}
+
+impl<I, F> ExactSizeIterator for HintInspect<I, F>
+where
+  I: ExactSizeIterator,
+  F: FnMut(usize, Option<usize>),
+{
+}
+
+impl<I, F> FusedIterator for HintInspect<I, F>
+where
+  I: FusedIterator,
+  F: FnMut(usize, Option<usize>),
+{
+}
+
+impl<I: core::fmt::Debug, F> core::fmt::Debug for HintInspect<I, F> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("HintInspect")
+      .field("iter", &self.iter)
+      .finish_non_exhaustive()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use super::*;
+  use crate::CollectWith;
+
+  #[test]
+  fn records_hints_of_a_pipeline() {
+    let mut seen = Vec::new();
+    let v = (0..20)
+      .map(|x| x * 2)
+      .hint_inspect(|l, u| seen.push(("mapped", l, u)))
+      .filter(|x| x % 3 == 0)
+      .collect_with::<Vec<_>>(|n| n);
+
+    assert_eq!(v, [0, 6, 12, 18, 24, 30, 36]);
+    // Once at construction, once when `Filter` asked for the hint.
+    assert_eq!(seen, [("mapped", 20, Some(20)), ("mapped", 20, Some(20))]);
+  }
+
+  #[test]
+  fn does_not_alter_iteration() {
+    let plain = (0..50)
+      .filter(|x| x % 7 == 1)
+      .collect::<Vec<_>>();
+
+    let mut calls = 0;
+    let mut inspected = Vec::new();
+    for x in (0..50)
+      .filter(|x| x % 7 == 1)
+      .hint_inspect(|_, _| calls += 1)
+      .rev()
+    {
+      inspected.push(x);
+    }
+
+    assert_eq!(
+      inspected
+        .into_iter()
+        .rev()
+        .collect::<Vec<_>>(),
+      plain
+    );
+    // Only the call at construction: a plain loop never asks for the hint.
+    assert_eq!(calls, 1);
+  }
+}
@@ -0,0 +1,54 @@
+/// Length statistics of the fragments appended during a collection.
+///
+/// Returned by
+/// [collect_string_with_stats()](crate::CollectString::collect_string_with_stats)
+/// and
+/// [collect_bytes_with_stats()](crate::CollectVector::collect_bytes_with_stats),
+/// to tune byte-capacity closures without a second pass over the input. All
+/// fields are 0 when nothing was collected.
+///
+/// ## Example
+///
+/// ```
+/// use collect_with::LenStats;
+///
+/// let mut stats = LenStats::default();
+/// [3, 1, 8].into_iter().for_each(|len| stats.record(len));
+/// assert_eq!(
+///   stats,
+///   LenStats { count: 3, total: 12, min: 1, max: 8 }
+/// );
+/// assert_eq!(stats.mean(), Some(4.0));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LenStats {
+  /// Number of fragments.
+  pub count: usize,
+  /// Sum of all fragment lengths.
+  pub total: usize,
+  /// Shortest fragment length.
+  pub min: usize,
+  /// Longest fragment length.
+  pub max: usize,
+}
+
+impl LenStats {
+  /// Adds one fragment of `len` bytes.
+  pub fn record(&mut self, len: usize) {
+    self.min = match self.count {
+      0 => len,
+      _ => self.min.min(len),
+    };
+    self.max = self.max.max(len);
+    self.total = self.total.saturating_add(len);
+    self.count += 1;
+  }
+
+  /// Average fragment length, or `None` when nothing was recorded.
+  pub fn mean(&self) -> Option<f64> {
+    match self.count {
+      0 => None,
+      n => Some(self.total as f64 / n as f64),
+    }
+  }
+}
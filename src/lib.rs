@@ -1,5 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(__unstable_doc, feature(doc_auto_cfg, doc_notable_trait))]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 /*!
 # collect_with
 
@@ -19,6 +20,9 @@ Provides traits for collecting iterators into collections with:
 
 - `std`:
   - Enables standard library integrations
+  - Enables `CollectHash` trait for std `HashMap` & `HashSet` collections
+  - Enables `CollectHashContainer` for collecting into any `HashContainer`
+    backend (`StdHashContainer`, `AHashContainer`, `IndexContainer`)
   - When disabled, uses `alloc` crate for **no_std** environments
 
 ### Collection Specialization
@@ -26,12 +30,106 @@ Provides traits for collecting iterators into collections with:
 - `collect_vec`:
   - Enables `CollectVector` trait for enhanced `Vec` collection
   - Provides `collect_vec_with()` and `collect_vec_with_exact()`
+  - Enables `CollectDeque` trait, the same methods for `VecDeque`
+  - Enables `CollectBitSet` trait, collecting indices into a bit-packed
+    `BitSetVec`
+  - Provides `collect_lines_vec_with()` and `collect_split_vec_with()` for
+    splitting a `&str` with an exact line/piece count
+  - Provides `collect_merged_sorted_with()` for k-way merging sorted sources
+  - Provides `collect_boxed_slice_with()` for collecting into `Box<[T]>`
+  - Provides `ReusableCollector` for re-collecting into the same `Vec`
+    across loop iterations
+- `smallvec`:
+  - Enables `CollectSmallVec` trait for `SmallVec` collection
+  - Provides `collect_smallvec_with()` and `collect_smallvec_with_exact()`
+- `tinyvec`:
+  - Enables `CollectTinyVec` trait for `TinyVec` collection
+  - Provides `collect_tinyvec_with()` and `collect_tinyvec_with_exact()`
+- `arrayvec`:
+  - Enables `CollectArrayVec` trait for overflow-aware `ArrayVec` collection
+  - Provides `collect_arrayvec_with()`
+- `heapless`:
+  - Enables `CollectHeapless` trait for allocation-free `heapless::Vec`
+    collection
+  - Provides `collect_heapless_with()`
+- `camino`:
+  - Adds `collect_utf8_pathbuf_with()` to `CollectPath` for
+    `camino::Utf8PathBuf` collection
+- `im`:
+  - Enables `CollectIm` trait for persistent `im::Vector` & `im::HashMap`
+    collection
+  - Provides `collect_im_vector_with()` and `collect_im_map_with()`
+- `slab`:
+  - Enables `CollectSlab` trait for `slab::Slab` collection
+  - Provides `collect_slab_with()` and `collect_slab_keys_with()`
+- `bitvec`:
+  - Enables `CollectBitVec` trait for `bitvec::vec::BitVec` collection
+  - Provides `collect_bitvec_with()`, with the capacity in bits
+- `dashmap`:
+  - Enables `CollectDash` trait for concurrent `DashMap` collection
+  - Provides `collect_dashmap_with()` and `collect_dashmap_with_exact()`
+- `compact_str`:
+  - Implements `ExtendWithCapacity` for `CompactString`
+  - Provides `collect_compact_string_with()` (with `collect_string`)
+- `vec1`:
+  - Enables `CollectVec1` trait for non-empty `Vec1` collection
+  - Provides `collect_vec1_with()` and `try_collect_vec1_with()`
+- `slotmap`:
+  - Enables `CollectSlotMap` trait for `SlotMap`, `DenseSlotMap` and
+    `HopSlotMap` collection
+  - Provides `collect_slotmap_with()` and `collect_slotmap_keys_with()`
+- `bytes`:
+  - Enables `CollectBytes` trait for `BytesMut` & `Bytes` collection
+  - Provides `collect_bytes_mut_with()` and `collect_bytes_with()`
+- `collect_string`:
+  - Enables `CollectString` trait for enhanced `String` collection
+  - Provides `collect_string_with()`, `collect_string_with_exact()` and
+    `collect_string_hinted()` (`try_collect_string_with()` with `try`)
+- `itoa` / `ryu`:
+  - Enables `CollectNumeric` trait for joining integers (`itoa`) and floats
+    (`ryu`) into a `String` without `core::fmt`
+  - Provides `collect_numeric_string_with()` and
+    `collect_numeric_string_exact()`
 - `ahash`:
   - Enables `CollectAHash` trait for AHash-powered hash collections
   - Provides `collect_ahashmap_with()` and `collect_ahashset_with()`
+  - With `indexmap`, also provides `collect_indexmap_ahash_with()` and
+    `collect_indexset_ahash_with()`
+- `foldhash`:
+  - Enables `CollectFold` trait for std hash collections using foldhash
+  - Provides `collect_foldmap_with()` and `collect_foldset_with()`
+- `rustc-hash`:
+  - Enables `CollectFx` trait for `FxHashMap` & `FxHashSet` collections
+  - Provides `collect_fxhashmap_with()` and `collect_fxhashset_with()`
+- `fnv`:
+  - Enables `CollectFnv` trait for `FnvHashMap` & `FnvHashSet` collections
+  - Provides `collect_fnvmap_with()` and `collect_fnvset_with()`
+- `hashbrown`:
+  - Enables `CollectHashbrown` trait for hashbrown collections, also in
+    **no_std** environments
+  - Provides `collect_hashbrown_map_with()` and `collect_hashbrown_set_with()`
 - `indexmap`:
   - Enables `CollectIndex` trait for `IndexMap` & `IndexSet` collections
   - Provides `collect_indexmap_with()` and `collect_indexset_with()`
+- `either`:
+  - Enables `CollectEither` trait for splitting an iterator into two
+    collections
+  - Provides `collect_partition_map_with()`
+- `bytemuck`:
+  - Enables `CollectCast` trait for collecting `Pod` items and reinterpreting
+    them as another `Pod` type
+  - Provides `collect_cast_vec_with()`
+- `rayon`:
+  - With `indexmap`, enables `ParCollectIndex` for collecting parallel
+    iterators into `IndexMap` & `IndexSet` in sequential order
+  - Provides `par_collect_indexmap_with()` and `par_collect_indexset_with()`
+- `tokio`:
+  - Provides `collect_joinset_with()` and `try_collect_joinset_with()` for
+    awaiting a `JoinSet` into a pre-sized container
+- `wasm`:
+  - Enables `CollectJsArray` trait for building a preallocated
+    `js_sys::Array`
+  - Provides `collect_js_array_with()` and `try_collect_js_array_with()`
 
 ### Fallible Collection
 
@@ -40,6 +138,16 @@ Provides traits for collecting iterators into collections with:
     converting fallible types like `Option<T>` to `Result<T, ()>`.
   - `TryCollectWith` trait for error-propagating collection
 
+### Testing
+
+- `test-util`:
+  - Exports `HintedIter`, an iterator wrapper with a controllable size hint,
+    and `HintBehavior`, which picks how a lying hint changes
+  - Exports `FusedProbe`, which records how many times `next()` was called
+- `allocator_api` (nightly only, not included in `all`):
+  - Enables `ExtendWithCapacityIn` for containers with a custom allocator
+  - Provides `collect_vec_in_with()`
+
 ## Examples
 
 ### Basic usage with collection
@@ -128,40 +236,295 @@ If you need an exact capacity size, please use the `.collect_with_exact()` or `.
 
 - `ExtendWithCapacity`: Base trait for capacity-aware collections
 - `CollectWith`/`CollectWithCapacity`: Primary collection traits
+- `CollectWithHasher`/`ExtendWithCapacityAndHasher`: Collect into hashed
+  collections built from a given hasher instance
+- `Recollect`: Convert between collection types with capacity carried over
+- `FromIteratorWithCapacity`: Container-side constructor, the dual of
+  `CollectWith` (`TryFromIteratorWithCapacity` with feature = "try")
+- `InspectHint`: Observe the size hints flowing through an iterator pipeline
+- `PreserveHint`: Keep a known size hint through `map`/`filter`/`enumerate`
+- `HintCache`: Reuse one size bound across collections of cloned sources
+- `CollectError`: Error type shared by validating collectors
+- `NoneError`: `Error` type for `None` items, used by
+  `try_collect_with_none_err()`
+- `ShortInput`: Error for `collect_exact_take()` when the input is too short
+- `ErrorSummary`: Bounded error report of
+  `try_collect_with_error_summary()`
+- `ExactViolated`: Error for `collect_vec_with_exact_checked()` when the
+  requested capacity was too small
+- `TrailingBytes`: Error for `try_collect_packed_with()` when the input ends
+  in a partial record
+- `PollError`: Error of `Poll<Result<T, E>>` items, telling `Pending` apart
+  from `Ready(Err(_))`
+- `CollectBTree`: B-tree collection methods, with bulk builds from sorted
+  input
+- `CollectShared`: Collect into `Arc<[T]>`, `Rc<[T]>` and `Arc<str>`
 
 ### Optional Components
 
 - `CollectVector` (feature = "collect_vec"): Specialized Vec collection methods
+- `CollectDeque` (feature = "collect_vec"): VecDeque collection methods
+- `CollectBitSet` (feature = "collect_vec"): Collect `usize` indices into a
+  `Vec<u64>`-backed `BitSetVec`
+- `CollectSmallVec` (feature = "smallvec"): SmallVec collection methods
+- `CollectTinyVec` (feature = "tinyvec"): TinyVec collection methods
+- `CollectArrayVec` (feature = "arrayvec"): Fallible ArrayVec collection
+- `CollectHeapless` (feature = "heapless"): Fallible heapless::Vec collection
+- `CollectOsString` (feature = "std"): OsString concatenation sized in bytes
+- `CollectPath` (feature = "std"): PathBuf collection methods sized in bytes
+  (Utf8PathBuf with feature = "camino")
+- `CollectIm` (feature = "im"): Persistent im collection methods
+- `CollectSlab` (feature = "slab"): Slab collection methods, optionally
+  returning the assigned keys
+- `CollectBitVec` (feature = "bitvec"): BitVec collection methods
+- `CollectDash` (feature = "dashmap"): DashMap collection methods
+- `CollectVec1` (feature = "vec1"): Fallible non-empty Vec1 collection
+- `CollectSlotMap` (feature = "slotmap"): Slot map collection methods,
+  optionally returning the generated keys
+- `CollectBytes` (feature = "bytes"): BytesMut/Bytes collection methods
+- `CollectString` (feature = "collect_string"): Specialized String collection
+  methods
+- `LenStats` (feature = "collect_vec" or "collect_string"): Fragment length
+  statistics gathered while collecting
+- `CollectNumeric` (feature = "itoa" or "ryu"): Fast numeric string joining
+- `CollectHash` (feature = "std"): std HashMap/HashSet collection support
+- `CollectHashContainer`/`HashContainer` (feature = "std"): Backend-generic
+  map/set collection
 - `CollectAHash` (feature = "ahash"): AHash-based collection support
+- `CollectFold` (feature = "foldhash"): foldhash-based collection support
+- `CollectFx` (feature = "rustc-hash"): FxHashMap/FxHashSet collection support
+- `CollectFnv` (feature = "fnv"): FnvHashMap/FnvHashSet collection support
+- `CollectHashbrown` (feature = "hashbrown"): hashbrown collection support
 - `CollectIndex` (feature = "indexmap"): IndexMap/IndexSet collection support
+- `CollectEither` (feature = "either"): Either-based partitioned collection
+- `CollectCast` (feature = "bytemuck"): Collect-then-cast `Vec` support
+- `CollectJsArray` (feature = "wasm"): `js_sys::Array` collection
+- `ParCollectIndex` (features = "rayon" + "indexmap"): Order-preserving
+  parallel IndexMap/IndexSet collection
 - `TryExtract`/`TryCollectWith` (feature = "try")
 */
 
 extern crate alloc;
 
 mod extend;
-pub use extend::ExtendWithCapacity;
+#[cfg(feature = "allocator_api")]
+pub use extend::ExtendWithCapacityIn;
+pub use extend::{ExtendWithCapacity, ExtendWithCapacityAndHasher};
+
+mod error;
+pub use error::{
+  CollectError, ErrorSummary, ExactViolated, NoneError, PollError, ShortInput,
+  TrailingBytes,
+};
 
 // ---------
 mod collect;
-pub use collect::{CollectWith, CollectWithCapacity};
+pub use collect::{CollectWith, CollectWithCapacity, CollectWithHasher};
+
+mod recollect;
+pub use recollect::Recollect;
+
+mod from_iter;
+pub use from_iter::FromIteratorWithCapacity;
+#[cfg(feature = "try")]
+pub use from_iter::TryFromIteratorWithCapacity;
+
+mod hint_inspect;
+pub use hint_inspect::{HintInspect, InspectHint};
+
+mod preserve_hint;
+pub use preserve_hint::{HintPreserving, PreserveHint};
+
+mod hint_cache;
+pub use hint_cache::HintCache;
+
+mod collect_btree;
+pub use collect_btree::CollectBTree;
+
+mod collect_shared;
+pub use collect_shared::CollectShared;
 
 // ---------
 
+#[cfg(any(feature = "collect_vec", feature = "collect_string"))]
+mod len_stats;
+#[cfg(any(feature = "collect_vec", feature = "collect_string"))]
+pub use len_stats::LenStats;
+
 #[cfg(feature = "collect_vec")]
 mod collect_vec;
 #[cfg(feature = "collect_vec")]
 pub use collect_vec::CollectVector;
 
+#[cfg(feature = "collect_vec")]
+mod collect_deque;
+#[cfg(feature = "collect_vec")]
+pub use collect_deque::CollectDeque;
+
+#[cfg(feature = "collect_vec")]
+mod collect_bitset;
+#[cfg(feature = "collect_vec")]
+pub use collect_bitset::{BitSetVec, CollectBitSet};
+
+#[cfg(feature = "collect_vec")]
+mod reusable;
+#[cfg(feature = "collect_vec")]
+pub use reusable::ReusableCollector;
+
+#[cfg(feature = "collect_vec")]
+mod collect_lines;
+#[cfg(feature = "collect_vec")]
+pub use collect_lines::{collect_lines_vec_with, collect_split_vec_with};
+
+#[cfg(feature = "collect_vec")]
+mod collect_merged;
+#[cfg(feature = "collect_vec")]
+pub use collect_merged::collect_merged_sorted_with;
+
+#[cfg(feature = "smallvec")]
+mod collect_smallvec;
+#[cfg(feature = "smallvec")]
+pub use collect_smallvec::CollectSmallVec;
+
+#[cfg(feature = "tinyvec")]
+mod collect_tinyvec;
+#[cfg(feature = "tinyvec")]
+pub use collect_tinyvec::CollectTinyVec;
+
+#[cfg(feature = "arrayvec")]
+mod collect_arrayvec;
+#[cfg(feature = "arrayvec")]
+pub use collect_arrayvec::{CapacityOverflow, CollectArrayVec};
+
+#[cfg(feature = "heapless")]
+mod collect_heapless;
+#[cfg(feature = "heapless")]
+pub use collect_heapless::{CollectHeapless, ExceededCapacity};
+
+#[cfg(feature = "std")]
+mod collect_osstring;
+#[cfg(feature = "std")]
+pub use collect_osstring::CollectOsString;
+
+#[cfg(feature = "std")]
+mod collect_path;
+#[cfg(feature = "std")]
+pub use collect_path::CollectPath;
+
+#[cfg(feature = "im")]
+mod collect_im;
+#[cfg(feature = "im")]
+pub use collect_im::CollectIm;
+
+#[cfg(feature = "slab")]
+mod collect_slab;
+#[cfg(feature = "slab")]
+pub use collect_slab::CollectSlab;
+
+#[cfg(feature = "bitvec")]
+mod collect_bitvec;
+#[cfg(feature = "bitvec")]
+pub use collect_bitvec::CollectBitVec;
+
+#[cfg(feature = "dashmap")]
+mod collect_dashmap;
+#[cfg(feature = "dashmap")]
+pub use collect_dashmap::CollectDash;
+
+#[cfg(feature = "vec1")]
+mod collect_vec1;
+#[cfg(feature = "vec1")]
+pub use collect_vec1::{CollectVec1, CollectVec1Error};
+
+#[cfg(feature = "slotmap")]
+mod collect_slotmap;
+#[cfg(feature = "slotmap")]
+pub use collect_slotmap::CollectSlotMap;
+
+#[cfg(feature = "bytes")]
+mod collect_bytes;
+#[cfg(feature = "bytes")]
+pub use collect_bytes::CollectBytes;
+
+#[cfg(feature = "collect_string")]
+mod collect_string;
+#[cfg(feature = "collect_string")]
+pub use collect_string::CollectString;
+
+#[cfg(any(feature = "itoa", feature = "ryu"))]
+mod collect_numeric;
+#[cfg(any(feature = "itoa", feature = "ryu"))]
+pub use collect_numeric::{CollectNumeric, FastNumber};
+
+#[cfg(feature = "std")]
+mod collect_hash;
+#[cfg(feature = "std")]
+pub use collect_hash::CollectHash;
+
+#[cfg(feature = "std")]
+mod collect_hash_container;
+#[cfg(feature = "ahash")]
+pub use collect_hash_container::AHashContainer;
+#[cfg(feature = "indexmap")]
+pub use collect_hash_container::IndexContainer;
+#[cfg(feature = "std")]
+pub use collect_hash_container::{
+  CollectHashContainer, HashContainer, StdHashContainer,
+};
+
 #[cfg(feature = "ahash")]
 mod collect_ahash;
 #[cfg(feature = "ahash")]
 pub use collect_ahash::CollectAHash;
 
+#[cfg(feature = "foldhash")]
+mod collect_foldhash;
+#[cfg(feature = "foldhash")]
+pub use collect_foldhash::CollectFold;
+
+#[cfg(feature = "rustc-hash")]
+mod collect_fxhash;
+#[cfg(feature = "rustc-hash")]
+pub use collect_fxhash::CollectFx;
+
+#[cfg(feature = "fnv")]
+mod collect_fnv;
+#[cfg(feature = "fnv")]
+pub use collect_fnv::CollectFnv;
+
+#[cfg(feature = "hashbrown")]
+mod collect_hashbrown;
+#[cfg(feature = "hashbrown")]
+pub use collect_hashbrown::CollectHashbrown;
+
 #[cfg(feature = "indexmap")]
 mod collect_index;
 #[cfg(feature = "indexmap")]
 pub use collect_index::CollectIndex;
+#[cfg(feature = "either")]
+mod collect_either;
+#[cfg(feature = "either")]
+pub use collect_either::CollectEither;
+
+#[cfg(all(feature = "rayon", feature = "indexmap"))]
+mod par_collect_index;
+#[cfg(all(feature = "rayon", feature = "indexmap"))]
+pub use par_collect_index::ParCollectIndex;
+
+#[cfg(feature = "tokio")]
+mod collect_joinset;
+#[cfg(feature = "tokio")]
+pub use collect_joinset::{collect_joinset_with, try_collect_joinset_with};
+
+#[cfg(feature = "bytemuck")]
+mod collect_cast;
+#[cfg(feature = "bytemuck")]
+pub use collect_cast::{CastMode, CollectCast};
+
+#[cfg(feature = "wasm")]
+mod collect_js;
+#[cfg(feature = "wasm")]
+pub use collect_js::CollectJsArray;
 // ---------
 #[cfg(feature = "try")]
 mod try_extract;
@@ -173,5 +536,13 @@ mod try_collect;
 #[cfg(feature = "try")]
 pub use try_collect::TryCollectWith;
 
+// ---------
+#[cfg(any(test, feature = "test-util"))]
+mod test_util;
+#[cfg(any(test, feature = "test-util"))]
+pub use test_util::{FusedProbe, HintBehavior, HintedIter};
+
 // ---------
 mod common;
+#[cfg(feature = "std")]
+mod entry;
@@ -0,0 +1,143 @@
+use alloc::{collections::LinkedList, vec::Vec};
+use core::hash::Hash;
+
+use indexmap::{IndexMap, IndexSet};
+use rayon::iter::ParallelIterator;
+
+use crate::common::collect_with_exact_capacity;
+
+impl<I: ParallelIterator> ParCollectIndex for I {}
+
+/// Trait for collecting parallel iterators into `IndexMap` or `IndexSet` while
+/// keeping the sequential insertion order.
+///
+/// Each rayon job folds its items into a `Vec`, the chunks are joined in their
+/// original order, and the result is inserted into a pre-sized container.
+/// For indexed parallel iterators (e.g. `par_iter()` on a slice or a range),
+/// the final order is identical to the sequential iterator.
+pub trait ParCollectIndex: ParallelIterator {
+  /// Collects pairs into an `IndexMap` in sequential order.
+  ///
+  /// The size bound comes from
+  /// [opt_len()](rayon::iter::ParallelIterator::opt_len), or 0 when the
+  /// length is unknown. The final capacity is
+  /// `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::ParCollectIndex;
+  /// use rayon::prelude::*;
+  ///
+  /// let map = (0..100u32)
+  ///   .into_par_iter()
+  ///   .map(|n| (n, n * n))
+  ///   .par_collect_indexmap_with(|n| n + 10);
+  /// assert_eq!(map.get_index(99), Some((&99, &9801)));
+  /// assert!(map.capacity() >= 110);
+  /// ```
+  fn par_collect_indexmap_with<K, V>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> IndexMap<K, V>
+  where
+    Self: ParallelIterator<Item = (K, V)>,
+    K: Hash + Eq + Send,
+    V: Send,
+  {
+    collect_ordered(self, capacity)
+  }
+
+  /// Collects items into an `IndexSet` in sequential order.
+  ///
+  /// Duplicates keep their first position, as with a sequential collection.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::ParCollectIndex;
+  /// use rayon::prelude::*;
+  ///
+  /// let set = [3, 1, 3, 2]
+  ///   .into_par_iter()
+  ///   .par_collect_indexset_with(|n| n);
+  /// assert!(set.iter().eq(&[3, 1, 2]));
+  /// ```
+  fn par_collect_indexset_with<K>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> IndexSet<K>
+  where
+    Self: ParallelIterator<Item = K>,
+    K: Hash + Eq + Send,
+  {
+    collect_ordered(self, capacity)
+  }
+}
+
+fn collect_ordered<T, I>(iter: I, capacity: impl FnOnce(usize) -> usize) -> T
+where
+  I: ParallelIterator,
+  T: crate::ExtendWithCapacity<I::Item>,
+{
+  let bound = iter.opt_len().unwrap_or(0);
+  let real_capacity = capacity(bound).max(bound);
+
+  let chunks = iter
+    .fold(Vec::new, |mut chunk, item| {
+      chunk.push(item);
+      chunk
+    })
+    .map(|chunk| LinkedList::from([chunk]))
+    .reduce(LinkedList::new, |mut left, mut right| {
+      left.append(&mut right);
+      left
+    });
+
+  collect_with_exact_capacity(chunks.into_iter().flatten(), real_capacity)
+}
+
+#[cfg(test)]
+mod tests {
+  use rayon::prelude::*;
+
+  use super::*;
+  use crate::CollectIndex;
+
+  #[test]
+  fn map_order_matches_sequential() {
+    let input = (0..10_000u32)
+      .map(|n| (n.wrapping_mul(2_654_435_761) % 5_000, n))
+      .collect::<Vec<_>>();
+
+    let par = input
+      .par_iter()
+      .copied()
+      .par_collect_indexmap_with(|n| n);
+    let seq = input
+      .iter()
+      .copied()
+      .collect_indexmap_with(|n| n);
+
+    assert_eq!(par.len(), seq.len());
+    assert!(par.iter().eq(seq.iter()));
+    assert!(par.capacity() >= input.len());
+  }
+
+  #[test]
+  fn set_order_matches_sequential() {
+    let input = (0..5_000u32)
+      .map(|n| (n % 97) ^ (n % 13))
+      .collect::<Vec<_>>();
+
+    let par = input
+      .par_iter()
+      .copied()
+      .par_collect_indexset_with(|n| n);
+    let seq = input
+      .iter()
+      .copied()
+      .collect_indexset_with(|n| n);
+    assert!(par.iter().eq(seq.iter()));
+  }
+}
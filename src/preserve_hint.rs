@@ -0,0 +1,149 @@
+use core::iter::{Enumerate, Filter, FusedIterator, Map};
+
+impl<I: Iterator> PreserveHint for I {}
+
+/// Trait for keeping a known size hint alive through later adapters.
+pub trait PreserveHint: Iterator {
+  /// Snapshot the current [size_hint()](Iterator::size_hint) and keep
+  /// reporting it through `map`, `filter` and `enumerate` applied to the
+  /// returned wrapper.
+  ///
+  /// The snapshot shrinks as items are yielded. `filter` clamps the lower
+  /// bound to 0, so the reported hint is never too high; the upper bound is
+  /// kept, which is what the capacity closures receive as size bound.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::{CollectWith, PreserveHint};
+  ///
+  /// let v = (0..100)
+  ///   .preserve_hint()
+  ///   .filter(|n| n % 2 == 0)
+  ///   .map(|n| n * 10)
+  ///   .collect_with::<Vec<_>>(|n| n);
+  /// assert_eq!(v.len(), 50);
+  /// assert_eq!(v.capacity(), 100);
+  /// ```
+  fn preserve_hint(self) -> HintPreserving<Self>
+  where
+    Self: Sized,
+  {
+    let (lower, upper) = self.size_hint();
+    HintPreserving {
+      iter: self,
+      lower,
+      upper,
+    }
+  }
+}
+
+/// Iterator returned by [preserve_hint()](PreserveHint::preserve_hint).
+///
+/// Its `map`, `filter` and `enumerate` methods shadow those of [`Iterator`]
+/// and return another `HintPreserving` carrying the same snapshot.
+#[derive(Debug, Clone)]
+pub struct HintPreserving<I> {
+  iter: I,
+  lower: usize,
+  upper: Option<usize>,
+}
+
+impl<I: Iterator> HintPreserving<I> {
+  /// Like [`Iterator::map()`], keeping the snapshot as is.
+  pub fn map<B, F>(self, f: F) -> HintPreserving<Map<I, F>>
+  where
+    F: FnMut(I::Item) -> B,
+  {
+    HintPreserving {
+      iter: self.iter.map(f),
+      lower: self.lower,
+      upper: self.upper,
+    }
+  }
+
+  /// Like [`Iterator::filter()`], clamping the lower bound to 0.
+  pub fn filter<P>(self, predicate: P) -> HintPreserving<Filter<I, P>>
+  where
+    P: FnMut(&I::Item) -> bool,
+  {
+    HintPreserving {
+      iter: self.iter.filter(predicate),
+      lower: 0,
+      upper: self.upper,
+    }
+  }
+
+  /// Like [`Iterator::enumerate()`], keeping the snapshot as is.
+  pub fn enumerate(self) -> HintPreserving<Enumerate<I>> {
+    HintPreserving {
+      iter: self.iter.enumerate(),
+      lower: self.lower,
+      upper: self.upper,
+    }
+  }
+
+  /// Returns the inner iterator.
+  pub fn into_inner(self) -> I {
+    self.iter
+  }
+}
+
+impl<I: Iterator> Iterator for HintPreserving<I> {
+  type Item = I::Item;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let item = self.iter.next()?;
+    self.lower = self.lower.saturating_sub(1);
+    self.upper = self
+      .upper
+      .map(|u| u.saturating_sub(1));
+    Some(item)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.lower, self.upper)
+  }
+}
+
+impl<I: FusedIterator> FusedIterator for HintPreserving<I> {}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use super::*;
+  use crate::{CollectWith, HintedIter};
+
+  #[test]
+  fn filter_map_chain_keeps_upper_bound() {
+    let src = HintedIter::exact(0..20u32, 20);
+    let v = src
+      .preserve_hint()
+      .filter(|n| n % 3 == 0)
+      .map(|n| n + 1)
+      .enumerate()
+      .collect_with::<Vec<_>>(|n| n);
+    assert_eq!(
+      v,
+      [(0, 1), (1, 4), (2, 7), (3, 10), (4, 13), (5, 16), (6, 19)]
+    );
+    assert_eq!(v.capacity(), 20);
+  }
+
+  #[test]
+  fn hint_shrinks_and_lower_is_clamped() {
+    let mut it = (0..4)
+      .preserve_hint()
+      .map(|n| n * 2);
+    assert_eq!(it.size_hint(), (4, Some(4)));
+    assert_eq!(it.next(), Some(0));
+    assert_eq!(it.size_hint(), (3, Some(3)));
+
+    let mut it = it.filter(|n| *n > 2);
+    assert_eq!(it.size_hint(), (0, Some(3)));
+    assert_eq!(it.next(), Some(4));
+    assert_eq!(it.size_hint(), (0, Some(2)));
+    assert_eq!(it.collect::<Vec<_>>(), [6]);
+  }
+}
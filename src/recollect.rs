@@ -0,0 +1,107 @@
+use crate::{ExtendWithCapacity, common::collect_iterator};
+
+/// Blanket implementation for every `IntoIterator` type.
+impl<C: IntoIterator> Recollect for C {}
+
+/// Trait for converting one collection into another while carrying the known
+/// length over as capacity.
+///
+/// `vec.into_iter().collect::<HashSet<_>>()` is fine when the source iterator
+/// reports an accurate size hint, but the intent ("convert this collection")
+/// gets lost in the adapter chain. `Recollect` makes it explicit and lets the
+/// exact variant use [`len()`](ExactSizeIterator::len) directly.
+pub trait Recollect: IntoIterator {
+  /// Convert into another collection with a capacity calculated from the
+  /// source's size hint.
+  ///
+  /// The capacity closure follows the same rules as
+  /// [collect_with()](crate::CollectWith::collect_with): the final capacity is
+  /// `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::Recollect;
+  ///
+  /// let chars = "hello".chars().recollect_with::<Vec<_>>(|n| n + 3);
+  /// assert_eq!(chars, ['h', 'e', 'l', 'l', 'o']);
+  /// assert!(chars.capacity() >= 5);
+  /// ```
+  fn recollect_with<T>(self, capacity: impl FnOnce(usize) -> usize) -> T
+  where
+    T: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+  {
+    collect_iterator(self.into_iter(), false, capacity)
+  }
+
+  /// Convert into another collection, using the source's exact length as the
+  /// capacity.
+  ///
+  /// Only available when the source's `IntoIter` implements
+  /// [`ExactSizeIterator`].
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use std::collections::VecDeque;
+  ///
+  /// use collect_with::Recollect;
+  ///
+  /// let deque = vec![1, 2, 3].recollect_exact::<VecDeque<_>>();
+  /// assert_eq!(deque, [1, 2, 3]);
+  /// assert_eq!(deque.capacity(), 3);
+  /// ```
+  fn recollect_exact<T>(self) -> T
+  where
+    T: ExtendWithCapacity<Self::Item>,
+    Self: Sized,
+    Self::IntoIter: ExactSizeIterator,
+  {
+    let iter = self.into_iter();
+    let len = iter.len();
+    collect_iterator(iter, true, |_| len)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use alloc::vec::Vec;
+
+  use super::*;
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn vec_into_hashset() {
+    use std::collections::HashSet;
+
+    let set = alloc::vec![3, 1, 4, 1, 5].recollect_exact::<HashSet<_>>();
+    assert_eq!(set.len(), 4);
+    assert!(set.capacity() >= 5);
+    assert!(set.contains(&4));
+  }
+
+  #[cfg(feature = "indexmap")]
+  #[test]
+  fn indexmap_into_pairs() {
+    use indexmap::IndexMap;
+
+    let map = [('a', 1), ('b', 2), ('c', 3)]
+      .into_iter()
+      .collect::<IndexMap<_, _>>();
+    let pairs = map.recollect_exact::<Vec<_>>();
+    assert_eq!(pairs, [('a', 1), ('b', 2), ('c', 3)]);
+    assert_eq!(pairs.capacity(), 3);
+  }
+
+  #[test]
+  fn chars_into_vec_fallback() {
+    // `Chars` is not an ExactSizeIterator, so only the hinted form applies.
+    let s = alloc::string::String::from("abcd");
+    let chars = s
+      .chars()
+      .recollect_with::<Vec<char>>(|n| n.max(4));
+    assert_eq!(chars, ['a', 'b', 'c', 'd']);
+    assert!(chars.capacity() >= 4);
+  }
+}
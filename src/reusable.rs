@@ -0,0 +1,115 @@
+use alloc::vec::Vec;
+use core::mem;
+
+use crate::common::get_max_hint_bound;
+
+/// A `Vec` buffer that is re-collected into again and again.
+///
+/// Each [collect_from()](ReusableCollector::collect_from) clears the buffer
+/// and only reserves when the new batch needs more than the current
+/// capacity, so a loop whose batches stay the same size or shrink never
+/// reallocates after the first round.
+///
+/// ## Example
+///
+/// ```
+/// use collect_with::ReusableCollector;
+///
+/// let mut buf = ReusableCollector::new();
+/// for tick in 0..3 {
+///   let frame = buf.collect_from((0..10 - tick).map(|n| n * 2), |n| n);
+///   assert_eq!(frame.len(), 10 - tick);
+/// }
+/// assert_eq!(buf.take().capacity(), 10);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ReusableCollector<T> {
+  buf: Vec<T>,
+}
+
+impl<T> ReusableCollector<T> {
+  /// Creates an empty collector without allocating.
+  pub const fn new() -> Self {
+    Self { buf: Vec::new() }
+  }
+
+  /// Creates a collector whose buffer can hold `capacity` items up front.
+  pub fn with_capacity(capacity: usize) -> Self {
+    Self {
+      buf: Vec::with_capacity(capacity),
+    }
+  }
+
+  /// Clears the buffer and collects `iter` into it.
+  ///
+  /// The buffer is grown to at least `max(size_bound, capacity(size_bound))`
+  /// if it is smaller; an existing, larger allocation is kept as is.
+  pub fn collect_from<I>(
+    &mut self,
+    iter: I,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> &mut Vec<T>
+  where
+    I: IntoIterator<Item = T>,
+  {
+    let iter = iter.into_iter();
+    let bound = get_max_hint_bound(iter.size_hint());
+
+    self.buf.clear();
+    self
+      .buf
+      .reserve(capacity(bound).max(bound));
+    self.buf.extend(iter);
+    &mut self.buf
+  }
+
+  /// Returns the items of the last collection.
+  pub fn as_slice(&self) -> &[T] {
+    &self.buf
+  }
+
+  /// Takes the buffer out, leaving an empty, unallocated one behind.
+  pub fn take(&mut self) -> Vec<T> {
+    mem::take(&mut self.buf)
+  }
+}
+
+impl<T> From<Vec<T>> for ReusableCollector<T> {
+  /// Reuses the allocation of `buf`; its items are dropped on the next
+  /// collection.
+  fn from(buf: Vec<T>) -> Self {
+    Self { buf }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn shrinking_batches_keep_the_buffer() {
+    let mut collector = ReusableCollector::new();
+    let ptr = collector
+      .collect_from(0..100u32, |n| n)
+      .as_ptr();
+
+    for len in [60, 5] {
+      let v = collector.collect_from(0..len, |n| n);
+      assert_eq!(v.len() as u32, len);
+      assert_eq!(v.as_ptr(), ptr);
+    }
+    assert_eq!(collector.as_slice(), [0, 1, 2, 3, 4]);
+
+    let v = collector.take();
+    assert_eq!(v.capacity(), 100);
+    assert!(collector.as_slice().is_empty());
+  }
+
+  #[test]
+  fn grows_when_batch_is_larger() {
+    let mut collector = ReusableCollector::from(Vec::with_capacity(2));
+    let v = collector.collect_from([1, 2, 3], |n| n + 1);
+    assert_eq!(*v, [1, 2, 3]);
+    assert!(v.capacity() >= 4);
+  }
+}
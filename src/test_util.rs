@@ -0,0 +1,200 @@
+//! Iterator wrappers for testing capacity closures.
+
+use core::iter::FusedIterator;
+
+/// How a [lying](HintedIter::lying) hint changes while items are yielded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintBehavior {
+  /// The reported bounds never change, like a stale cached length.
+  Frozen,
+  /// The reported bounds shrink by one per item, like a well-behaved
+  /// iterator that started from the wrong count.
+  Tracking,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Hint {
+  /// Bounds shrink as items are yielded, like a well-behaved iterator.
+  Tracking(usize, Option<usize>),
+  /// Bounds are reported unchanged, no matter what the iterator yields.
+  Frozen(usize, Option<usize>),
+}
+
+/// An iterator wrapper with a controllable [size_hint()](Iterator::size_hint).
+///
+/// Items are forwarded from the inner iterator untouched; only the reported
+/// hint is replaced.
+///
+/// ## Example
+///
+/// ```
+/// use collect_with::{CollectVector, HintedIter};
+///
+/// let v = HintedIter::bounds(0..3, 0, None).collect_vec_with(|n| n + 4);
+/// assert_eq!(v.capacity(), 4);
+///
+/// let v = HintedIter::exact(0..3, 3).collect_vec_with(|n| n);
+/// assert_eq!(v.capacity(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HintedIter<I> {
+  iter: I,
+  hint: Hint,
+}
+
+impl<I> HintedIter<I> {
+  /// Reports `(n, Some(n))`, decreasing as items are yielded.
+  pub fn exact(iter: I, n: usize) -> Self {
+    Self::bounds(iter, n, Some(n))
+  }
+
+  /// Reports `(lower, upper)`, decreasing as items are yielded.
+  pub fn bounds(iter: I, lower: usize, upper: Option<usize>) -> Self {
+    Self {
+      iter,
+      hint: Hint::Tracking(lower, upper),
+    }
+  }
+
+  /// Reports `reported`, regardless of how many items the inner iterator
+  /// actually yields.
+  ///
+  /// Use it to simulate iterators whose hint is wrong, e.g. `(100, Some(100))`
+  /// for an iterator that yields only 3 items. `actual_behavior` picks whether
+  /// the wrong hint stays fixed or counts down as items are yielded.
+  pub fn lying(
+    iter: I,
+    reported: (usize, Option<usize>),
+    actual_behavior: HintBehavior,
+  ) -> Self {
+    let (lower, upper) = reported;
+    let hint = match actual_behavior {
+      HintBehavior::Frozen => Hint::Frozen(lower, upper),
+      HintBehavior::Tracking => Hint::Tracking(lower, upper),
+    };
+    Self { iter, hint }
+  }
+
+  /// Returns the inner iterator.
+  pub fn into_inner(self) -> I {
+    self.iter
+  }
+}
+
+impl<I: Iterator> Iterator for HintedIter<I> {
+  type Item = I::Item;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let item = self.iter.next()?;
+    if let Hint::Tracking(lower, upper) = &mut self.hint {
+      *lower = lower.saturating_sub(1);
+      *upper = upper.map(|u| u.saturating_sub(1));
+    }
+    Some(item)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    match self.hint {
+      Hint::Tracking(lower, upper) | Hint::Frozen(lower, upper) => (lower, upper),
+    }
+  }
+}
+
+/// A fused iterator wrapper that records how many times
+/// [next()](Iterator::next) was called.
+///
+/// Pass it by `&mut` to keep access to the counter after collection.
+///
+/// ## Example
+///
+/// ```
+/// use collect_with::{CollectVector, FusedProbe};
+///
+/// let mut probe = FusedProbe::new(0..3);
+/// let v = (&mut probe).collect_vec_with(|n| n);
+/// assert_eq!(v, [0, 1, 2]);
+/// // Three items, plus the call that returned `None`.
+/// assert_eq!(probe.next_calls(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FusedProbe<I> {
+  iter: I,
+  calls: usize,
+  done: bool,
+}
+
+impl<I> FusedProbe<I> {
+  pub fn new(iter: I) -> Self {
+    Self {
+      iter,
+      calls: 0,
+      done: false,
+    }
+  }
+
+  /// Number of times `next()` has been called so far.
+  pub fn next_calls(&self) -> usize {
+    self.calls
+  }
+
+  /// Whether the inner iterator has returned `None`.
+  pub fn is_exhausted(&self) -> bool {
+    self.done
+  }
+}
+
+impl<I: Iterator> Iterator for FusedProbe<I> {
+  type Item = I::Item;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.calls += 1;
+    if self.done {
+      return None;
+    }
+    let item = self.iter.next();
+    self.done = item.is_none();
+    item
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    if self.done {
+      return (0, Some(0));
+    }
+    self.iter.size_hint()
+  }
+}
+
+impl<I: Iterator> FusedIterator for FusedProbe<I> {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn hinted_iter_reports_hints() {
+    let mut it = HintedIter::exact(0..2, 2);
+    assert_eq!(it.size_hint(), (2, Some(2)));
+    it.next();
+    assert_eq!(it.size_hint(), (1, Some(1)));
+
+    let mut it = HintedIter::lying(0..2, (10, None), HintBehavior::Frozen);
+    it.next();
+    assert_eq!(it.size_hint(), (10, None));
+    assert_eq!(it.count(), 1);
+
+    let mut it = HintedIter::lying(0..2, (10, Some(10)), HintBehavior::Tracking);
+    it.next();
+    assert_eq!(it.size_hint(), (9, Some(9)));
+    assert_eq!(it.count(), 1);
+  }
+
+  #[test]
+  fn fused_probe_counts_and_fuses() {
+    let mut probe = FusedProbe::new(core::iter::once(1));
+    assert_eq!(probe.next(), Some(1));
+    assert_eq!(probe.next(), None);
+    assert_eq!(probe.next(), None);
+    assert!(probe.is_exhausted());
+    assert_eq!(probe.next_calls(), 3);
+  }
+}
@@ -1,6 +1,12 @@
-use alloc::vec::Vec;
+use alloc::{
+  borrow::{Cow, ToOwned},
+  vec::Vec,
+};
 
-use crate::{ExtendWithCapacity, TryExtract, common::get_max_hint_bound};
+use crate::{
+  ErrorSummary, ExtendWithCapacity, TryExtract,
+  common::{bound_or, try_collect_iterator},
+};
 
 impl<I: Iterator> TryCollectWith for I {}
 
@@ -45,15 +51,73 @@ pub trait TryCollectWith: Iterator {
     Self: Sized,
     Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
   {
-    let bound = get_max_hint_bound(self.size_hint());
-    let mut container = T::with_capacity(capacity(bound).max(bound));
+    try_collect_iterator(self, false, capacity)
+  }
 
-    for item in self {
-      let value = item.try_extract()?;
-      container.extend(core::iter::once(value));
-    }
+  /// Attempt to collect iterator elements, using the size hint bound as the
+  /// exact capacity, or `fallback` when the bound is 0.
+  ///
+  /// See [collect_with_fallback()](crate::CollectWith::collect_with_fallback).
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let result = "1 2 3"
+  ///   .split(' ')
+  ///   .map(|x| x.parse::<u8>())
+  ///   .try_collect_with_fallback::<Vec<_>, _, _>(4);
+  ///
+  /// let v = result.unwrap();
+  /// assert_eq!(v, [1, 2, 3]);
+  /// assert_eq!(v.capacity(), 4);
+  /// ```
+  fn try_collect_with_fallback<'a, T, OK, ERR>(
+    self,
+    fallback: usize,
+  ) -> Result<T, ERR>
+  where
+    T: ExtendWithCapacity<OK>,
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    try_collect_iterator(self, true, bound_or(fallback))
+  }
 
-    Ok(container)
+  /// Attempt to collect borrowed `Copy` values into a collection of owned
+  /// values, short-circuiting on the first error.
+  ///
+  /// This is the fallible counterpart of
+  /// [collect_copied_with()](crate::CollectWith::collect_copied_with), for
+  /// items such as `&Option<T>` or `&Result<T, E>` whose success values are
+  /// references.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let items = [Some(1u32), Some(2)];
+  /// let nums: Option<Vec<u32>> = items
+  ///   .iter()
+  ///   .try_collect_copied_with(|n| n)
+  ///   .ok();
+  /// assert_eq!(nums, Some(vec![1, 2]));
+  /// ```
+  fn try_collect_copied_with<'a, T, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<T, ERR>
+  where
+    T: ExtendWithCapacity<OK>,
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = &'a OK, Err = ERR>,
+    OK: Copy + 'a,
+  {
+    self
+      .map(|item| item.try_extract().copied())
+      .try_collect_with(capacity)
   }
 
   /// Convenience method for collecting into `Result<Vec<OK>, Err>`
@@ -86,4 +150,1013 @@ pub trait TryCollectWith: Iterator {
   {
     self.try_collect_with(capacity)
   }
+
+  /// Attempt to collect elements into the owned variant of a [`Cow`].
+  ///
+  /// See [collect_cow_with()](crate::CollectWith::collect_cow_with).
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let s = [Some('o'), Some('k')]
+  ///   .into_iter()
+  ///   .try_collect_cow_with::<str, _, _>(|n| n);
+  /// assert_eq!(s.as_deref(), Ok("ok"));
+  /// ```
+  fn try_collect_cow_with<'a, 'b, B, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<Cow<'b, B>, ERR>
+  where
+    B: ToOwned + ?Sized,
+    B::Owned: ExtendWithCapacity<OK>,
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    try_collect_iterator(self, false, capacity).map(Cow::Owned)
+  }
+
+  /// Like [try_collect_with()](TryCollectWith::try_collect_with), also
+  /// returning how long the collection took, including a short-circuited one.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use std::time::Instant;
+  ///
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let start = Instant::now();
+  /// let (result, elapsed) = ["1", "x"]
+  ///   .into_iter()
+  ///   .map(str::parse::<u8>)
+  ///   .try_collect_with_timed::<Vec<_>, _, _>(|n| n);
+  /// assert!(result.is_err());
+  /// assert!(elapsed <= start.elapsed());
+  /// ```
+  #[cfg(feature = "std")]
+  fn try_collect_with_timed<'a, T, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (Result<T, ERR>, core::time::Duration)
+  where
+    T: ExtendWithCapacity<OK>,
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    let start = std::time::Instant::now();
+    let result = try_collect_iterator(self, false, capacity);
+    (result, start.elapsed())
+  }
+
+  /// Attempt to concatenate byte chunks into one `Vec<u8>`, short-circuiting
+  /// on the first error.
+  ///
+  /// Each `Ok` chunk is appended with [`Vec::extend_from_slice()`], so the
+  /// flattening and the error handling happen in a single pass. Chunks after
+  /// the first error are not read.
+  ///
+  /// - `capacity`
+  ///   - Closure that calculates the **byte** capacity from the **chunk** count
+  ///     bound of the size hint. If frames have a typical size, scale the bound
+  ///     by it, e.g. `|chunks| chunks * 1500` for MTU-sized frames.
+  ///
+  /// The final capacity is `max(size_bound, capacity(size_bound))`.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let frames: [Result<&[u8], &str>; 3] = [Ok(b"he"), Ok(b""), Ok(b"llo")];
+  /// let buf = frames
+  ///   .into_iter()
+  ///   .try_collect_flat_bytes_with(|n| n * 4)
+  ///   .unwrap();
+  /// assert_eq!(buf, b"hello");
+  /// assert_eq!(buf.capacity(), 12);
+  /// ```
+  #[cfg(feature = "collect_vec")]
+  fn try_collect_flat_bytes_with<'a, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<Vec<u8>, ERR>
+  where
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+    OK: AsRef<[u8]>,
+  {
+    let bound = crate::common::get_max_hint_bound(self.size_hint());
+    let mut buf = Vec::with_capacity(capacity(bound).max(bound));
+    for item in self {
+      buf.extend_from_slice(item.try_extract()?.as_ref());
+    }
+    Ok(buf)
+  }
+
+  /// Like [try_collect_with()](TryCollectWith::try_collect_with), also
+  /// returning how many items were pulled from the iterator.
+  ///
+  /// The count is reported on both paths: on success it is the number of
+  /// collected items, on error it includes the failing item. Passing the
+  /// iterator by `&mut` lets the caller resume right after that position.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let mut frames = [Ok(1), Ok(2), Err("eof"), Ok(4)].into_iter();
+  /// let (err, consumed) = (&mut frames)
+  ///   .try_collect_with_counted::<Vec<_>, _, _>(|n| n)
+  ///   .unwrap_err();
+  /// assert_eq!((err, consumed), ("eof", 3));
+  /// assert_eq!(frames.next(), Some(Ok(4)));
+  /// ```
+  fn try_collect_with_counted<'a, T, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<(T, usize), (ERR, usize)>
+  where
+    T: ExtendWithCapacity<OK>,
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    let mut consumed = 0;
+    let result =
+      try_collect_iterator(self.inspect(|_| consumed += 1), false, capacity);
+    match result {
+      Ok(container) => Ok((container, consumed)),
+      Err(e) => Err((e, consumed)),
+    }
+  }
+
+  /// Collects the `Ok` values gathered before the first error, returning the
+  /// error as the stop reason instead of discarding the collected work.
+  ///
+  /// The result is never wrapped in a `Result`: the container always holds
+  /// every value extracted before the stop, and the `Option` is `None` when
+  /// the iterator ran to completion. Items after the first error are not
+  /// read.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let (v, stop) = ["1", "2", "x", "4"]
+  ///   .into_iter()
+  ///   .map(str::parse::<u8>)
+  ///   .collect_until_err_with::<Vec<_>, _, _>(|n| n);
+  /// assert_eq!(v, [1, 2]);
+  /// assert!(stop.is_some());
+  /// ```
+  fn collect_until_err_with<'a, T, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (T, Option<ERR>)
+  where
+    T: ExtendWithCapacity<OK>,
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    let bound = crate::common::get_max_hint_bound(self.size_hint());
+    let mut container = T::with_capacity(capacity(bound).max(bound));
+    for item in self {
+      match item.try_extract() {
+        Ok(value) => container.extend(core::iter::once(value)),
+        Err(e) => return (container, Some(e)),
+      }
+    }
+    (container, None)
+  }
+
+  /// Like [try_collect_with()](TryCollectWith::try_collect_with) for
+  /// `Option` items, failing with [`NoneError`](crate::NoneError) instead of
+  /// `()`.
+  ///
+  /// `NoneError` implements [`Error`](core::error::Error), so the result can
+  /// be propagated with `?` into `Box<dyn Error>` or `anyhow::Result`, and it
+  /// does not trip `clippy::result_unit_err` in public signatures.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use std::error::Error;
+  ///
+  /// use collect_with::TryCollectWith;
+  ///
+  /// fn ports(args: &[&str]) -> Result<Vec<u16>, Box<dyn Error>> {
+  ///   let ports = args
+  ///     .iter()
+  ///     .map(|a| a.strip_prefix("--port="))
+  ///     .try_collect_with_none_err::<Vec<_>, _>(|n| n)?;
+  ///   Ok(ports.into_iter().map(str::parse).collect::<Result<_, _>>()?)
+  /// }
+  ///
+  /// assert_eq!(ports(&["--port=80", "--port=443"]).unwrap(), [80, 443]);
+  /// let err = ports(&["--port=80", "-v"]).unwrap_err();
+  /// assert_eq!(err.to_string(), "encountered a `None` item");
+  /// ```
+  fn try_collect_with_none_err<'a, T, OK>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<T, crate::NoneError>
+  where
+    T: ExtendWithCapacity<OK>,
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ()>,
+  {
+    try_collect_iterator(self, false, capacity).map_err(crate::NoneError::from)
+  }
+
+  /// Attempt to collect iterator elements into a `Box<[OK]>`.
+  ///
+  /// See
+  /// [collect_boxed_slice_with()](crate::CollectVector::collect_boxed_slice_with)
+  /// for when the final conversion reallocates.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let ids = ["3", "1", "2"]
+  ///   .into_iter()
+  ///   .map(str::parse::<u32>)
+  ///   .try_collect_boxed_slice_with(|n| n)
+  ///   .unwrap();
+  /// assert_eq!(*ids, [3, 1, 2]);
+  /// ```
+  #[cfg(feature = "collect_vec")]
+  fn try_collect_boxed_slice_with<'a, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<alloc::boxed::Box<[OK]>, ERR>
+  where
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    try_collect_iterator::<Vec<_>, _, _, _>(self, false, capacity)
+      .map(Vec::into_boxed_slice)
+  }
+
+  /// Collects the `Continue` payloads of a state machine, stopping at the
+  /// first `Break` and returning its payload alongside the collection.
+  ///
+  /// This is [collect_until_err_with()](TryCollectWith::collect_until_err_with)
+  /// specialized to [`ControlFlow`], for pipelines where `Break` carries a
+  /// summary rather than an error.
+  ///
+  /// ## Example
+  ///
+  /// ```rust
+  /// use core::ops::ControlFlow;
+  ///
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let tokens = ["let", "x", "=", "1", ";", "rest"]
+  ///   .into_iter()
+  ///   .map(|t| match t {
+  ///     ";" => ControlFlow::Break("end of statement"),
+  ///     t => ControlFlow::Continue(t),
+  ///   });
+  /// let (stmt, stop) = tokens.try_collect_until_break_with::<Vec<_>, _, _>(|n| n);
+  /// assert_eq!(stmt, ["let", "x", "=", "1"]);
+  /// assert_eq!(stop, Some("end of statement"));
+  /// ```
+  fn try_collect_until_break_with<T, B, C>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> (T, Option<B>)
+  where
+    T: ExtendWithCapacity<C>,
+    Self: Sized + Iterator<Item = core::ops::ControlFlow<B, C>>,
+  {
+    self.collect_until_err_with(capacity)
+  }
+
+  /// Attempts to flatten fallible arrays `[T; N]` into a `Vec<T>`,
+  /// short-circuiting on the first error.
+  ///
+  /// The `try_` counterpart of
+  /// [collect_flat_arrays_with()](crate::CollectVector::collect_flat_arrays_with):
+  /// the `Vec` is pre-sized to `size_bound * N` (saturating) elements.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let frames = [Ok([1, 2]), Ok([3, 4])]
+  ///   .into_iter()
+  ///   .try_collect_flat_arrays_with::<u8, (), 2>();
+  /// assert_eq!(frames, Ok(vec![1, 2, 3, 4]));
+  ///
+  /// let err = [Ok([1, 2]), Err("truncated")]
+  ///   .into_iter()
+  ///   .try_collect_flat_arrays_with();
+  /// assert_eq!(err, Err("truncated"));
+  /// ```
+  #[cfg(feature = "collect_vec")]
+  fn try_collect_flat_arrays_with<'a, T, ERR, const N: usize>(
+    self,
+  ) -> Result<Vec<T>, ERR>
+  where
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = [T; N], Err = ERR>,
+  {
+    let bound = crate::common::get_max_hint_bound(self.size_hint());
+    let mut vec = Vec::with_capacity(bound.saturating_mul(N));
+    for item in self {
+      vec.extend(item.try_extract()?);
+    }
+    Ok(vec)
+  }
+
+  /// Attempts to collect into an inner container and wrap it, short-circuiting
+  /// on the first error.
+  ///
+  /// The `try_` counterpart of
+  /// [collect_wrapped_with()](crate::CollectWith::collect_wrapped_with);
+  /// `wrap` only runs on success.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::TryCollectWith;
+  ///
+  /// struct Ports(Vec<u16>);
+  ///
+  /// let ports = ["80", "443"]
+  ///   .into_iter()
+  ///   .map(str::parse::<u16>)
+  ///   .try_collect_wrapped_with(|n| n, Ports)
+  ///   .unwrap();
+  /// assert_eq!(ports.0, [80, 443]);
+  /// ```
+  fn try_collect_wrapped_with<'a, W, C, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    wrap: impl FnOnce(C) -> W,
+  ) -> Result<W, ERR>
+  where
+    C: ExtendWithCapacity<OK>,
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    try_collect_iterator(self, false, capacity).map(wrap)
+  }
+
+  /// Attempts to collect the first halves of fallible pairs into a `Vec`,
+  /// short-circuiting on the first error.
+  ///
+  /// See [collect_keys_vec_with()](crate::CollectVector::collect_keys_vec_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let keys = [Some(("a", 1)), Some(("b", 2))]
+  ///   .into_iter()
+  ///   .try_collect_keys_vec_with(|n| n);
+  /// assert_eq!(keys, Ok(vec!["a", "b"]));
+  /// ```
+  #[cfg(feature = "collect_vec")]
+  fn try_collect_keys_vec_with<'a, K, V, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<Vec<K>, ERR>
+  where
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = (K, V), Err = ERR>,
+  {
+    self
+      .map(|item| {
+        item
+          .try_extract()
+          .map(|(k, _)| k)
+      })
+      .try_collect_with(capacity)
+  }
+
+  /// Attempts to collect the second halves of fallible pairs into a `Vec`,
+  /// short-circuiting on the first error.
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let err = [Ok(("a", 1)), Err("bad row")]
+  ///   .into_iter()
+  ///   .try_collect_values_vec_with(|n| n);
+  /// assert_eq!(err, Err("bad row"));
+  /// ```
+  #[cfg(feature = "collect_vec")]
+  fn try_collect_values_vec_with<'a, K, V, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<Vec<V>, ERR>
+  where
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = (K, V), Err = ERR>,
+  {
+    self
+      .map(|item| {
+        item
+          .try_extract()
+          .map(|(_, v)| v)
+      })
+      .try_collect_with(capacity)
+  }
+
+  /// Attempts to collect elements, summarizing every failure instead of
+  /// stopping at the first one.
+  ///
+  /// The whole iterator is consumed. Failed items are counted, and the first
+  /// `max_examples` errors are kept with their item index; the example `Vec`
+  /// is allocated with room for exactly `max_examples` when the first error
+  /// is seen. Once an item has failed, the successful ones are no longer
+  /// collected. The result is `Ok` only if no item failed.
+  ///
+  /// The capacity rule is that of
+  /// [try_collect_with()](TryCollectWith::try_collect_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::TryCollectWith;
+  ///
+  /// let rows = ["1", "x", "3", "y", "z"];
+  /// let summary = rows
+  ///   .into_iter()
+  ///   .map(str::parse::<u8>)
+  ///   .try_collect_with_error_summary::<Vec<_>, _, _>(|n| n, 2)
+  ///   .unwrap_err();
+  /// assert_eq!(summary.total_errors, 3);
+  /// assert_eq!(summary.first_index, 1);
+  /// assert_eq!(summary.examples.len(), 2);
+  /// assert_eq!(summary.examples[1].0, 3);
+  /// ```
+  fn try_collect_with_error_summary<'a, T, OK, ERR>(
+    self,
+    capacity: impl FnOnce(usize) -> usize,
+    max_examples: usize,
+  ) -> Result<T, ErrorSummary<ERR>>
+  where
+    T: ExtendWithCapacity<OK>,
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    let bound = crate::common::get_max_hint_bound(self.size_hint());
+    let mut container = T::with_capacity(capacity(bound).max(bound));
+    let mut summary: Option<ErrorSummary<ERR>> = None;
+
+    for (index, item) in self.enumerate() {
+      match (item.try_extract(), &mut summary) {
+        (Ok(value), None) => container.extend(core::iter::once(value)),
+        (Ok(_), Some(_)) => {}
+        (Err(e), None) => {
+          let mut examples = Vec::with_capacity(max_examples);
+          if max_examples > 0 {
+            examples.push((index, e));
+          }
+          summary = Some(ErrorSummary {
+            total_errors: 1,
+            first_index: index,
+            examples,
+          });
+        }
+        (Err(e), Some(s)) => {
+          s.total_errors += 1;
+          if s.examples.len() < max_examples {
+            s.examples.push((index, e));
+          }
+        }
+      }
+    }
+
+    match summary {
+      Some(s) => Err(s),
+      None => Ok(container),
+    }
+  }
+
+  /// Attempts to collect elements, returning every error instead of the first
+  /// one.
+  ///
+  /// - `ok_capacity`
+  ///   - Closure for the success container, with the rule of
+  ///     [try_collect_with()](TryCollectWith::try_collect_with)
+  /// - `err_capacity`
+  ///   - Closure that receives the same size bound and returns the capacity of
+  ///     the error `Vec`, used as is
+  ///
+  /// The whole iterator is consumed. The error `Vec` is allocated when the
+  /// first error is seen, and from then on successful items are dropped. The
+  /// result is `Ok` only if no item failed, so it composes with `?` like
+  /// [try_collect_with()](TryCollectWith::try_collect_with).
+  ///
+  /// ## Example
+  ///
+  /// ```
+  /// use collect_with::TryCollectWith;
+  ///
+  /// fn check(fields: &[&str]) -> Result<Vec<u8>, Vec<String>> {
+  ///   fields
+  ///     .iter()
+  ///     .map(|f| f.parse::<u8>().map_err(|_| format!("invalid field: {f}")))
+  ///     .try_collect_all_errors_with(|n| n, |_| 4)
+  /// }
+  ///
+  /// assert_eq!(check(&["1", "2"]), Ok(vec![1, 2]));
+  ///
+  /// let errors = check(&["1", "x", "y"]).unwrap_err();
+  /// assert_eq!(errors, ["invalid field: x", "invalid field: y"]);
+  /// ```
+  fn try_collect_all_errors_with<'a, T, OK, ERR>(
+    self,
+    ok_capacity: impl FnOnce(usize) -> usize,
+    err_capacity: impl FnOnce(usize) -> usize,
+  ) -> Result<T, Vec<ERR>>
+  where
+    T: ExtendWithCapacity<OK>,
+    Self: Sized,
+    Self::Item: TryExtract<'a, Ok = OK, Err = ERR>,
+  {
+    let bound = crate::common::get_max_hint_bound(self.size_hint());
+    let mut container = T::with_capacity(ok_capacity(bound).max(bound));
+    let err_capacity = err_capacity(bound);
+    let mut errors: Option<Vec<ERR>> = None;
+
+    for item in self {
+      match (item.try_extract(), &mut errors) {
+        (Ok(value), None) => container.extend(core::iter::once(value)),
+        (Ok(_), Some(_)) => {}
+        (Err(e), None) => {
+          let mut list = Vec::with_capacity(err_capacity);
+          list.push(e);
+          errors = Some(list);
+        }
+        (Err(e), Some(list)) => list.push(e),
+      }
+    }
+
+    match errors {
+      Some(list) => Err(list),
+      None => Ok(container),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{FusedProbe, HintedIter};
+
+  #[test]
+  fn try_collect_with_hints() {
+    let v = HintedIter::exact([Some(1), Some(2)].into_iter(), 2)
+      .try_collect_with::<Vec<_>, _, _>(|n| n + 1)
+      .unwrap();
+    assert_eq!(v, [1, 2]);
+    assert_eq!(v.capacity(), 3);
+
+    let v = HintedIter::bounds([Ok::<_, ()>(1)].into_iter(), 0, None)
+      .try_collect_with::<Vec<_>, _, _>(|n| n)
+      .unwrap();
+    assert_eq!(v, [1]);
+  }
+
+  #[test]
+  fn try_collect_with_short_circuits() {
+    let mut probe = FusedProbe::new([Ok(1), Err("bad"), Ok(3)].into_iter());
+    let result = (&mut probe).try_collect_with::<Vec<_>, _, _>(|n| n);
+    assert_eq!(result, Err("bad"));
+    assert_eq!(probe.next_calls(), 2);
+    assert_eq!(probe.next(), Some(Ok(3)));
+  }
+
+  #[test]
+  fn try_collect_with_fallback_hints() {
+    // (0, None): the fallback is used as is.
+    let v = HintedIter::bounds([Ok::<_, ()>(7), Ok(8)].into_iter(), 0, None)
+      .try_collect_with_fallback::<Vec<_>, _, _>(5)
+      .unwrap();
+    assert_eq!(v, [7, 8]);
+    assert_eq!(v.capacity(), 5);
+
+    // (3, Some(3)): the bound wins over the fallback.
+    let v = HintedIter::exact([Some(1), Some(2), Some(3)].into_iter(), 3)
+      .try_collect_with_fallback::<Vec<_>, _, _>(16)
+      .unwrap();
+    assert_eq!(v.capacity(), 3);
+
+    let err = [Some(1), None]
+      .into_iter()
+      .try_collect_with_fallback::<Vec<_>, _, _>(16);
+    assert_eq!(err, Err(()));
+  }
+
+  #[test]
+  fn try_collect_vec_from_option_refs() {
+    let items = [Some(1u32), Some(2), Some(3)];
+    let v = items
+      .iter()
+      .try_collect_copied_with::<Vec<u32>, _, _>(|n| n);
+    assert_eq!(v, Ok(alloc::vec![1, 2, 3]));
+
+    let borrowed = items
+      .iter()
+      .try_collect_with::<Vec<_>, _, _>(|n| n);
+    assert_eq!(borrowed, Ok(alloc::vec![&1, &2, &3]));
+
+    let items = [Some(1u32), None];
+    let v = items
+      .iter()
+      .try_collect_copied_with::<Vec<u32>, _, _>(|n| n);
+    assert_eq!(v, Err(()));
+  }
+
+  #[test]
+  fn try_collect_deque_from_result_refs() {
+    use alloc::collections::VecDeque;
+
+    let items: [Result<u8, &str>; 3] = [Ok(1), Ok(2), Ok(3)];
+    let deque = items
+      .iter()
+      .try_collect_copied_with::<VecDeque<u8>, _, _>(|n| n);
+    assert_eq!(deque, Ok(VecDeque::from([1, 2, 3])));
+
+    let items: [Result<u8, &str>; 2] = [Ok(1), Err("bad")];
+    let deque = items
+      .iter()
+      .try_collect_copied_with::<VecDeque<u8>, _, _>(|n| n);
+    assert_eq!(deque, Err(&"bad"));
+  }
+
+  #[test]
+  fn try_collect_cow() {
+    use alloc::borrow::Cow;
+
+    let v = ["1", "2"]
+      .into_iter()
+      .map(str::parse::<u8>)
+      .try_collect_cow_with::<[u8], _, _>(|n| n);
+    assert!(matches!(v, Ok(Cow::Owned(_))));
+    assert_eq!(*v.unwrap(), [1, 2]);
+
+    let v = [Some('a'), None]
+      .into_iter()
+      .try_collect_cow_with::<str, _, _>(|n| n);
+    assert_eq!(v, Err(()));
+  }
+
+  #[cfg(feature = "std")]
+  #[test]
+  fn try_collect_with_timed_unchanged_result() {
+    let start = std::time::Instant::now();
+    let (v, elapsed) = (0..50_000u32)
+      .map(Some)
+      .try_collect_with_timed::<Vec<_>, _, _>(|n| n);
+    assert!(elapsed <= start.elapsed());
+    let v = v.unwrap();
+    assert_eq!(v.len(), 50_000);
+    assert_eq!(v.capacity(), 50_000);
+  }
+
+  #[cfg(feature = "collect_vec")]
+  #[test]
+  fn try_collect_flat_bytes() {
+    #[derive(Debug, PartialEq)]
+    struct FrameError;
+
+    let frames: [Result<&[u8], FrameError>; 3] =
+      [Ok(&[1, 2]), Ok(&[3]), Ok(&[4, 5, 6])];
+    let buf = frames
+      .into_iter()
+      .try_collect_flat_bytes_with(|n| n * 2);
+    assert_eq!(buf.as_deref(), Ok(&[1, 2, 3, 4, 5, 6][..]));
+
+    let frames: [Result<&[u8], FrameError>; 4] =
+      [Ok(&[1]), Err(FrameError), Ok(&[2]), Ok(&[3])];
+    let mut iter = frames.into_iter();
+    let buf = (&mut iter).try_collect_flat_bytes_with(|n| n);
+    assert_eq!(buf, Err(FrameError));
+    // The chunks after the error are still in the iterator.
+    assert_eq!(iter.len(), 2);
+
+    let frames: [Result<&[u8], FrameError>; 2] = [Ok(&[]), Ok(&[])];
+    let buf = frames
+      .into_iter()
+      .try_collect_flat_bytes_with(|_| 0)
+      .unwrap();
+    assert!(buf.is_empty());
+    // owned chunks work too
+    let buf = [Some(alloc::vec![7u8]), Some(alloc::vec![8])]
+      .into_iter()
+      .try_collect_flat_bytes_with(|n| n);
+    assert_eq!(buf.as_deref(), Ok(&[7, 8][..]));
+  }
+
+  #[test]
+  fn try_collect_with_counted_positions() {
+    let (v, consumed) = ["1", "2", "3"]
+      .into_iter()
+      .map(str::parse::<u8>)
+      .try_collect_with_counted::<Vec<_>, _, _>(|n| n)
+      .unwrap();
+    assert_eq!(v, [1, 2, 3]);
+    assert_eq!(consumed, 3);
+
+    let input = [Some(1), Some(2), Some(3), None, Some(5), None];
+    let mut probe = FusedProbe::new(input.into_iter());
+    let ((), consumed) = (&mut probe)
+      .try_collect_with_counted::<Vec<_>, _, _>(|n| n)
+      .unwrap_err();
+    // The failing `None` is at index 3.
+    assert_eq!(consumed, 4);
+    assert_eq!(probe.next_calls(), 4);
+
+    // Resume from the shared iterator: the next run starts at index 4.
+    let ((), consumed) = (&mut probe)
+      .try_collect_with_counted::<Vec<_>, _, _>(|n| n)
+      .unwrap_err();
+    assert_eq!(consumed, 2);
+    assert!(probe.next().is_none());
+  }
+
+  #[test]
+  fn collect_until_err_stop_reasons() {
+    let (v, stop) = [Ok::<_, &str>(1), Ok(2)]
+      .into_iter()
+      .collect_until_err_with::<Vec<_>, _, _>(|n| n + 1);
+    assert_eq!(v, [1, 2]);
+    assert_eq!(v.capacity(), 3);
+    assert_eq!(stop, None);
+
+    let (v, stop) = [Err("first"), Ok(2)]
+      .into_iter()
+      .collect_until_err_with::<Vec<i32>, _, _>(|n| n);
+    assert!(v.is_empty());
+    assert_eq!(stop, Some("first"));
+
+    let mut probe = FusedProbe::new([Ok(1), Ok(2), Err("mid"), Ok(4)].into_iter());
+    let (v, stop) = (&mut probe).collect_until_err_with::<Vec<_>, _, _>(|n| n);
+    assert_eq!(v, [1, 2]);
+    assert_eq!(stop, Some("mid"));
+    assert_eq!(probe.next_calls(), 3);
+  }
+
+  #[test]
+  fn try_collect_with_none_err_reports() {
+    use crate::NoneError;
+
+    let v = [Some(1), Some(2)]
+      .iter()
+      .try_collect_with_none_err::<Vec<&i32>, _>(|n| n)
+      .unwrap();
+    assert_eq!(v, [&1, &2]);
+
+    let mut probe = FusedProbe::new([Some('a'), None, Some('c')].into_iter());
+    let err =
+      (&mut probe).try_collect_with_none_err::<alloc::string::String, _>(|n| n);
+    assert_eq!(err, Err(NoneError));
+    assert_eq!(probe.next_calls(), 2);
+  }
+
+  #[cfg(feature = "collect_vec")]
+  #[test]
+  fn try_collect_boxed_slice() {
+    let boxed = [Ok::<_, ()>(1u8), Ok(2)]
+      .into_iter()
+      .try_collect_boxed_slice_with(|n| n);
+    assert_eq!(boxed.as_deref(), Ok(&[1, 2][..]));
+
+    let err = [Some(1), None]
+      .into_iter()
+      .try_collect_boxed_slice_with(|n| n);
+    assert_eq!(err, Err(()));
+  }
+
+  #[test]
+  fn try_collect_until_break_payloads() {
+    use core::ops::ControlFlow::{self, Break, Continue};
+
+    let (v, stop) = [Continue(1), Continue(2)]
+      .into_iter()
+      .try_collect_until_break_with::<Vec<_>, (), _>(|n| n);
+    assert_eq!(v, [1, 2]);
+    assert_eq!(stop, None);
+
+    let (v, stop) = [Break("first"), Continue(2)]
+      .into_iter()
+      .try_collect_until_break_with::<Vec<i32>, _, _>(|n| n);
+    assert!(v.is_empty());
+    assert_eq!(stop, Some("first"));
+
+    let steps: [ControlFlow<usize, char>; 5] = [
+      Continue('a'),
+      Continue('b'),
+      Break(2),
+      Continue('c'),
+      Break(3),
+    ];
+    let mut probe = FusedProbe::new(steps.into_iter());
+    let (prefix, stop) = (&mut probe)
+      .try_collect_until_break_with::<alloc::string::String, _, _>(|n| n);
+    assert_eq!(prefix, "ab");
+    assert_eq!(stop, Some(2));
+    assert_eq!(probe.next(), Some(Continue('c')));
+  }
+
+  #[cfg(feature = "collect_vec")]
+  #[test]
+  fn try_collect_flat_arrays() {
+    let v = (0..3)
+      .map(|i| Some([i, -i]))
+      .try_collect_flat_arrays_with();
+    assert_eq!(v.as_deref(), Ok(&[0, 0, 1, -1, 2, -2][..]));
+    assert_eq!(v.unwrap().capacity(), 6);
+
+    let err = [Ok([1u8; 0]), Err('x')]
+      .into_iter()
+      .try_collect_flat_arrays_with();
+    assert_eq!(err, Err('x'));
+  }
+
+  #[test]
+  fn try_collect_wrapped_with() {
+    struct Names(Vec<char>);
+
+    let ok = [Some('a'), Some('b')]
+      .into_iter()
+      .try_collect_wrapped_with(|n| n + 2, Names)
+      .unwrap();
+    assert_eq!(ok.0, ['a', 'b']);
+    assert_eq!(ok.0.capacity(), 4);
+
+    let mut wrapped = false;
+    let err = [Ok('a'), Err(7)]
+      .into_iter()
+      .try_collect_wrapped_with(
+        |n| n,
+        |v: Vec<char>| {
+          wrapped = true;
+          Names(v)
+        },
+      );
+    assert!(matches!(err, Err(7)));
+    assert!(!wrapped);
+  }
+
+  #[cfg(feature = "collect_vec")]
+  #[test]
+  fn try_collect_keys_and_values() {
+    let rows = || [Ok::<_, &str>((1, 'a')), Ok((2, 'b'))].into_iter();
+    let keys = rows()
+      .try_collect_keys_vec_with(|n| n + 2)
+      .unwrap();
+    assert_eq!(keys, [1, 2]);
+    assert_eq!(keys.capacity(), 4);
+    assert_eq!(
+      rows().try_collect_values_vec_with(|n| n),
+      Ok(alloc::vec!['a', 'b'])
+    );
+
+    let err = [Ok((1, 'a')), Err("x"), Ok((3, 'c'))]
+      .into_iter()
+      .try_collect_keys_vec_with(|n| n);
+    assert_eq!(err, Err("x"));
+  }
+
+  #[test]
+  fn error_summary_counts_and_truncates() {
+    let ok = (0..5)
+      .map(Ok::<_, ()>)
+      .try_collect_with_error_summary::<Vec<_>, _, _>(|n| n, 3)
+      .unwrap();
+    assert_eq!(ok, [0, 1, 2, 3, 4]);
+    assert_eq!(ok.capacity(), 5);
+
+    fn parse(items: &[&'static str]) -> Result<Vec<u8>, ErrorSummary<&'static str>> {
+      items
+        .iter()
+        .map(|s| s.parse::<u8>().map_err(|_| *s))
+        .try_collect_with_error_summary(|n| n, 3)
+    }
+
+    let few = parse(&["1", "a", "2", "b"]).unwrap_err();
+    assert_eq!(few.total_errors, 2);
+    assert_eq!(few.first_index, 1);
+    assert_eq!(few.examples, [(1, "a"), (3, "b")]);
+    assert_eq!(few.examples.capacity(), 3);
+
+    let many = parse(&["a", "b", "1", "c", "d", "e"]).unwrap_err();
+    assert_eq!(many.total_errors, 5);
+    assert_eq!(many.first_index, 0);
+    assert_eq!(many.examples, [(0, "a"), (1, "b"), (3, "c")]);
+    assert_eq!(
+      alloc::string::ToString::to_string(&many),
+      "5 failed items, first at index 0: a"
+    );
+
+    let zero = ["x", "y"]
+      .iter()
+      .map(|s| s.parse::<u8>())
+      .try_collect_with_error_summary::<Vec<_>, _, _>(|n| n, 0)
+      .unwrap_err();
+    assert_eq!(zero.total_errors, 2);
+    assert!(zero.examples.is_empty());
+  }
+
+  #[test]
+  fn try_collect_all_errors() {
+    let ok = HintedIter::bounds((0..4).map(Ok::<_, char>), 0, None)
+      .try_collect_all_errors_with::<Vec<_>, _, _>(|_| 10, |_| 3)
+      .unwrap();
+    assert_eq!(ok, [0, 1, 2, 3]);
+    assert_eq!(ok.capacity(), 10);
+
+    let one = [Ok(1), Err('a'), Ok(2)]
+      .into_iter()
+      .try_collect_all_errors_with::<Vec<_>, _, _>(|n| n, |n| n / 2);
+    let errors = one.unwrap_err();
+    assert_eq!(errors, ['a']);
+    assert_eq!(errors.capacity(), 1);
+
+    let mut seen = None;
+    let many = [Err('a'), Ok(1), Err('b'), Err('c'), Ok(2)]
+      .into_iter()
+      .try_collect_all_errors_with::<Vec<_>, _, _>(
+        |n| n,
+        |n| {
+          seen = Some(n);
+          8
+        },
+      )
+      .unwrap_err();
+    assert_eq!(many, ['a', 'b', 'c']);
+    assert_eq!(many.capacity(), 8);
+    assert_eq!(seen, Some(5));
+
+    // A zero error capacity still keeps every error.
+    let mut probe =
+      FusedProbe::new((0..6).map(|i| if i % 2 == 0 { Err(i) } else { Ok(i) }));
+    let all = (&mut probe)
+      .try_collect_all_errors_with::<Vec<_>, _, _>(|n| n, |_| 0)
+      .unwrap_err();
+    assert_eq!(all, [0, 2, 4]);
+    assert!(probe.is_exhausted());
+  }
+
+  #[cfg(feature = "collect_vec")]
+  #[test]
+  fn try_collect_polled_results() {
+    use core::task::Poll;
+
+    use crate::PollError;
+
+    let ready = [Poll::Ready(Ok::<_, &str>(1)), Poll::Ready(Ok(2))]
+      .into_iter()
+      .try_collect_vec_with(|n| n);
+    assert_eq!(ready, Ok(alloc::vec![1, 2]));
+
+    let failed = [Poll::Ready(Ok(1)), Poll::Ready(Err("io")), Poll::Pending]
+      .into_iter()
+      .try_collect_vec_with(|n| n);
+    assert_eq!(failed, Err(PollError::Inner("io")));
+
+    let mut probe = FusedProbe::new(
+      [
+        Poll::Ready(Ok::<_, ()>(1)),
+        Poll::Pending,
+        Poll::Ready(Ok(3)),
+      ]
+      .into_iter(),
+    );
+    let pending = (&mut probe).try_collect_with::<Vec<_>, _, _>(|n| n);
+    assert_eq!(pending, Err(PollError::Pending));
+    // Short-circuits on the pending item.
+    assert_eq!(probe.next_calls(), 2);
+    assert_eq!(probe.next(), Some(Poll::Ready(Ok(3))));
+
+    let polled = [Poll::Ready(Ok(1)), Poll::Ready(Err('e'))];
+    let borrowed = polled[..1]
+      .iter()
+      .try_collect_vec_with(|n| n);
+    assert_eq!(borrowed, Ok(alloc::vec![&1]));
+    let err = polled
+      .iter()
+      .try_collect_vec_with(|n| n);
+    assert_eq!(err, Err(PollError::Inner(&'e')));
+    let pending = [Poll::<Result<u8, ()>>::Pending]
+      .iter()
+      .try_collect_vec_with(|n| n);
+    assert_eq!(pending, Err(PollError::Pending));
+  }
 }
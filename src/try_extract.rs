@@ -1,4 +1,6 @@
-use core::ops::ControlFlow;
+use core::{ops::ControlFlow, task::Poll};
+
+use crate::PollError;
 
 /// A trait for uniformly extracting success/error values from various container
 /// types.
@@ -9,6 +11,7 @@ use core::ops::ControlFlow;
 /// - [`Result`] (by value and by reference)
 /// - [`Option`] (by value and by reference)
 /// - [`ControlFlow`] (by value and by reference)
+/// - [`Poll<Result>`](Poll) (by value and by reference)
 ///
 /// The conversion preserves semantic meanings:
 /// - Success cases map to `Ok`
@@ -115,3 +118,41 @@ impl<'a, C, B> TryExtract<'a> for &'a ControlFlow<B, C> {
     }
   }
 }
+
+// Poll implementations ---------------------------------------------
+
+impl<T, E> TryExtract<'_> for Poll<Result<T, E>> {
+  type Ok = T;
+  type Err = PollError<E>;
+
+  /// Converts a polled Result into a Result:
+  /// - Ready(Ok(t)) => Ok(t)
+  /// - Ready(Err(e)) => Err(PollError::Inner(e))
+  /// - Pending => Err(PollError::Pending)
+  #[inline]
+  fn try_extract(self) -> Result<Self::Ok, Self::Err> {
+    match self {
+      Poll::Ready(result) => result.map_err(PollError::Inner),
+      Poll::Pending => Err(PollError::Pending),
+    }
+  }
+}
+
+impl<'a, T, E> TryExtract<'a> for &'a Poll<Result<T, E>> {
+  type Ok = &'a T;
+  type Err = PollError<&'a E>;
+
+  /// Extracts references from a polled Result:
+  /// - Ready(Ok(t)) => Ok(&t)
+  /// - Ready(Err(e)) => Err(PollError::Inner(&e))
+  /// - Pending => Err(PollError::Pending)
+  #[inline]
+  fn try_extract(self) -> Result<Self::Ok, Self::Err> {
+    match self {
+      Poll::Ready(result) => result
+        .as_ref()
+        .map_err(PollError::Inner),
+      Poll::Pending => Err(PollError::Pending),
+    }
+  }
+}
@@ -0,0 +1,68 @@
+#![cfg(all(feature = "collect_vec", feature = "test-util"))]
+
+use std::{
+  alloc::{GlobalAlloc, Layout, System},
+  cell::Cell,
+};
+
+use collect_with::{CollectVector, HintedIter};
+
+/// Counts the reallocations made by the current thread, so that tests running
+/// in parallel do not see each other's.
+struct CountingAlloc;
+
+thread_local! {
+  static REALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    unsafe { System.alloc(layout) }
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    unsafe { System.dealloc(ptr, layout) }
+  }
+
+  unsafe fn realloc(
+    &self,
+    ptr: *mut u8,
+    layout: Layout,
+    new_size: usize,
+  ) -> *mut u8 {
+    REALLOCS.with(|n| n.set(n.get() + 1));
+    unsafe { System.realloc(ptr, layout, new_size) }
+  }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+fn reallocs_during<T>(f: impl FnOnce() -> T) -> (T, usize) {
+  let before = REALLOCS.with(Cell::get);
+  let out = f();
+  (out, REALLOCS.with(Cell::get) - before)
+}
+
+#[test]
+fn exact_hint_boxes_without_reallocating() {
+  let (boxed, reallocs) =
+    reallocs_during(|| HintedIter::exact(0..16, 16).collect_boxed_slice_with(|n| n));
+  assert!(boxed.iter().copied().eq(0..16));
+  assert_eq!(reallocs, 0);
+
+  let (boxed, reallocs) = reallocs_during(|| {
+    HintedIter::bounds(0..5, 0, None).collect_boxed_slice_with_exact(|_| 5)
+  });
+  assert_eq!(*boxed, [0, 1, 2, 3, 4]);
+  assert_eq!(reallocs, 0);
+}
+
+#[test]
+fn headroom_is_shrunk_away() {
+  let (boxed, reallocs) = reallocs_during(|| {
+    HintedIter::bounds(0..5, 0, None).collect_boxed_slice_with(|_| 32)
+  });
+  assert_eq!(*boxed, [0, 1, 2, 3, 4]);
+  assert_eq!(reallocs, 1);
+}